@@ -1,5 +1,5 @@
 from os import PathLike
-from typing import Any, ClassVar, Dict, List, Optional, Tuple
+from typing import Any, ClassVar, Dict, List, Optional, Tuple, Union
 
 #########
 ## ABI ##
@@ -25,16 +25,237 @@ class TransactionExecutor:
         check_signature: Optional[bool] = None,
     ) -> None: ...
     def execute(
-        self, message: Message, account: Optional[AccountState] = None
+        self,
+        message: Message,
+        account: Optional[AccountState] = None,
+        overrides: Optional[ExecutionOverrides] = None,
     ) -> Tuple[Transaction, Optional[AccountState]]:
         """
         Executes the specified message on account state.
 
         :param message: message to execute.
         :param account: account state. (`None` for non-existing).
+        :param overrides: optional block params and VM flags.
+        """
+        ...
+
+    def execute_traced(
+        self,
+        message: Message,
+        account: Optional[AccountState] = None,
+        overrides: Optional[ExecutionOverrides] = None,
+        trace: Optional[TraceOptions] = None,
+    ) -> Tuple[Transaction, Optional[AccountState], List[VmStep]]:
+        """
+        Same as `execute`, but also collects all VM steps of the compute phase.
+        Accepts the same parameters.
+        On failure the collected steps are attached to the error as `trace`.
+
+        :param trace: optional trace collection options.
+        """
+        ...
+
+    def execute_tick_tock(
+        self,
+        account: AccountState,
+        kind: TransactionType,
+        overrides: Optional[ExecutionOverrides] = None,
+    ) -> Tuple[Transaction, Optional[AccountState]]:
+        """
+        Runs a tick or tock transaction on the special account.
+
+        :param account: special account state with the matching `StateInit.special` flag.
+        :param kind: `TransactionType.Tick` or `TransactionType.Tock`.
+        :param overrides: optional block params and VM flags.
+        """
+        ...
+
+    def execute_tree(
+        self,
+        message: Message,
+        accounts: Dict[Address, Optional[AccountState]],
+        max_messages: Optional[int] = None,
+        overrides: Optional[ExecutionOverrides] = None,
+    ) -> TreeExecutionOutput:
+        """
+        Executes the message and delivers all produced internal messages
+        to the specified accounts in the order of their logical time.
+        Messages to other accounts are not delivered.
+
+        :param message: root message to execute.
+        :param accounts: known account states. (`None` for non-existing).
+        :param max_messages: max number of delivered internal messages. (10000 by default).
+            Remaining messages are returned as pending.
+        :param overrides: optional block params and VM flags shared by all transactions.
+            `block_lt` is used only for the root transaction.
+        :return: a tree of executed transactions, final account states and undelivered messages.
         """
         ...
 
+class ExecutionOverrides:
+    """
+    Block params and VM flags which override the executor defaults.
+
+    :param block_lt: optional block logical time. (account `last_trans_lt + 10` by default).
+    :param block_unixtime: optional block timestamp. (clock time by default).
+    :param seed_block: optional 32-byte random seed. (random by default).
+    :param gas_limit: optional gas limit which overrides ConfigParam20 and ConfigParam21.
+    :param behavior_modifiers: optional VM behavior flags.
+    """
+
+    def __init__(
+        self,
+        block_lt: Optional[int] = None,
+        block_unixtime: Optional[int] = None,
+        seed_block: Optional[bytes] = None,
+        gas_limit: Optional[int] = None,
+        behavior_modifiers: Optional[BehaviorModifiers] = None,
+    ) -> None: ...
+    @property
+    def block_lt(self) -> Optional[int]:
+        """Block logical time."""
+        ...
+
+    @property
+    def block_unixtime(self) -> Optional[int]:
+        """Block timestamp."""
+        ...
+
+    @property
+    def seed_block(self) -> Optional[bytes]:
+        """32-byte random seed."""
+        ...
+
+    @property
+    def gas_limit(self) -> Optional[int]:
+        """Gas limit which overrides ConfigParam20 and ConfigParam21."""
+        ...
+
+    @property
+    def behavior_modifiers(self) -> Optional[BehaviorModifiers]:
+        """VM behavior flags."""
+        ...
+
+class TreeExecutionOutput:
+    """Result of the `TransactionExecutor.execute_tree`."""
+
+    @property
+    def tree(self) -> TransactionTree:
+        """A tree of executed transactions."""
+        ...
+
+    @property
+    def accounts(self) -> Dict[Address, Optional[AccountState]]:
+        """Final account states."""
+        ...
+
+    @property
+    def truncated(self) -> bool:
+        """Whether the tree was truncated by `max_messages`."""
+        ...
+
+    @property
+    def pending(self) -> List[Message]:
+        """Messages which were not delivered (to unknown accounts or after truncation)."""
+        ...
+
+    @property
+    def failed(self) -> List[Tuple[Message, ExecutionError]]:
+        """Messages which failed to execute with the corresponding errors."""
+        ...
+
+class VmStep:
+    """A single VM step."""
+
+    @property
+    def step(self) -> int:
+        """Step index."""
+        ...
+
+    @property
+    def kind(self) -> str:
+        """Trace entry kind (`Start`, `Normal`, `Implicit`, `Exception`, `Finish`, ...)."""
+        ...
+
+    @property
+    def instruction(self) -> str:
+        """Executed instruction mnemonic with its arguments, as decoded by the VM."""
+        ...
+
+    @property
+    def gas_used(self) -> int:
+        """Total gas used after this step."""
+        ...
+
+    @property
+    def gas_cmd(self) -> int:
+        """Gas used by this step (including cell loads)."""
+        ...
+
+    @property
+    def gas_remaining(self) -> int:
+        """Gas remaining after this step."""
+        ...
+
+    @property
+    def stack(self) -> List[str]:
+        """Up to 5 items from the top of the stack (top first)."""
+        ...
+
+    @property
+    def code_hash(self) -> bytes:
+        """Repr hash of the code cell which contains the instruction."""
+        ...
+
+    @property
+    def code_offset(self) -> int:
+        """Bit offset of the instruction in the code cell with `code_hash`."""
+        ...
+
+    @property
+    def cell_loads(self) -> List[bytes]:
+        """Repr hashes of cells loaded by this step."""
+        ...
+
+class TraceOptions:
+    """
+    VM trace collection options.
+
+    :param max_steps: optional max number of collected steps.
+    :param path: optional path to a file which receives steps as JSON lines
+        instead of collecting them in memory.
+    """
+
+    def __init__(
+        self,
+        max_steps: Optional[int] = None,
+        path: Optional[str | PathLike[str]] = None,
+    ) -> None: ...
+    @property
+    def max_steps(self) -> Optional[int]:
+        """Max number of collected steps."""
+        ...
+
+    @property
+    def path(self) -> Optional[str]:
+        """Path to the trace file."""
+        ...
+
+class BehaviorModifiers:
+    """
+    VM behavior flags applied on top of the executor defaults.
+
+    Other executor flags (e.g. the signature id) are derived from the blockchain
+    config and can't be overridden, so emulation stays consistent with the network.
+
+    :param chksig_always_succeed: whether signature checks in VM always succeed.
+    """
+
+    chksig_always_succeed: bool
+    """Whether signature checks in VM always succeed."""
+
+    def __init__(self, chksig_always_succeed: Optional[bool] = None) -> None: ...
+
 class ContractAbi:
     """
     Parsed contract ABI.
@@ -155,6 +376,9 @@ class ContractAbi:
         :param transaction: transaction to decode.
         """
         ...
+    def __copy__(self) -> ContractAbi: ...
+    def __deepcopy__(self, memo: Any) -> ContractAbi: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class FunctionAbi:
     """Parsed function ABI."""
@@ -194,6 +418,7 @@ class FunctionAbi:
         responsible: Optional[bool] = None,
         clock: Optional[Clock] = None,
         config: Optional[BlockchainConfig] = None,
+        trace: Optional[TraceOptions] = None,
     ) -> ExecutionOutput:
         """
         Runs this function as a getter.
@@ -202,6 +427,7 @@ class FunctionAbi:
         :param input: function intput.
         :param responsible: whether to run this getter as responsible.
         :param clock: optional clock to modify execution timestamp.
+        :param trace: optional trace options. Collects VM steps into `ExecutionOutput.trace`.
         """
         ...
 
@@ -312,6 +538,9 @@ class FunctionAbi:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> FunctionAbi: ...
+    def __deepcopy__(self, memo: Any) -> FunctionAbi: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class FunctionAbiWithArgs:
     """Parsed function ABI."""
@@ -332,6 +561,7 @@ class FunctionAbiWithArgs:
         responsible: Optional[bool] = None,
         clock: Optional[Clock] = None,
         config: Optional[BlockchainConfig] = None,
+        trace: Optional[TraceOptions] = None,
     ) -> ExecutionOutput:
         """
         Runs this function as a getter.
@@ -339,6 +569,7 @@ class FunctionAbiWithArgs:
         :param account_state: a state of existing account which will be used for execution.
         :param responsible: whether to run this getter as responsible.
         :param clock: optional clock to modify execution timestamp.
+        :param trace: optional trace options. Collects VM steps into `ExecutionOutput.trace`.
         """
         ...
 
@@ -422,6 +653,11 @@ class ExecutionOutput:
         """Parsed output in case of successful execution."""
         ...
 
+    @property
+    def trace(self) -> Optional[List[VmStep]]:
+        """VM steps, collected only for traced calls."""
+        ...
+
 class FunctionCall:
     """Parsed function call."""
 
@@ -489,6 +725,9 @@ class EventAbi:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> EventAbi: ...
+    def __deepcopy__(self, memo: Any) -> EventAbi: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class GetterAbi:
     """Parsed getter ABI."""
@@ -509,6 +748,7 @@ class GetterAbi:
         input: Dict,
         clock: Optional[Clock] = None,
         config: Optional[BlockchainConfig] = None,
+        trace: Optional[TraceOptions] = None,
     ) -> ExecutionOutput:
         """
         Runs this function as a getter.
@@ -516,6 +756,7 @@ class GetterAbi:
         :param account_state: a state of existing account which will be used for execution.
         :param input: function intput.
         :param clock: optional clock to modify execution timestamp.
+        :param trace: optional trace options. Collects VM steps into `ExecutionOutput.trace`.
         """
         ...
 
@@ -526,6 +767,9 @@ class GetterAbi:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> GetterAbi: ...
+    def __deepcopy__(self, memo: Any) -> GetterAbi: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Message:
     """
@@ -659,6 +903,9 @@ class Message:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> Message: ...
+    def __deepcopy__(self, memo: Any) -> Message: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class SignedExternalMessage(Message):
     """
@@ -680,13 +927,36 @@ class SignedExternalMessage(Message):
     def split(self) -> Tuple[Message, int]:
         """Splits into inner message and expiration timestamp."""
         ...
+    def __copy__(self) -> SignedExternalMessage: ...
+    def __deepcopy__(self, memo: Any) -> SignedExternalMessage: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class UnsignedExternalMessage:
-    """Unsigned external message with function intput."""
+    """
+    Unsigned external message with function intput.
+
+    :param dst: destination address.
+    :param body: unsigned function input.
+    :param state_init: optional state init.
+    """
+
+    def __init__(
+        self, dst: Address, body: UnsignedBody, state_init: Optional[StateInit] = None
+    ) -> None: ...
 
     state_init: Optional[StateInit]
     """Optional state init."""
 
+    @property
+    def dst(self) -> Address:
+        """Destination address."""
+        ...
+
+    @property
+    def body(self) -> UnsignedBody:
+        """Unsigned function input."""
+        ...
+
     @property
     def hash(self) -> bytes:
         """A hash to sign."""
@@ -723,9 +993,32 @@ class UnsignedExternalMessage:
     def without_signature(self) -> SignedExternalMessage:
         """Creates an input without a signature."""
         ...
+    def __copy__(self) -> UnsignedExternalMessage: ...
+    def __deepcopy__(self, memo: Any) -> UnsignedExternalMessage: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class UnsignedBody:
-    """Unsigned function input."""
+    """
+    Unsigned function input.
+
+    :param abi_version: ABI version of the function.
+    :param payload: encoded function input without a signature.
+    :param hash: a hash to sign.
+    :param expire_at: expiration unix timestamp.
+    """
+
+    def __init__(
+        self, abi_version: AbiVersion, payload: Cell, hash: bytes, expire_at: int
+    ) -> None: ...
+    @property
+    def abi_version(self) -> AbiVersion:
+        """ABI version of the function."""
+        ...
+
+    @property
+    def payload(self) -> Cell:
+        """Encoded function input without a signature."""
+        ...
 
     @property
     def hash(self) -> bytes:
@@ -761,6 +1054,9 @@ class UnsignedBody:
     def without_signature(self) -> Cell:
         """Creates an input without a signature."""
         ...
+    def __copy__(self) -> UnsignedBody: ...
+    def __deepcopy__(self, memo: Any) -> UnsignedBody: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class AbiParam:
     """
@@ -940,6 +1236,9 @@ class AbiVersion:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> AbiVersion: ...
+    def __deepcopy__(self, memo: Any) -> AbiVersion: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 # </editor-fold>
 
@@ -953,6 +1252,16 @@ class BlockchainConfig:
     Partially parsed blockchain config.
     """
 
+    @staticmethod
+    def from_params_dict_cell(params: Cell, global_id: int) -> BlockchainConfig:
+        """
+        Constructs blockchain config from the params dictionary.
+
+        :param params: config params dictionary (`HashmapE 32 ^Cell`).
+        :param global_id: network ID.
+        """
+        ...
+
     @property
     def global_id(self) -> int:
         """Network ID."""
@@ -1021,12 +1330,28 @@ class BlockchainConfig:
         TLB: `HashmapE 32 ^Cell`
         """
         ...
+    def __copy__(self) -> BlockchainConfig: ...
+    def __deepcopy__(self, memo: Any) -> BlockchainConfig: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class AccountState:
     """
     A state of an existing account.
     """
 
+    @staticmethod
+    def from_bytes(bytes: bytes) -> AccountState:
+        """
+        Decodes account state from raw bytes.
+
+        :param bytes: raw bytes with BOC of an existing account.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes account state into raw bytes with BOC."""
+        ...
+
     @property
     def storage_used(self) -> StorageUsed:
         """Storage usage statistics."""
@@ -1066,12 +1391,28 @@ class AccountState:
     def frozen_state_hash(self) -> Optional[bytes]:
         """A hash of the last known state for the frozen account."""
         ...
+    def __copy__(self) -> AccountState: ...
+    def __deepcopy__(self, memo: Any) -> AccountState: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class StorageUsed:
     """
     Account storage stats.
     """
 
+    @staticmethod
+    def from_bytes(bytes: bytes) -> StorageUsed:
+        """
+        Decodes storage stats from raw bytes.
+
+        :param bytes: raw bytes with BOC.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes storage stats into raw bytes."""
+        ...
+
     @property
     def cells(self) -> int:
         """Number of cells occupied by this account."""
@@ -1081,6 +1422,9 @@ class StorageUsed:
     def bits(self) -> int:
         """Number of bits occupied by this account."""
         ...
+    def __copy__(self) -> StorageUsed: ...
+    def __deepcopy__(self, memo: Any) -> StorageUsed: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Transaction:
     """Blockchain transaction."""
@@ -1254,10 +1598,26 @@ class Transaction:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> Transaction: ...
+    def __deepcopy__(self, memo: Any) -> Transaction: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionStoragePhase:
     """Transaction storage phase."""
 
+    @staticmethod
+    def from_bytes(bytes: bytes) -> TransactionStoragePhase:
+        """
+        Decodes the phase from raw bytes.
+
+        :param bytes: raw bytes with BOC.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes the phase into raw bytes."""
+        ...
+
     @property
     def storage_fees_collected(self) -> Tokens:
         """Amount of collected storage fees in nano EVERs."""
@@ -1272,10 +1632,26 @@ class TransactionStoragePhase:
     def status_change(self) -> AccountStatusChange:
         """Account status change during this phase."""
         ...
+    def __copy__(self) -> TransactionStoragePhase: ...
+    def __deepcopy__(self, memo: Any) -> TransactionStoragePhase: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionCreditPhase:
     """Transaction credit phase."""
 
+    @staticmethod
+    def from_bytes(bytes: bytes) -> TransactionCreditPhase:
+        """
+        Decodes the phase from raw bytes.
+
+        :param bytes: raw bytes with BOC.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes the phase into raw bytes."""
+        ...
+
     @property
     def due_fees_collected(self) -> Optional[Tokens]:
         """Amount of collected storage fees in nano EVERs."""
@@ -1285,10 +1661,26 @@ class TransactionCreditPhase:
     def credit(self) -> Tokens:
         """Increased balance in nano EVERs."""
         ...
+    def __copy__(self) -> TransactionCreditPhase: ...
+    def __deepcopy__(self, memo: Any) -> TransactionCreditPhase: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionComputePhase:
     """Transaction compute phase."""
 
+    @staticmethod
+    def from_bytes(bytes: bytes) -> TransactionComputePhase:
+        """
+        Decodes the phase from raw bytes.
+
+        :param bytes: raw bytes with BOC.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes the phase into raw bytes."""
+        ...
+
     @property
     def success(self) -> bool: ...
     @property
@@ -1315,10 +1707,26 @@ class TransactionComputePhase:
     def vm_init_state_hash(self) -> bytes: ...
     @property
     def vm_final_state_hash(self) -> bytes: ...
+    def __copy__(self) -> TransactionComputePhase: ...
+    def __deepcopy__(self, memo: Any) -> TransactionComputePhase: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionActionPhase:
     """Transaction action phase."""
 
+    @staticmethod
+    def from_bytes(bytes: bytes) -> TransactionActionPhase:
+        """
+        Decodes the phase from raw bytes.
+
+        :param bytes: raw bytes with BOC.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes the phase into raw bytes."""
+        ...
+
     @property
     def success(self) -> bool: ...
     @property
@@ -1345,12 +1753,33 @@ class TransactionActionPhase:
     def messages_created(self) -> int: ...
     @property
     def action_list_hash(self) -> bytes: ...
+    def __copy__(self) -> TransactionActionPhase: ...
+    def __deepcopy__(self, memo: Any) -> TransactionActionPhase: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionBouncePhase:
+    """Transaction bounce phase."""
+
+    @staticmethod
+    def from_bytes(bytes: bytes) -> TransactionBouncePhase:
+        """
+        Decodes the phase from raw bytes.
+
+        :param bytes: raw bytes with BOC.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Encodes the phase into raw bytes."""
+        ...
+
     @property
     def msg_fees(self) -> Tokens: ...
     @property
     def fwd_fees(self) -> Tokens: ...
+    def __copy__(self) -> TransactionBouncePhase: ...
+    def __deepcopy__(self, memo: Any) -> TransactionBouncePhase: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionType:
     Ordinary: ClassVar[TransactionType] = ...
@@ -1399,6 +1828,18 @@ class TransactionTree:
         """
         ...
 
+    def to_bytes(self) -> bytes:
+        """Encodes the tree into raw bytes."""
+        ...
+
+    def encode(self, encoding: Optional[str] = None) -> str:
+        """
+        Encodes the tree into BOC.
+
+        :param encoding: encoding type. `base64` (default) or `hex`.
+        """
+        ...
+
     @property
     def root(self) -> Transaction:
         """
@@ -1414,6 +1855,9 @@ class TransactionTree:
         ...
 
     def __iter__(self) -> TransactionTreeIter: ...
+    def __copy__(self) -> TransactionTree: ...
+    def __deepcopy__(self, memo: Any) -> TransactionTree: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class TransactionTreeIter:
     """
@@ -1518,6 +1962,9 @@ class InternalMessageHeader(MessageHeader):
     def created_at(self) -> int: ...
     @property
     def created_lt(self) -> int: ...
+    def __copy__(self) -> InternalMessageHeader: ...
+    def __deepcopy__(self, memo: Any) -> InternalMessageHeader: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class ExternalInMessageHeader(MessageHeader):
     """External incoming message header."""
@@ -1536,6 +1983,9 @@ class ExternalInMessageHeader(MessageHeader):
     def import_fee(self) -> Tokens:
         """Import fee in nano EVERs"""
         ...
+    def __copy__(self) -> ExternalInMessageHeader: ...
+    def __deepcopy__(self, memo: Any) -> ExternalInMessageHeader: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class ExternalOutMessageHeader(MessageHeader):
     """External outgoing message header."""
@@ -1560,6 +2010,9 @@ class ExternalOutMessageHeader(MessageHeader):
     def created_lt(self) -> int:
         """A logical time when the message was created."""
         ...
+    def __copy__(self) -> ExternalOutMessageHeader: ...
+    def __deepcopy__(self, memo: Any) -> ExternalOutMessageHeader: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class MessageType:
     """Message type."""
@@ -1589,6 +2042,7 @@ class StateInit:
 
     :param code: optional contract code.
     :param data: optional contract data.
+    :param special: optional tick and tock flags of the special account.
     """
 
     code: Optional[Cell]
@@ -1597,6 +2051,9 @@ class StateInit:
     data: Optional[Cell]
     """Optional contract data."""
 
+    special: Optional[Tuple[bool, bool]]
+    """Tick and tock flags of the special account."""
+
     @staticmethod
     def from_bytes(bytes: bytes) -> StateInit:
         """
@@ -1626,7 +2083,10 @@ class StateInit:
         ...
 
     def __init__(
-        self, code: Optional[Cell] = None, data: Optional[Cell] = None
+        self,
+        code: Optional[Cell] = None,
+        data: Optional[Cell] = None,
+        special: Optional[Tuple[bool, bool]] = None,
     ) -> None: ...
     @property
     def code_hash(self) -> Optional[bytes]:
@@ -1670,6 +2130,9 @@ class StateInit:
     def build_cell(self) -> Cell:
         """Creates a new cell with StateInit."""
         ...
+    def __copy__(self) -> StateInit: ...
+    def __deepcopy__(self, memo: Any) -> StateInit: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Address:
     """
@@ -1743,12 +2206,30 @@ class Address:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> Address: ...
+    def __deepcopy__(self, memo: Any) -> Address: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class CellSlice:
     """
     A read-only view for a subrange of a cell.
+
+    :param cell: the underlying cell.
+    :param bits_offset: optional data offset in bits.
+    :param refs_offset: optional references offset.
+    :param bits: optional data length in bits. Remaining bits by default.
+    :param refs: optional number of references. Remaining references by default.
     """
 
+    def __init__(
+        self,
+        cell: Cell,
+        bits_offset: Optional[int] = None,
+        refs_offset: Optional[int] = None,
+        bits: Optional[int] = None,
+        refs: Optional[int] = None,
+    ) -> None: ...
+
     @property
     def cell(self) -> Cell:
         """Returns the underlying cell."""
@@ -1929,6 +2410,9 @@ class CellSlice:
     def load_reference(self) -> Cell:
         """Tries to read the next cell, incrementing the refs window start."""
         ...
+    def __copy__(self) -> CellSlice: ...
+    def __deepcopy__(self, memo: Any) -> CellSlice: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class CellBuilder:
     """
@@ -2174,6 +2658,9 @@ class Cell:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> Cell: ...
+    def __deepcopy__(self, memo: Any) -> Cell: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Tokens:
     """
@@ -2230,6 +2717,9 @@ class Tokens:
     def __le__(self, other: Tokens) -> Any: ...
     def __lt__(self, other: Tokens) -> Any: ...
     def __ne__(self, other: Tokens) -> Any: ...
+    def __copy__(self) -> Tokens: ...
+    def __deepcopy__(self, memo: Any) -> Tokens: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 # </editor-fold>
 
@@ -2241,81 +2731,202 @@ class Tokens:
 class Transport:
     """Base transport"""
 
+    subscription_options: SubscriptionOptions
+    """Settings for subscriptions created after the update."""
+
     @property
     def clock(self) -> Clock:
         """Time context."""
         ...
 
+    @property
+    def cache_stats(self) -> Optional[CacheStats]:
+        """Cache hit/miss counters or `None` if the cache is disabled."""
+        ...
+
+    @property
+    def sync(self) -> SyncTransport:
+        """Blocking facade for the transport methods."""
+        ...
+
     async def check_connection(self) -> None:
         """Checks the connection."""
         ...
 
     async def send_external_message(
-        self, message: SignedExternalMessage
+        self,
+        message: SignedExternalMessage,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
     ) -> Optional[Transaction]:
         """
         Sends an external message to the network and waits until the transaction.
 
         :param message: signed external message.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional timeout in seconds for the whole operation.
         """
         ...
 
-    async def get_signature_id(self) -> Optional[int]:
-        """Fetches signature id for the selected network."""
-        ...
+    async def send_external_message_nowait(
+        self,
+        message: SignedExternalMessage,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> PendingMessage:
+        """
+        Sends an external message to the network without waiting for the transaction.
 
-    async def get_signature_context(self) -> SignatureContext:
-        """Fetches signature context from the selected network."""
+        :param message: signed external message.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional timeout in seconds for the broadcast.
+        """
         ...
 
-    async def get_blockchain_config(
-        self, force: Optional[bool] = None
-    ) -> BlockchainConfig:
+    async def estimate_fees(
+        self,
+        message: Message | UnsignedExternalMessage,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> EstimatedFees:
         """
-        Fetches the latest blockchain config.
+        Emulates an external message on top of the latest destination account state.
+        Signature checks are disabled, unsigned messages are filled with a fake signature.
 
-        :param force: whether to ignore cache.
+        :param message: external inbound message.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
         """
         ...
 
-    async def get_account_state(self, address: Address) -> Optional[AccountState]:
+    async def export_pending_messages(self) -> str:
         """
-        Fetches an account state for the specified address.
-
-        :param address: account address.
+        Returns all messages which are still waiting for the transaction as a JSON string.
+        Each item contains message BOC, hash and expiration timestamp.
         """
         ...
 
-    async def get_accounts_by_code_hash(
+    async def import_pending_messages(
         self,
-        code_hash: bytes,
-        continuation: Optional[Address] = None,
-        limit: Optional[int] = None,
-    ) -> List[Address]:
+        data: str,
+        rebroadcast: Optional[bool] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+    ) -> List[Union[PendingMessage, Exception]]:
+        """
+        Resumes tracking of the exported pending messages.
+
+        Messages which were already delivered or have expired are resolved immediately.
+        Others are awaited without sending them again, unless `rebroadcast` is set.
+        Entries which could not be resumed are returned as exceptions.
+
+        :param data: JSON string returned by `export_pending_messages`.
+        :param rebroadcast: whether to send unresolved messages once more. `False` by default.
+        :param retry_policy: optional retry policy to override the transport default.
+        """
+        ...
+
+    async def get_signature_id(
+        self,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[int]:
+        """
+        Fetches signature id for the selected network.
+
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    async def get_signature_context(
+        self,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> SignatureContext:
+        """
+        Fetches signature context from the selected network.
+
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    async def get_blockchain_config(
+        self,
+        force: Optional[bool] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> BlockchainConfig:
+        """
+        Fetches the latest blockchain config.
+
+        :param force: whether to ignore cache.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    async def get_account_state(
+        self,
+        address: Address,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[AccountState]:
+        """
+        Fetches an account state for the specified address.
+
+        :param address: account address.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    async def get_accounts_by_code_hash(
+        self,
+        code_hash: bytes,
+        continuation: Optional[Address] = None,
+        limit: Optional[int] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> List[Address]:
         """
         Fetches a list of address of accounts with the specified code hash.
 
         :param code_hash: code hash.
         :param continuation: optional account address from the previous batch.
         :param limit: max number of items in response.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
         """
         ...
 
-    async def get_transaction(self, transaction_hash: bytes) -> Optional[Transaction]:
+    async def get_transaction(
+        self,
+        transaction_hash: bytes,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[Transaction]:
         """
         Fetches the transaction by hash.
 
         :param transaction_hash: transaction hash.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
         """
         ...
 
     async def get_dst_transaction(
-        self, message_hash: bytes | Message
+        self,
+        message_hash: bytes | Message,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
     ) -> Optional[Transaction]:
         """
         Searches for a transaction by the hash of incoming message.
 
         :param message_hash: a hash of the incoming message, or the message itself.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
         """
         ...
 
@@ -2324,6 +2935,8 @@ class Transport:
         address: Address,
         lt: Optional[int] = None,
         limit: Optional[int] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
     ) -> List[Transaction]:
         """
         Fetches a transactions batch for the specified account.
@@ -2331,6 +2944,59 @@ class Transport:
         :param address: account address.
         :param lt: optoinal logical time of the latest transaction.
         :param limit: max number of items in response.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def iter_transactions(
+        self,
+        address: Address,
+        from_lt: Optional[int] = None,
+        to_lt: Optional[int] = None,
+        since: Optional[int] = None,
+        until: Optional[int] = None,
+        order: Optional[str] = None,
+        limit: Optional[int] = None,
+        prefetch: Optional[int] = None,
+    ) -> TransactionsIter:
+        """
+        Returns an async iterator over the account transactions history.
+        Pages are fetched starting from the first iteration.
+
+        Ascending order requires the whole range to be walked before the first item,
+        only the oldest `limit` transactions are kept in memory meanwhile.
+        Use `from_lt` or `since` to bound the walk.
+
+        :param address: account address.
+        :param from_lt: optional lowest logical time (inclusive).
+        :param to_lt: optional highest logical time (inclusive).
+        :param since: optional lowest unix timestamp (inclusive).
+        :param until: optional highest unix timestamp (inclusive).
+        :param order: transactions order, `desc` or `asc`. Default: `desc`.
+        :param limit: optional max number of transactions.
+        :param prefetch: max number of pages fetched ahead. Default: 2.
+        """
+        ...
+
+    def iter_accounts_by_code_hash(
+        self,
+        code_hash: bytes,
+        with_states: bool = False,
+        concurrency: Optional[int] = None,
+        page_size: Optional[int] = None,
+        prefetch: Optional[int] = None,
+    ) -> AccountsByCodeHashIter:
+        """
+        Returns an async iterator over all accounts with the specified code hash.
+
+        Yields addresses or `(Address, Optional[AccountState])` tuples if `with_states` is set.
+
+        :param code_hash: hash of the account code.
+        :param with_states: whether to fetch account states. Default: `False`.
+        :param concurrency: max number of concurrent state requests. Default: 10.
+        :param page_size: number of addresses per request. Default: 50.
+        :param prefetch: max number of pages fetched ahead. Default: 2.
         """
         ...
 
@@ -2350,17 +3016,287 @@ class Transport:
         """
         ...
 
+    def watch_accounts(self, addresses: List[Address]) -> AccountsWatcher:
+        """
+        Returns an async transactions iterator for multiple accounts.
+
+        :param addresses: initial list of account addresses.
+        """
+        ...
+
     def trace_transaction(
-        self, transaction_hash: bytes | Transaction, yield_root: bool = False
+        self,
+        transaction_hash: bytes | Transaction,
+        yield_root: bool = False,
+        timeout: Optional[float] = None,
+        max_depth: Optional[int] = None,
+        max_count: Optional[int] = None,
     ) -> TraceTransaction:
         """
         Returns an async transactions iterator over the transactions tree.
 
         :param transaction_hash: hash of the root transaction, or the root transaction itself.
         :param yield_root: whether to emit the root transaction.
+        :param timeout: optional total timeout in seconds, 300 by default. Raises `Timeout` when exceeded.
+        :param max_depth: optional max depth of traced transactions. Root transaction has depth 0.
+        :param max_count: optional max number of traced transactions, including the root one.
         """
         ...
 
+class SyncTransport:
+    """
+    Blocking versions of the transport methods.
+
+    Requests are executed on the shared runtime while the GIL is released,
+    so they can be used without an event loop. Calling them from a thread
+    with a running event loop raises `RuntimeError`.
+    """
+
+    def check_connection(self) -> None:
+        """Checks the connection."""
+        ...
+
+    def send_external_message(
+        self,
+        message: SignedExternalMessage,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[Transaction]:
+        """
+        Sends an external message to the network and waits until the transaction.
+
+        :param message: signed external message.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional timeout in seconds for the whole operation.
+        """
+        ...
+
+    def send_external_message_nowait(
+        self,
+        message: SignedExternalMessage,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> PendingMessage:
+        """
+        Sends an external message to the network without waiting for the transaction.
+
+        :param message: signed external message.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional timeout in seconds for the broadcast.
+        """
+        ...
+
+    def wait_pending_message(self, message: PendingMessage) -> Optional[Transaction]:
+        """
+        Waits until the transaction is found or the message expires.
+        Returns `None` if the message has expired.
+
+        :param message: pending message returned by `send_external_message_nowait`.
+        """
+        ...
+
+    def get_signature_id(
+        self,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[int]:
+        """
+        Fetches signature id for the selected network.
+
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_signature_context(
+        self,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> SignatureContext:
+        """
+        Fetches signature context from the selected network.
+
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def estimate_fees(
+        self,
+        message: Message | UnsignedExternalMessage,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> EstimatedFees:
+        """
+        Emulates an external message on top of the latest destination account state.
+        Signature checks are disabled, unsigned messages are filled with a fake signature.
+
+        :param message: external inbound message.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_blockchain_config(
+        self,
+        force: Optional[bool] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> BlockchainConfig:
+        """
+        Fetches the latest blockchain config.
+
+        :param force: whether to ignore cache.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_account_state(
+        self,
+        address: Address,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[AccountState]:
+        """
+        Fetches an account state for the specified address.
+
+        :param address: account address.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_accounts_by_code_hash(
+        self,
+        code_hash: bytes,
+        continuation: Optional[Address] = None,
+        limit: Optional[int] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> List[Address]:
+        """
+        Fetches a list of address of accounts with the specified code hash.
+
+        :param code_hash: code hash.
+        :param continuation: optional account address from the previous batch.
+        :param limit: max number of items in response.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_transaction(
+        self,
+        transaction_hash: bytes,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[Transaction]:
+        """
+        Fetches the transaction by hash.
+
+        :param transaction_hash: transaction hash.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_dst_transaction(
+        self,
+        message_hash: bytes | Message,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> Optional[Transaction]:
+        """
+        Searches for a transaction by the hash of incoming message.
+
+        :param message_hash: a hash of the incoming message, or the message itself.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def get_transactions(
+        self,
+        address: Address,
+        lt: Optional[int] = None,
+        limit: Optional[int] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> List[Transaction]:
+        """
+        Fetches a transactions batch for the specified account.
+
+        :param address: account address.
+        :param lt: optoinal logical time of the latest transaction.
+        :param limit: max number of items in response.
+        :param retry_policy: optional retry policy to override the transport default.
+        :param timeout: optional request timeout in seconds to override the transport default.
+        """
+        ...
+
+    def iter_transactions(
+        self,
+        address: Address,
+        from_lt: Optional[int] = None,
+        to_lt: Optional[int] = None,
+        since: Optional[int] = None,
+        until: Optional[int] = None,
+        order: Optional[str] = None,
+        limit: Optional[int] = None,
+        prefetch: Optional[int] = None,
+    ) -> BlockingIter:
+        """
+        Returns a blocking iterator over the account transactions history.
+        See `Transport.iter_transactions` for details.
+
+        :param address: account address.
+        :param from_lt: optional lowest logical time (inclusive).
+        :param to_lt: optional highest logical time (inclusive).
+        :param since: optional lowest unix timestamp (inclusive).
+        :param until: optional highest unix timestamp (inclusive).
+        :param order: transactions order, `desc` or `asc`. Default: `desc`.
+        :param limit: optional max number of transactions.
+        :param prefetch: max number of pages fetched ahead. Default: 2.
+        """
+        ...
+
+    def iter_accounts_by_code_hash(
+        self,
+        code_hash: bytes,
+        with_states: bool = False,
+        concurrency: Optional[int] = None,
+        page_size: Optional[int] = None,
+        prefetch: Optional[int] = None,
+    ) -> BlockingIter:
+        """
+        Returns a blocking iterator over all accounts with the specified code hash.
+        See `Transport.iter_accounts_by_code_hash` for details.
+
+        :param code_hash: hash of the account code.
+        :param with_states: whether to fetch account states. Default: `False`.
+        :param concurrency: max number of concurrent state requests. Default: 10.
+        :param page_size: number of addresses per request. Default: 50.
+        :param prefetch: max number of pages fetched ahead. Default: 2.
+        """
+        ...
+
+class BlockingIter:
+    """
+    Blocking iterator over the items fetched in background.
+    """
+
+    def close(self) -> None:
+        """
+        Stops fetching items.
+        """
+        ...
+
+    def __enter__(self) -> BlockingIter: ...
+    def __exit__(self, exc_type, exc_val, exc_tb) -> None: ...
+    def __iter__(self) -> BlockingIter: ...
+    def __next__(self) -> Any: ...
+
 class GqlTransport(Transport):
     """
     GraphQl transport.
@@ -2368,6 +3304,10 @@ class GqlTransport(Transport):
     :param endpoints: a list of gql endpoints.
     :param clock: optional clock to modify timestamp.
     :param local: whether the connection is with local node.
+    :param retry_policy: optional default retry policy for all requests.
+    :param timeout: optional default request timeout in seconds.
+    :param cache: optional request cache settings. Cache is disabled by default.
+    :param polling_mode: how subscriptions track accounts, `polling` or `block_walking`. Default: `polling`.
     """
 
     def __init__(
@@ -2375,6 +3315,10 @@ class GqlTransport(Transport):
         endpoints: List[str],
         clock: Optional[Clock] = None,
         local: Optional[bool] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+        cache: Optional[CacheConfig] = None,
+        polling_mode: Optional[str] = None,
     ) -> None: ...
     async def query_transactions(
         self,
@@ -2413,43 +3357,270 @@ class GqlTransport(Transport):
         limit: Optional[int] = None,
     ) -> List[Tuple[Address, Optional[AccountState]]]:
         """
-        Accounts GQL query.
+        Accounts GQL query.
+
+        :param filter: filter parts.
+        :param order_by: optional orderBy parts.
+        :param limit: optional limit.
+        """
+        ...
+
+class GqlExprPart:
+    """
+    GQL query part.
+
+    :param value: part value.
+    """
+
+    def __init__(self, value: str) -> None: ...
+    def __str__(self) -> str: ...
+
+class JrpcTransport(Transport):
+    """
+    JRPC transport.
+
+    Requests are sent to another endpoint only on connection errors,
+    timeouts and 5xx responses.
+
+    :param endpoint: JRPC endpoint.
+    :param clock: optional clock to modify timestamp.
+    :param retry_policy: optional default retry policy for all requests.
+    :param timeout: optional default request timeout in seconds.
+    :param strategy: endpoint selection strategy, `failover` or `round_robin`. Default: `failover`.
+    :param health_check_interval: optional interval of endpoint health checks in seconds. Default: 10.
+    :param cache: optional request cache settings. Cache is disabled by default.
+    :param endpoints: a list of JRPC endpoints. Can't be used together with `endpoint`.
+    """
+
+    def __init__(
+        self,
+        endpoint: Optional[str] = None,
+        clock: Optional[Clock] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+        strategy: Optional[str] = None,
+        health_check_interval: Optional[float] = None,
+        cache: Optional[CacheConfig] = None,
+        endpoints: Optional[List[str]] = None,
+    ) -> None: ...
+
+    @property
+    def endpoints(self) -> List[str]:
+        """
+        All configured endpoints.
+        """
+        ...
+
+    @property
+    def active_endpoint(self) -> str:
+        """
+        Endpoint which has served the last request.
+        """
+        ...
+
+class ProtoTransport(Transport):
+    """
+    Protobuf transport.
+
+    Requests are sent to another endpoint only on connection errors,
+    timeouts and 5xx responses.
+
+    :param endpoint: Protobuf endpoint.
+    :param clock: optional clock to modify timestamp.
+    :param retry_policy: optional default retry policy for all requests.
+    :param timeout: optional default request timeout in seconds.
+    :param strategy: endpoint selection strategy, `failover` or `round_robin`. Default: `failover`.
+    :param health_check_interval: optional interval of endpoint health checks in seconds. Default: 10.
+    :param cache: optional request cache settings. Cache is disabled by default.
+    :param endpoints: a list of Protobuf endpoints. Can't be used together with `endpoint`.
+    """
+
+    def __init__(
+        self,
+        endpoint: Optional[str] = None,
+        clock: Optional[Clock] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+        strategy: Optional[str] = None,
+        health_check_interval: Optional[float] = None,
+        cache: Optional[CacheConfig] = None,
+        endpoints: Optional[List[str]] = None,
+    ) -> None: ...
+
+    @property
+    def endpoints(self) -> List[str]:
+        """
+        All configured endpoints.
+        """
+        ...
+
+    @property
+    def active_endpoint(self) -> str:
+        """
+        Endpoint which has served the last request.
+        """
+        ...
+
+class LocalTransport(Transport):
+    """
+    In-memory blockchain transport.
+
+    Every sent message is executed locally. Produced internal messages
+    are delivered to their destination accounts.
+
+    In fork mode, the state of each account is fetched from the forked transport
+    on first access, all later changes are kept local. Nothing is sent to the network.
+
+    Sent messages are processed one by one, so transactions of concurrent
+    sends never interleave. The only exception is fetching an unknown account
+    from the forked transport, other sends are processed meanwhile.
+
+    Sending raises `ExecutionError` if the message is not accepted
+    or its transaction is aborted.
+
+    :param config: blockchain config used for execution.
+        Fetched from the forked transport on first use if not specified.
+    :param clock: optional clock to modify timestamp.
+    :param check_signature: whether to check signatures of external messages. Default: `True`.
+    :param fork: optional transport to fetch unknown accounts from.
+    :param retry_policy: optional retry policy for requests to the forked transport.
+    :param timeout: optional timeout in seconds for requests to the forked transport.
+    """
+
+    def __init__(
+        self,
+        config: Optional[BlockchainConfig] = None,
+        clock: Optional[Clock] = None,
+        check_signature: Optional[bool] = None,
+        fork: Optional[Transport] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+        timeout: Optional[float] = None,
+    ) -> None: ...
+    @property
+    def accounts(self) -> List[Address]:
+        """A list of all existing accounts."""
+        ...
+
+    def get_account(self, address: Address) -> Optional[AccountState]:
+        """
+        Returns the current state of the account.
+        Accounts are not fetched from the forked transport here, use `get_account_state` instead.
+
+        :param address: account address.
+        """
+        ...
+
+    def set_account(self, address: Address, state: Optional[AccountState]) -> None:
+        """
+        Replaces the state of the account.
 
-        :param filter: filter parts.
-        :param order_by: optional orderBy parts.
-        :param limit: optional limit.
+        :param address: account address.
+        :param state: new account state, or `None` to remove the account.
         """
         ...
 
-class GqlExprPart:
+class CustomTransport(Transport):
     """
-    GQL query part.
+    Transport backed by the Python handler.
 
-    :param value: part value.
+    Handler must implement the following coroutines:
+
+    - `get_account_state(address: Address) -> Optional[AccountState]`
+    - `get_transactions(address: Address, lt: int, limit: int) -> List[Transaction]`
+    - `get_transaction(hash: bytes) -> Optional[Transaction]`
+    - `get_dst_transaction(message_hash: bytes) -> Optional[Transaction]`
+    - `get_accounts_by_code_hash(code_hash: bytes, limit: int, continuation: Optional[Address]) -> List[Address]`
+    - `get_blockchain_config(force: bool) -> BlockchainConfig`
+    - `send_message(message: Message) -> None`
+
+    Must be created inside a running event loop. Handler coroutines run on the event loop
+    of the caller, requests made by background subscriptions use the creation loop.
+
+    :param handler: an object with transport coroutines.
+    :param clock: optional clock to modify timestamp.
+    :param max_transactions_per_fetch: max number of transactions requested at once. Default: 50.
+    :param timeout: optional default request timeout in seconds.
+    :param cache: optional request cache settings. Cache is disabled by default.
     """
 
-    def __init__(self, value: str) -> None: ...
-    def __str__(self) -> str: ...
+    def __init__(
+        self,
+        handler: Any,
+        clock: Optional[Clock] = None,
+        max_transactions_per_fetch: Optional[int] = None,
+        timeout: Optional[float] = None,
+        cache: Optional[CacheConfig] = None,
+    ) -> None: ...
+    @property
+    def handler(self) -> Any:
+        """Python handler object."""
+        ...
 
-class JrpcTransport(Transport):
+class RecordingTransport(Transport):
     """
-    JRPC transport.
+    Transport wrapper which writes all requests and responses into the file.
 
-    :param endpoint: JRPC endpoint.
-    :param clock: optional clock to modify timestamp.
+    Use a stopped clock (see `Clock.frozen_at_ms`) to produce the same
+    external messages during replay.
+
+    GQL transports with the `block_walking` polling mode can't be recorded.
+
+    :param transport: underlying transport.
+    :param path: path to the records file.
     """
 
-    def __init__(self, endpoint: str, clock: Optional[Clock] = None) -> None: ...
+    def __init__(self, transport: Transport, path: str | PathLike[str]) -> None: ...
 
-class ProtoTransport(Transport):
+class ReplayTransport(Transport):
     """
-    Protobuf transport.
+    Transport which serves responses from the records file
+    created by `RecordingTransport`.
 
-    :param endpoint: Protobuf endpoint.
-    :param clock: optional clock to modify timestamp.
+    Responses for the same request are served in the recorded order,
+    the last one is repeated. Sent messages are matched by the call order.
+
+    :param path: path to the records file.
+    :param clock: optional clock to modify timestamp. By default, the clock is stopped
+        at the recording start time and moved to the recording time of each served response.
+    """
+
+    def __init__(
+        self, path: str | PathLike[str], clock: Optional[Clock] = None
+    ) -> None: ...
+
+class PendingMessage:
     """
+    Broadcasted external message.
+    """
+
+    @property
+    def hash(self) -> bytes:
+        """Message hash."""
+        ...
+
+    @property
+    def expire_at(self) -> int:
+        """Message expiration timestamp."""
+        ...
+
+    @property
+    def dst(self) -> Address:
+        """Destination address."""
+        ...
+
+    async def wait(self) -> Optional[Transaction]:
+        """
+        Waits until the transaction is found or the message expires.
+        Returns `None` if the message has expired.
+        """
+        ...
 
-    def __init__(self, endpoint: str, clock: Optional[Clock] = None) -> None: ...
+    def cancel(self) -> bool:
+        """
+        Stops waiting for the transaction.
+        Returns `False` if the message was already resolved.
+        """
+        ...
 
 class AccountStatesAsyncIter:
     """
@@ -2470,6 +3641,10 @@ class AccountStatesAsyncIter:
 class AccountTransactionsAsyncIter:
     """
     Async account transactions iterator.
+
+    When the iterator falls behind, it either raises `SubscriptionLagged`
+    or yields an empty batch with `gap` flag, depending on `SubscriptionOptions.lag_mode`.
+    Iteration can be continued in both cases.
     """
 
     async def close(self) -> None:
@@ -2483,9 +3658,89 @@ class AccountTransactionsAsyncIter:
     def __aiter__(self) -> AccountTransactionsAsyncIter: ...
     async def __anext__(self) -> Tuple[List[Transaction], TransactionsBatchInfo]: ...
 
+class AccountsWatcher:
+    """
+    Async transactions iterator for multiple accounts.
+
+    Each account is watched through the shared transport subscription, so it is polled
+    once together with other iterators of the same account, following the transport
+    subscription options. Skipped batches are reported according to their `lag_mode`.
+
+    Yields `(address, transactions, info)` tuples.
+    """
+
+    @property
+    def addresses(self) -> List[Address]:
+        """Currently watched addresses."""
+        ...
+
+    async def add(self, addresses: List[Address]) -> None:
+        """
+        Starts watching the specified accounts.
+
+        :param addresses: account addresses.
+        """
+        ...
+
+    async def remove(self, addresses: List[Address]) -> None:
+        """
+        Stops watching the specified accounts.
+
+        :param addresses: account addresses.
+        """
+        ...
+
+    async def close(self) -> None:
+        """
+        Closes async iterator.
+        """
+        ...
+
+    async def __aenter__(self) -> AccountsWatcher: ...
+    async def __aexit__(self, exc_type, exc_val, exc_tb) -> None: ...
+    def __aiter__(self) -> AccountsWatcher: ...
+    async def __anext__(
+        self,
+    ) -> Tuple[Address, List[Transaction], TransactionsBatchInfo]: ...
+
+class TransactionsIter:
+    """
+    Async account transactions history iterator.
+    """
+
+    async def close(self) -> None:
+        """
+        Closes async iterator.
+        """
+        ...
+
+    async def __aenter__(self) -> TransactionsIter: ...
+    async def __aexit__(self, exc_type, exc_val, exc_tb) -> None: ...
+    def __aiter__(self) -> TransactionsIter: ...
+    async def __anext__(self) -> Transaction: ...
+
+class AccountsByCodeHashIter:
+    """
+    Async iterator over accounts with the same code hash.
+    """
+
+    async def close(self) -> None:
+        """
+        Closes async iterator.
+        """
+        ...
+
+    async def __aenter__(self) -> AccountsByCodeHashIter: ...
+    async def __aexit__(self, exc_type, exc_val, exc_tb) -> None: ...
+    def __aiter__(self) -> AccountsByCodeHashIter: ...
+    async def __anext__(self) -> Address | Tuple[Address, Optional[AccountState]]: ...
+
 class TraceTransaction:
     """
     Async transactions tree iterator.
+
+    Transport errors are raised from the iterator. The failed step
+    is retried on the next iteration.
     """
 
     async def close(self) -> None:
@@ -2500,6 +3755,18 @@ class TraceTransaction:
         """
         ...
 
+    @property
+    def truncated(self) -> bool:
+        """Whether some messages were not traced due to `max_depth` or `max_count`."""
+        ...
+
+    async def tree(self) -> TransactionTree:
+        """
+        Waits for the last transaction and returns all traced transactions as a tree.
+        Transactions which were already yielded by the iterator are included.
+        """
+        ...
+
     async def __aenter__(self) -> TraceTransaction: ...
     async def __aexit__(self, exc_type, exc_val, exc_tb) -> None: ...
     def __aiter__(self) -> TraceTransaction: ...
@@ -2520,17 +3787,72 @@ class TransactionsBatchInfo:
         """The highest logical time in batch."""
         ...
 
+    @property
+    def gap(self) -> bool:
+        """Whether transactions in this range were skipped and must be fetched manually."""
+        ...
+
+class SubscriptionLagged(NekotonError):
+    """
+    Transactions iterator fell behind and skipped some batches.
+
+    Exception args contain the lowest and the highest logical time of the skipped range.
+    """
+
+class SubscriptionOptions:
+    """
+    Subscription settings.
+
+    :param interval: account polling interval in seconds. Default: 5.
+    :param short_interval: polling interval while there are pending messages in seconds. Default: 1.
+    :param capacity: max number of transaction batches buffered for each iterator. Default: 10.
+    :param lag_mode: what to do when an iterator falls behind, `raise` or `gap`. Default: `raise`.
+    """
+
+    def __init__(
+        self,
+        interval: Optional[float] = None,
+        short_interval: Optional[float] = None,
+        capacity: Optional[int] = None,
+        lag_mode: Optional[str] = None,
+    ) -> None: ...
+    @property
+    def interval(self) -> float:
+        """Account polling interval in seconds."""
+        ...
+
+    @property
+    def short_interval(self) -> float:
+        """Polling interval while there are pending messages in seconds."""
+        ...
+
+    @property
+    def capacity(self) -> int:
+        """Max number of transaction batches buffered for each iterator."""
+        ...
+
+    @property
+    def lag_mode(self) -> str:
+        """What to do when an iterator falls behind."""
+        ...
+
 class Clock:
     """
     Time context.
 
     :param offset: optional offset in milliseconds.
+    :param frozen_at_ms: optional timestamp in milliseconds to stop the clock at.
     """
 
     offset: int
     """Clock offset in milliseconds."""
 
-    def __init__(self, offset: Optional[int] = None) -> None: ...
+    frozen_at_ms: Optional[int]
+    """Timestamp in milliseconds at which the clock is stopped. `None` for the running clock."""
+
+    def __init__(
+        self, offset: Optional[int] = None, frozen_at_ms: Optional[int] = None
+    ) -> None: ...
     @property
     def now_sec(self) -> int:
         """Returns current timestamp in seconds."""
@@ -2541,6 +3863,154 @@ class Clock:
         """Returns current timestamp in milliseconds."""
         ...
 
+class RetryPolicy:
+    """
+    Retry policy for transport requests.
+
+    Delay between attempts grows exponentially from `initial_backoff`
+    up to `max_backoff`, each delay is randomly adjusted by `jitter`.
+
+    :param max_attempts: max number of attempts including the first one. Default: 3.
+    :param initial_backoff: delay before the second attempt in seconds. Default: 0.5.
+    :param max_backoff: max delay between attempts in seconds. Default: 10.
+    :param multiplier: backoff multiplier. Default: 2.
+    :param jitter: relative random delay deviation in range [0, 1]. Default: 0.1.
+    :param retry_on: optional list of error message patterns to retry.
+        By default, only transport-level errors are retried (connection errors,
+        timeouts, 5xx and 429 responses, `ConnectionError` and `TimeoutError`
+        raised by the custom transport handler).
+
+    External messages are sent again only if the previous attempt
+    has not reached the endpoint.
+    """
+
+    def __init__(
+        self,
+        max_attempts: Optional[int] = None,
+        initial_backoff: Optional[float] = None,
+        max_backoff: Optional[float] = None,
+        multiplier: Optional[float] = None,
+        jitter: Optional[float] = None,
+        retry_on: Optional[List[str]] = None,
+    ) -> None: ...
+    @property
+    def max_attempts(self) -> int:
+        """Max number of attempts including the first one."""
+        ...
+
+    @property
+    def initial_backoff(self) -> float:
+        """Delay before the second attempt in seconds."""
+        ...
+
+    @property
+    def max_backoff(self) -> float:
+        """Max delay between attempts in seconds."""
+        ...
+
+    @property
+    def multiplier(self) -> float:
+        """Backoff multiplier."""
+        ...
+
+    @property
+    def jitter(self) -> float:
+        """Relative random delay deviation."""
+        ...
+
+    @property
+    def retry_on(self) -> Optional[List[str]]:
+        """Error message patterns to retry."""
+        ...
+
+class CacheConfig:
+    """
+    Transport cache settings.
+
+    Transactions are immutable so they are only evicted when the cache is full.
+    Account states and blockchain config are cached for a short time.
+    The cache is shared by direct requests, iterators and subscriptions.
+    Subscriptions always poll fresh account states and update the cached ones.
+
+    :param account_state_ttl: account state lifetime in seconds. Default: 1.
+    :param account_states_capacity: max number of cached account states. Default: 1000.
+    :param transactions_capacity: max number of cached transactions. Default: 10000.
+    :param blockchain_config_ttl: blockchain config lifetime in seconds. Default: 60.
+    """
+
+    def __init__(
+        self,
+        account_state_ttl: Optional[float] = None,
+        account_states_capacity: Optional[int] = None,
+        transactions_capacity: Optional[int] = None,
+        blockchain_config_ttl: Optional[float] = None,
+    ) -> None: ...
+    @property
+    def account_state_ttl(self) -> float:
+        """Account state lifetime in seconds."""
+        ...
+
+    @property
+    def account_states_capacity(self) -> int:
+        """Max number of cached account states."""
+        ...
+
+    @property
+    def transactions_capacity(self) -> int:
+        """Max number of cached transactions."""
+        ...
+
+    @property
+    def blockchain_config_ttl(self) -> float:
+        """Blockchain config lifetime in seconds."""
+        ...
+
+class EstimatedFees:
+    """Result of the local emulation of an external message."""
+
+    @property
+    def total_fees(self) -> Tokens:
+        """Total fees paid by the account."""
+        ...
+
+    @property
+    def gas_used(self) -> Optional[int]:
+        """Gas used during the compute phase. `None` if it was skipped."""
+        ...
+
+    @property
+    def compute_phase(self) -> Optional[TransactionComputePhase]:
+        """Compute phase result, if it was not skipped."""
+        ...
+
+    @property
+    def action_phase(self) -> Optional[TransactionActionPhase]:
+        """Action phase result, if it was executed."""
+        ...
+
+    @property
+    def out_msgs(self) -> List[Message]:
+        """Produced outgoing messages."""
+        ...
+
+    @property
+    def transaction(self) -> Transaction:
+        """Emulated transaction."""
+        ...
+
+class CacheStats:
+    """Transport cache counters."""
+
+    @property
+    def hits(self) -> int:
+        """Number of requests served from the cache."""
+        ...
+
+    @property
+    def misses(self) -> int:
+        """Number of requests sent to the network."""
+        ...
+
 # </editor-fold>
 
 ############
@@ -2574,6 +4044,9 @@ class SignatureContext:
         :param data: data to sign.
         """
         ...
+    def __copy__(self) -> SignatureContext: ...
+    def __deepcopy__(self, memo: Any) -> SignatureContext: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class PublicKey:
     """
@@ -2641,6 +4114,9 @@ class PublicKey:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> PublicKey: ...
+    def __deepcopy__(self, memo: Any) -> PublicKey: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class KeyPair:
     """
@@ -2707,6 +4183,9 @@ class KeyPair:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> KeyPair: ...
+    def __deepcopy__(self, memo: Any) -> KeyPair: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Signature:
     """
@@ -2745,6 +4224,9 @@ class Signature:
     def __le__(self, other) -> Any: ...
     def __lt__(self, other) -> Any: ...
     def __ne__(self, other) -> Any: ...
+    def __copy__(self) -> Signature: ...
+    def __deepcopy__(self, memo: Any) -> Signature: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Seed:
     """Base seed."""
@@ -2772,6 +4254,9 @@ class LegacySeed(Seed):
     def derive(self) -> KeyPair:
         """Derives a key pair."""
         ...
+    def __copy__(self) -> LegacySeed: ...
+    def __deepcopy__(self, memo: Any) -> LegacySeed: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Bip39Seed(Seed):
     """
@@ -2809,6 +4294,9 @@ class Bip39Seed(Seed):
 ## ASM ##
 #########
 # <editor-fold desc="ABI">
+    def __copy__(self) -> Bip39Seed: ...
+    def __deepcopy__(self, memo: Any) -> Bip39Seed: ...
+    def __reduce__(self) -> Tuple[Any, Tuple[Any, ...]]: ...
 
 class Asm:
     """
@@ -2825,3 +4313,74 @@ class Asm:
         ...
 
 # </editor-fold>
+
+############
+## ERRORS ##
+############
+# <editor-fold desc="ERRORS">
+
+class NekotonError(RuntimeError):
+    """
+    Base class for all nekoton errors.
+    """
+
+class TransportError(NekotonError):
+    """
+    Transport request failed.
+    """
+
+    endpoint: Optional[str]
+    """Endpoint which failed the request, if known."""
+
+    retriable: bool
+    """Whether the error is temporary, e.g. a connection failure or an overloaded endpoint."""
+
+class Timeout(NekotonError, TimeoutError):
+    """
+    Operation timed out.
+    """
+
+class MessageExpired(Timeout):
+    """
+    External message expired while waiting for its delivery.
+    """
+
+class AbiEncodeError(NekotonError, ValueError):
+    """
+    Failed to encode ABI values.
+    """
+
+    path: Optional[str]
+    """Path to the invalid param, e.g. `recipients[1].amount`."""
+
+class AbiDecodeError(NekotonError, ValueError):
+    """
+    Failed to decode ABI values.
+    """
+
+    path: Optional[str]
+    """Path to the invalid param, if known."""
+
+class ExecutionError(NekotonError):
+    """
+    Failed to execute a contract.
+    """
+
+    exit_code: Optional[int]
+    """Compute phase exit code, if known."""
+
+    gas_used: Optional[int]
+    """Used gas, if known."""
+
+    trace: Optional[List[VmStep]]
+    """Collected VM steps, only for traced executions."""
+
+    transaction: Optional[Transaction]
+    """Aborted transaction, only for messages sent to `LocalTransport`."""
+
+class CellError(NekotonError, ValueError):
+    """
+    Invalid cell or BOC.
+    """
+
+# </editor-fold>
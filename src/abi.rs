@@ -46,13 +46,225 @@ impl TransactionExecutor {
 
     fn execute(
         &self,
+        py: Python<'_>,
         message: &Message,
         account: Option<&AccountState>,
+        overrides: Option<ExecutionOverrides>,
+    ) -> PyResult<(Transaction, Option<AccountState>)> {
+        let kind = TransactionKind::Ordinary(&message.data);
+        self.execute_impl(py, kind, account, overrides.unwrap_or_default(), None)
+    }
+
+    /// Same as `execute`, but also collects all VM steps of the compute phase.
+    /// On failure the collected steps are attached to the error as `trace`.
+    fn execute_traced(
+        &self,
+        py: Python<'_>,
+        message: &Message,
+        account: Option<&AccountState>,
+        overrides: Option<ExecutionOverrides>,
+        trace: Option<TraceOptions>,
+    ) -> PyResult<(Transaction, Option<AccountState>, Vec<VmStep>)> {
+        let tracer = Arc::new(VmTracer::new(trace.as_ref())?);
+        let kind = TransactionKind::Ordinary(&message.data);
+        let result = self.execute_impl(
+            py,
+            kind,
+            account,
+            overrides.unwrap_or_default(),
+            Some(tracer.clone()),
+        );
+        let steps = tracer.take()?;
+        match result {
+            Ok((transaction, account)) => Ok((transaction, account, steps)),
+            Err(e) => Err(with_trace(e, steps)),
+        }
+    }
+
+    /// Runs a tick or tock transaction on the special account.
+    fn execute_tick_tock(
+        &self,
+        py: Python<'_>,
+        account: &AccountState,
+        kind: TransactionType,
+        overrides: Option<ExecutionOverrides>,
     ) -> PyResult<(Transaction, Option<AccountState>)> {
-        use ton_executor::TransactionExecutor;
+        let special = match &account.0.storage.state {
+            ton_block::AccountState::AccountActive { state_init } => state_init.special.as_ref(),
+            _ => None,
+        };
+        let Some(special) = special else {
+            return Err(PyValueError::new_err("Account is not special"));
+        };
+
+        let kind = match kind {
+            TransactionType::Tick if special.tick => {
+                TransactionKind::TickTock(ton_block::TransactionTickTock::Tick)
+            }
+            TransactionType::Tock if special.tock => {
+                TransactionKind::TickTock(ton_block::TransactionTickTock::Tock)
+            }
+            TransactionType::Tick => return Err(PyValueError::new_err("Account has no tick flag")),
+            TransactionType::Tock => return Err(PyValueError::new_err("Account has no tock flag")),
+            TransactionType::Ordinary => {
+                return Err(PyValueError::new_err(
+                    "Expected tick or tock transaction type",
+                ))
+            }
+        };
 
-        const TRANSACTION_LT_OFFSET: u64 = 10;
+        self.execute_impl(py, kind, Some(account), overrides.unwrap_or_default(), None)
+    }
+
+    /// Executes the message and delivers all produced internal messages
+    /// to the specified accounts.
+    fn execute_tree(
+        &self,
+        py: Python<'_>,
+        message: &Message,
+        accounts: &PyDict,
+        max_messages: Option<usize>,
+        overrides: Option<ExecutionOverrides>,
+    ) -> PyResult<TreeExecutionOutput> {
+        const DEFAULT_MAX_MESSAGES: usize = 10000;
+
+        let Some(root_dst) = message.data.dst() else {
+            return Err(PyValueError::new_err("Message without destination"));
+        };
+
+        let overrides = overrides.unwrap_or_default();
+        // NOTE: all transactions of the tree share the same block
+        let seed_block = overrides
+            .seed_block
+            .unwrap_or_else(|| ton_types::UInt256::from(rand::thread_rng().gen::<[u8; 32]>()));
+
+        let mut states = FastHashMap::default();
+        for (address, state) in accounts {
+            let address = address.extract::<Address>()?;
+            let account = match state.extract::<Option<AccountState>>()? {
+                Some(state) => ton_block::Account::Account(state.0),
+                None => ton_block::Account::AccountNone,
+            };
+            states.insert(address.0, account);
+        }
+        states
+            .entry(root_dst)
+            .or_insert(ton_block::Account::AccountNone);
+
+        let clock = match &self.clock {
+            Some(clock) => clock.as_ref(),
+            None => &nt::utils::SimpleClock,
+        };
+        let block_unixtime = overrides
+            .block_unixtime
+            .unwrap_or_else(|| clock.now_sec_u64() as u32);
+
+        let max_messages = max_messages.unwrap_or(DEFAULT_MAX_MESSAGES);
+        let output = py
+            .allow_threads(|| {
+                let mut lt = states
+                    .values()
+                    .filter_map(ton_block::Account::last_tr_time)
+                    .max()
+                    .unwrap_or_default();
+                // Only the root transaction uses the explicit block lt
+                let mut root_lt = overrides.block_lt;
+
+                execute_cascade(&message.data, max_messages, |message| {
+                    let Some(account) = message.dst().and_then(|dst| states.get_mut(&dst)) else {
+                        return Ok(None);
+                    };
+
+                    let last_trans_lt = account.last_tr_time().unwrap_or_default();
+                    let block_lt = root_lt.take().unwrap_or_else(|| {
+                        std::cmp::max(lt, last_trans_lt) + TRANSACTION_LT_OFFSET
+                    });
+                    let transaction = execute_ordinary_transaction(
+                        self.config.as_ref(),
+                        message,
+                        account,
+                        ExecutionParams {
+                            block_unixtime,
+                            block_lt,
+                            check_signature: self.check_signature,
+                            seed_block: Some(seed_block.clone()),
+                            gas_limit: overrides.gas_limit,
+                            behavior_modifiers: overrides.behavior_modifiers.clone(),
+                            trace: None,
+                        },
+                    )?;
+                    lt = std::cmp::max(lt, transaction.end_lt);
+
+                    Ok(Some(transaction.raw))
+                })
+            })
+            .map_err(handle_execution_error)?;
+
+        // Children are always executed after their parents
+        let mut children = vec![Vec::new(); output.transactions.len()];
+        let mut root = None;
+        for (i, item) in output.transactions.into_iter().enumerate().rev() {
+            let mut node_children = std::mem::take(&mut children[i]);
+            node_children.reverse();
+
+            let node = TransactionTree::new(py, Transaction::try_from(item.raw)?, node_children)?;
+            match item.parent {
+                Some(parent) => children[parent].push(node),
+                None => root = Some(node),
+            }
+        }
+        let root = root.ok_or_else(|| ExecutionError::new_err("Root transaction not found"))?;
 
+        let result = PyDict::new(py);
+        for (address, account) in states {
+            let state = match account {
+                ton_block::Account::Account(state) => Some(AccountState(state)),
+                ton_block::Account::AccountNone => None,
+            };
+            result.set_item(Address(address), state)?;
+        }
+
+        let pending = output
+            .pending
+            .into_iter()
+            .map(Message::try_from)
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let failed = output
+            .failed
+            .into_iter()
+            .map(|(message, e)| {
+                let error = handle_execution_error(e).into_value(py);
+                Ok((Message::try_from(message)?, error))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(TreeExecutionOutput {
+            tree: root,
+            accounts: result.into(),
+            truncated: output.truncated,
+            pending,
+            failed,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<TransactionExecutor check_signature={}>",
+            DisplayBool(self.check_signature)
+        )
+    }
+}
+
+impl TransactionExecutor {
+    fn execute_impl(
+        &self,
+        py: Python<'_>,
+        kind: TransactionKind<'_>,
+        account: Option<&AccountState>,
+        overrides: ExecutionOverrides,
+        trace: Option<Arc<VmTracer>>,
+    ) -> PyResult<(Transaction, Option<AccountState>)> {
         let (last_trans_lt, mut account) = match account {
             None => (0, ton_block::Account::AccountNone),
             Some(state) => (
@@ -61,61 +273,933 @@ impl TransactionExecutor {
             ),
         };
 
-        let mut executor =
-            ton_executor::OrdinaryTransactionExecutor::new(self.config.as_ref().clone());
-        executor.set_signature_check_disabled(!self.check_signature);
-
         let clock = match &self.clock {
             Some(clock) => clock.as_ref(),
             None => &nt::utils::SimpleClock,
         };
 
-        let block_unixtime = clock.now_sec_u64() as u32;
-        let block_lt = last_trans_lt + TRANSACTION_LT_OFFSET;
+        let params = ExecutionParams {
+            block_unixtime: overrides
+                .block_unixtime
+                .unwrap_or_else(|| clock.now_sec_u64() as u32),
+            block_lt: overrides
+                .block_lt
+                .unwrap_or(last_trans_lt + TRANSACTION_LT_OFFSET),
+            check_signature: self.check_signature,
+            seed_block: overrides.seed_block,
+            gas_limit: overrides.gas_limit,
+            behavior_modifiers: overrides.behavior_modifiers,
+            trace,
+        };
+
+        let transaction = py
+            .allow_threads(|| execute_transaction(self.config.as_ref(), kind, &mut account, params))
+            .map_err(handle_execution_error)?;
+
+        let account_state = match account {
+            ton_block::Account::AccountNone => None,
+            ton_block::Account::Account(state) => Some(AccountState(state)),
+        };
+
+        Ok((Transaction::try_from(transaction.raw)?, account_state))
+    }
+}
+
+/// Block params and VM flags which override the executor defaults.
+#[derive(Default, Clone)]
+#[pyclass]
+pub struct ExecutionOverrides {
+    block_lt: Option<u64>,
+    block_unixtime: Option<u32>,
+    seed_block: Option<ton_types::UInt256>,
+    gas_limit: Option<u64>,
+    behavior_modifiers: Option<BehaviorModifiers>,
+}
+
+#[pymethods]
+impl ExecutionOverrides {
+    #[new]
+    fn new(
+        block_lt: Option<u64>,
+        block_unixtime: Option<u32>,
+        seed_block: Option<&[u8]>,
+        gas_limit: Option<u64>,
+        behavior_modifiers: Option<BehaviorModifiers>,
+    ) -> PyResult<Self> {
+        let seed_block = match seed_block {
+            Some(seed_block) => Some(ton_types::UInt256::from_bytes(seed_block, "seed block")?),
+            None => None,
+        };
+
+        Ok(Self {
+            block_lt,
+            block_unixtime,
+            seed_block,
+            gas_limit,
+            behavior_modifiers,
+        })
+    }
+
+    #[getter]
+    fn block_lt(&self) -> Option<u64> {
+        self.block_lt
+    }
+
+    #[getter]
+    fn block_unixtime(&self) -> Option<u32> {
+        self.block_unixtime
+    }
+
+    #[getter]
+    fn seed_block<'a>(&self, py: Python<'a>) -> Option<&'a PyBytes> {
+        let seed_block = self.seed_block.as_ref()?;
+        Some(PyBytes::new(py, seed_block.as_slice()))
+    }
+
+    #[getter]
+    fn gas_limit(&self) -> Option<u64> {
+        self.gas_limit
+    }
+
+    #[getter]
+    fn behavior_modifiers(&self) -> Option<BehaviorModifiers> {
+        self.behavior_modifiers.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        fn field_repr<T: std::fmt::Display>(value: &Option<T>) -> String {
+            match value {
+                Some(value) => value.to_string(),
+                None => "None".to_owned(),
+            }
+        }
+
+        format!(
+            "<ExecutionOverrides block_lt={}, block_unixtime={}, gas_limit={}>",
+            field_repr(&self.block_lt),
+            field_repr(&self.block_unixtime),
+            field_repr(&self.gas_limit),
+        )
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        let args = (
+            self.block_lt,
+            self.block_unixtime,
+            self.seed_block(py),
+            self.gas_limit,
+            self.behavior_modifiers.clone(),
+        );
+        reduce_new::<Self>(py, args)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+}
+
+/// Result of the [`TransactionExecutor::execute_tree`].
+#[pyclass(get_all)]
+pub struct TreeExecutionOutput {
+    tree: Py<TransactionTree>,
+    accounts: Py<PyDict>,
+    truncated: bool,
+    pending: Vec<Message>,
+    failed: Vec<(Message, Py<PyBaseException>)>,
+}
+
+#[pymethods]
+impl TreeExecutionOutput {
+    fn __repr__(&self) -> String {
+        format!(
+            "<TreeExecutionOutput truncated={}, pending={}, failed={}>",
+            DisplayBool(self.truncated),
+            self.pending.len(),
+            self.failed.len(),
+        )
+    }
+}
+
+/// VM trace collection options.
+#[derive(Clone, Default)]
+#[pyclass]
+pub struct TraceOptions {
+    max_steps: Option<usize>,
+    path: Option<PathBuf>,
+}
+
+#[pymethods]
+impl TraceOptions {
+    #[new]
+    fn new(max_steps: Option<usize>, path: Option<PathBuf>) -> PyResult<Self> {
+        if max_steps == Some(0) {
+            return Err(PyValueError::new_err("max_steps must be greater than 0"));
+        }
+        Ok(Self { max_steps, path })
+    }
+
+    #[getter]
+    fn max_steps(&self) -> Option<usize> {
+        self.max_steps
+    }
+
+    #[getter]
+    fn path(&self) -> Option<PathBuf> {
+        self.path.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        let max_steps = match self.max_steps {
+            Some(max_steps) => max_steps.to_string(),
+            None => "None".to_owned(),
+        };
+        let path = match &self.path {
+            Some(path) => format!("'{}'", path.display()),
+            None => "None".to_owned(),
+        };
+        format!("TraceOptions(max_steps={max_steps}, path={path})")
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.max_steps, self.path.clone()))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+}
+
+/// Collects VM steps reported by the trace callback.
+///
+/// Steps are either kept in memory or written to the file sink as JSON lines.
+pub(crate) struct VmTracer {
+    max_steps: Option<usize>,
+    state: parking_lot::Mutex<VmTracerState>,
+}
+
+#[derive(Default)]
+struct VmTracerState {
+    count: usize,
+    steps: Vec<VmStep>,
+    sink: Option<std::io::BufWriter<std::fs::File>>,
+    error: Option<std::io::Error>,
+}
+
+impl VmTracer {
+    /// Number of stack items from the top which are stored for each step.
+    const STACK_DEPTH: usize = 5;
+
+    /// Instructions which convert a cell into a slice (and pay for the cell load).
+    const CELL_LOAD_INSTRUCTIONS: &'static [&'static str] = &["CTOS", "XCTOS", "LDREFRTOS"];
+
+    pub(crate) fn new(options: Option<&TraceOptions>) -> PyResult<Self> {
+        let options = options.cloned().unwrap_or_default();
+        let sink = match &options.path {
+            Some(path) => Some(std::io::BufWriter::new(std::fs::File::create(path)?)),
+            None => None,
+        };
+
+        Ok(Self {
+            max_steps: options.max_steps,
+            state: parking_lot::Mutex::new(VmTracerState {
+                sink,
+                ..Default::default()
+            }),
+        })
+    }
+
+    fn push(
+        &self,
+        engine: &ton_vm::executor::Engine,
+        info: &ton_vm::executor::EngineTraceInfo<'_>,
+    ) {
+        let state = &mut *self.state.lock();
+        if matches!(self.max_steps, Some(max_steps) if state.count >= max_steps) {
+            return;
+        }
+        state.count += 1;
+
+        let depth = std::cmp::min(info.stack.depth(), Self::STACK_DEPTH);
+        let stack = (0..depth).map(|i| info.stack.get(i).to_string()).collect();
+
+        // NOTE: the trace is reported after the instruction, so the loaded
+        // cells are the slices on top of the stack which start at the beginning.
+        let opcode = info.cmd_str.split_whitespace().next().unwrap_or_default();
+        let cell_loads = if Self::CELL_LOAD_INSTRUCTIONS.contains(&opcode) {
+            (0..std::cmp::min(info.stack.depth(), 2))
+                .filter_map(|i| match info.stack.get(i) {
+                    ton_vm::stack::StackItem::Slice(slice)
+                        if slice.pos() == 0 && slice.get_references().start == 0 =>
+                    {
+                        Some(slice.cell().repr_hash())
+                    }
+                    _ => None,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let step = VmStep {
+            step: info.step,
+            kind: format!("{:?}", info.info_type),
+            instruction: info.cmd_str.clone(),
+            gas_used: info.gas_used,
+            gas_cmd: info.gas_cmd,
+            gas_remaining: engine.gas_remaining(),
+            stack,
+            code_hash: info.cmd_code.cell().repr_hash(),
+            code_offset: info.cmd_code.pos(),
+            cell_loads,
+        };
+
+        match &mut state.sink {
+            Some(sink) if state.error.is_none() => {
+                if let Err(e) = step.write_json(sink) {
+                    state.error = Some(e);
+                }
+            }
+            Some(_) => {}
+            None => state.steps.push(step),
+        }
+    }
+
+    /// Returns collected steps and flushes the file sink.
+    pub(crate) fn take(&self) -> PyResult<Vec<VmStep>> {
+        let state = &mut *self.state.lock();
+        if let Some(sink) = &mut state.sink {
+            if let Err(e) = std::io::Write::flush(sink) {
+                state.error.get_or_insert(e);
+            }
+        }
+        if let Some(e) = state.error.take() {
+            return Err(PyIOError::new_err(format!("Failed to write VM trace: {e}")));
+        }
+        Ok(std::mem::take(&mut state.steps))
+    }
+}
+
+/// Attaches collected VM steps to the error as a `trace` attribute.
+fn with_trace(error: PyErr, steps: Vec<VmStep>) -> PyErr {
+    with_error_attrs(error, |py, value| value.setattr("trace", steps.into_py(py)))
+}
+
+struct TracedCallOutput {
+    exit_code: i32,
+    stack: Vec<ton_vm::stack::StackItem>,
+    actions: Option<ton_types::Cell>,
+}
+
+/// Runs the account code on the specified stack without a transaction,
+/// reporting every VM step to the tracer.
+///
+/// The VM is set up like for untraced local calls: gas limits and prices
+/// are taken from the config and the account libraries are available.
+fn run_traced_call(
+    account: &ton_block::AccountStuff,
+    stack: Vec<ton_vm::stack::StackItem>,
+    clock: &dyn nt::utils::Clock,
+    config: &nt::abi::BriefBlockchainConfig,
+    tracer: Arc<VmTracer>,
+) -> anyhow::Result<TracedCallOutput> {
+    use ton_vm::stack::{savelist::SaveList, Stack, StackItem};
+
+    let ton_block::AccountState::AccountActive { state_init } = &account.storage.state else {
+        anyhow::bail!("Account is not active");
+    };
+    let (Some(code), Some(data)) = (state_init.code(), state_init.data()) else {
+        anyhow::bail!("Account has no code or data");
+    };
+
+    let info = ton_vm::SmartContractInfo {
+        capabilities: config.capabilities,
+        myself: ton_types::SliceData::load_builder(account.addr.write_to_new_cell()?)?,
+        block_lt: account.storage.last_trans_lt,
+        trans_lt: account.storage.last_trans_lt,
+        unix_time: clock.now_sec_u64() as u32,
+        balance: account.storage.balance.clone(),
+        config_params: config.raw_config.config_params.data().cloned(),
+        mycode: code.clone(),
+        ..Default::default()
+    };
+
+    let mut ctrls = SaveList::default();
+    ctrls.put(4, &mut StackItem::Cell(data.clone()))?;
+    ctrls.put(7, &mut info.into_temp_data_item())?;
+
+    let mut vm_stack = Stack::new();
+    for item in stack {
+        vm_stack.push(item);
+    }
+
+    let gas_prices = if account.addr.is_masterchain() {
+        &config.mc_gas_prices
+    } else {
+        &config.gas_prices
+    };
+    let gas_limit = gas_prices.gas_limit as i64;
+    let gas = ton_vm::executor::gas::gas_state::Gas::new(
+        gas_limit,
+        0,
+        gas_limit,
+        gas_prices.get_real_gas_price() as i64,
+    );
+
+    let mut engine = ton_vm::executor::Engine::with_capabilities(config.capabilities)
+        .setup_with_libraries(
+            ton_types::SliceData::load_cell_ref(code)?,
+            Some(ctrls),
+            Some(vm_stack),
+            Some(gas),
+            vec![state_init.libraries().inner()],
+        );
+    engine.set_trace_callback(move |engine, info| tracer.push(engine, info));
+
+    let exit_code = match engine.execute() {
+        Ok(exit_code) => exit_code,
+        Err(e) => ton_vm::error::tvm_exception_or_custom_code(&e),
+    };
+
+    Ok(TracedCallOutput {
+        exit_code,
+        stack: engine.stack().storage.clone(),
+        actions: engine
+            .get_committed_state()
+            .get_actions()
+            .as_cell()
+            .ok()
+            .cloned(),
+    })
+}
+
+/// A single VM step.
+#[derive(Clone)]
+#[pyclass]
+pub struct VmStep {
+    step: u32,
+    kind: String,
+    instruction: String,
+    gas_used: i64,
+    gas_cmd: i64,
+    gas_remaining: i64,
+    stack: Vec<String>,
+    code_hash: ton_types::UInt256,
+    code_offset: usize,
+    cell_loads: Vec<ton_types::UInt256>,
+}
+
+impl VmStep {
+    fn write_json<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let value = serde_json::json!({
+            "step": self.step,
+            "kind": self.kind,
+            "instruction": self.instruction,
+            "gas_used": self.gas_used,
+            "gas_cmd": self.gas_cmd,
+            "gas_remaining": self.gas_remaining,
+            "stack": self.stack,
+            "code_hash": self.code_hash.to_hex_string(),
+            "code_offset": self.code_offset,
+            "cell_loads": self
+                .cell_loads
+                .iter()
+                .map(|hash| hash.to_hex_string())
+                .collect::<Vec<_>>(),
+        });
+        serde_json::to_writer(&mut *writer, &value)?;
+        writer.write_all(b"\n")
+    }
+}
+
+#[pymethods]
+impl VmStep {
+    #[getter]
+    fn step(&self) -> u32 {
+        self.step
+    }
+
+    #[getter]
+    fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    #[getter]
+    fn instruction(&self) -> &str {
+        &self.instruction
+    }
+
+    #[getter]
+    fn gas_used(&self) -> i64 {
+        self.gas_used
+    }
+
+    #[getter]
+    fn gas_cmd(&self) -> i64 {
+        self.gas_cmd
+    }
+
+    #[getter]
+    fn gas_remaining(&self) -> i64 {
+        self.gas_remaining
+    }
+
+    #[getter]
+    fn stack(&self) -> Vec<String> {
+        self.stack.clone()
+    }
+
+    #[getter]
+    fn code_hash<'a>(&self, py: Python<'a>) -> &'a PyBytes {
+        PyBytes::new(py, self.code_hash.as_slice())
+    }
+
+    #[getter]
+    fn code_offset(&self) -> usize {
+        self.code_offset
+    }
+
+    #[getter]
+    fn cell_loads<'a>(&self, py: Python<'a>) -> Vec<&'a PyBytes> {
+        self.cell_loads
+            .iter()
+            .map(|hash| PyBytes::new(py, hash.as_slice()))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<VmStep step={}, {}, instruction='{}', gas_used={}, code_hash='{:x}', code_offset={}>",
+            self.step, self.kind, self.instruction, self.gas_used, self.code_hash, self.code_offset
+        )
+    }
+}
+
+/// Logical time gap between the last account transaction and the emulated one.
+pub(crate) const TRANSACTION_LT_OFFSET: u64 = 10;
+
+#[derive(Default)]
+pub(crate) struct ExecutionParams {
+    pub block_unixtime: u32,
+    pub block_lt: u64,
+    pub check_signature: bool,
+    /// Random seed for the VM, generated when not specified.
+    pub seed_block: Option<ton_types::UInt256>,
+    /// Overrides gas limits from ConfigParam20 and ConfigParam21.
+    pub gas_limit: Option<u64>,
+    pub behavior_modifiers: Option<BehaviorModifiers>,
+    pub trace: Option<Arc<VmTracer>>,
+}
+
+pub(crate) struct ExecutedTransaction {
+    pub raw: nt::transport::models::RawTransaction,
+    /// Logical time right after the last produced message.
+    pub end_lt: u64,
+}
+
+pub(crate) enum TransactionKind<'a> {
+    Ordinary(&'a ton_block::Message),
+    TickTock(ton_block::TransactionTickTock),
+}
+
+/// Runs an ordinary transaction and updates the account storage stat.
+pub(crate) fn execute_ordinary_transaction(
+    config: &ton_executor::BlockchainConfig,
+    message: &ton_block::Message,
+    account: &mut ton_block::Account,
+    params: ExecutionParams,
+) -> anyhow::Result<ExecutedTransaction> {
+    execute_transaction(config, TransactionKind::Ordinary(message), account, params)
+}
+
+/// Runs a transaction of the specified kind and updates the account storage stat.
+pub(crate) fn execute_transaction(
+    config: &ton_executor::BlockchainConfig,
+    kind: TransactionKind<'_>,
+    account: &mut ton_block::Account,
+    params: ExecutionParams,
+) -> anyhow::Result<ExecutedTransaction> {
+    let config = match params.gas_limit {
+        Some(gas_limit) => override_gas_limit(config, gas_limit)?,
+        None => config.clone(),
+    };
+
+    match kind {
+        TransactionKind::Ordinary(message) => {
+            let mut executor = ton_executor::OrdinaryTransactionExecutor::new(config);
+            executor.set_signature_check_disabled(!params.check_signature);
+            run_executor(&executor, Some(message), account, params)
+        }
+        TransactionKind::TickTock(tt) => {
+            let executor = ton_executor::TickTockTransactionExecutor::new(config, tt);
+            run_executor(&executor, None, account, params)
+        }
+    }
+}
+
+fn run_executor<E: ton_executor::TransactionExecutor>(
+    executor: &E,
+    message: Option<&ton_block::Message>,
+    account: &mut ton_block::Account,
+    params: ExecutionParams,
+) -> anyhow::Result<ExecutedTransaction> {
+    // NOTE: only the exposed flags are overridden, the rest come from the config
+    let mut behavior_modifiers = executor.behavior_modifiers();
+    if let Some(modifiers) = &params.behavior_modifiers {
+        behavior_modifiers.chksig_always_succeed = modifiers.chksig_always_succeed;
+    }
+
+    let seed_block = params
+        .seed_block
+        .unwrap_or_else(|| ton_types::UInt256::from(rand::thread_rng().gen::<[u8; 32]>()));
+
+    // NOTE: executor errors don't include the used gas,
+    // so it is tracked by the trace callback of the same run.
+    let gas_used = Arc::new(AtomicU64::new(0));
+    let trace_callback = {
+        let gas_used = gas_used.clone();
+        let tracer = params.trace.clone();
+        move |engine: &ton_vm::executor::Engine, info: &ton_vm::executor::EngineTraceInfo<'_>| {
+            gas_used.store(info.gas_used as u64, std::sync::atomic::Ordering::Release);
+            if let Some(tracer) = &tracer {
+                tracer.push(engine, info);
+            }
+        }
+    };
+
+    let last_tr_lt = Arc::new(AtomicU64::new(params.block_lt));
+    let execute_params = ton_executor::ExecuteParams {
+        block_unixtime: params.block_unixtime,
+        block_lt: params.block_lt,
+        last_tr_lt: last_tr_lt.clone(),
+        seed_block,
+        behavior_modifiers: Some(behavior_modifiers),
+        trace_callback: Some(Arc::new(trace_callback)),
+        ..Default::default()
+    };
+
+    let transaction = match executor.execute_with_params(message, account, execute_params) {
+        Ok(transaction) => transaction,
+        Err(e) if is_compute_phase_error(&e) => {
+            let gas_used = gas_used.load(std::sync::atomic::Ordering::Acquire);
+            return Err(e.context(ComputePhaseFailed { gas_used }));
+        }
+        Err(e) => return Err(e),
+    };
+    let hash = transaction.hash()?;
+
+    if executor
+        .config()
+        .has_capability(ton_block::GlobalCapabilities::CapFastStorageStat)
+    {
+        account.update_storage_stat_fast()?;
+    } else {
+        account.update_storage_stat()?;
+    }
+
+    Ok(ExecutedTransaction {
+        raw: nt::transport::models::RawTransaction {
+            data: transaction,
+            hash,
+        },
+        end_lt: last_tr_lt.load(std::sync::atomic::Ordering::Acquire),
+    })
+}
+
+pub(crate) struct CascadeTransaction {
+    /// Index of the transaction which produced the message.
+    pub parent: Option<usize>,
+    pub raw: nt::transport::models::RawTransaction,
+}
+
+pub(crate) struct CascadeMessage {
+    /// Index of the transaction which produced the message.
+    pub parent: Option<usize>,
+    pub message: ton_block::Message,
+}
+
+/// Delivery queue for the root message and all internal messages produced by it.
+///
+/// Internal messages are delivered in the order of their creation lt.
+pub(crate) struct MessageCascade {
+    messages: BTreeMap<(u64, usize), CascadeMessage>,
+    seqno: usize,
+    delivered: usize,
+    max_messages: usize,
+    truncated: bool,
+    transactions: Vec<CascadeTransaction>,
+    pending: Vec<ton_block::Message>,
+    failed: Vec<(ton_block::Message, anyhow::Error)>,
+}
+
+impl MessageCascade {
+    pub fn new(root: ton_block::Message, max_messages: usize) -> Self {
+        let mut messages = BTreeMap::new();
+        messages.insert(
+            (0, 0),
+            CascadeMessage {
+                parent: None,
+                message: root,
+            },
+        );
+
+        Self {
+            messages,
+            seqno: 1,
+            delivered: 0,
+            max_messages,
+            truncated: false,
+            transactions: Vec::new(),
+            pending: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    /// Returns the next message to deliver.
+    ///
+    /// `None` means that the queue is drained or the message limit is reached.
+    pub fn next(&mut self) -> Option<CascadeMessage> {
+        let entry = self.messages.first_entry()?;
+        if entry.get().parent.is_some() {
+            if self.delivered >= self.max_messages {
+                self.truncated = true;
+                return None;
+            }
+            self.delivered += 1;
+        }
+        Some(entry.remove())
+    }
+
+    /// Handles the execution result of the message returned by [`MessageCascade::next`].
+    ///
+    /// `None` means that the message was not delivered.
+    /// Only errors for the root message are propagated.
+    pub fn complete(
+        &mut self,
+        message: CascadeMessage,
+        result: anyhow::Result<Option<nt::transport::models::RawTransaction>>,
+    ) -> anyhow::Result<()> {
+        let raw = match result {
+            Ok(Some(raw)) => raw,
+            Ok(None) => {
+                self.pending.push(message.message);
+                return Ok(());
+            }
+            Err(e) if message.parent.is_none() => return Err(e),
+            Err(e) => {
+                self.failed.push((message.message, e));
+                return Ok(());
+            }
+        };
+
+        let index = self.transactions.len();
+        raw.data.out_msgs.iterate_slices(|slice| {
+            if let Some(msg_cell) = slice.reference_opt(0) {
+                let message = ton_block::Message::construct_from_cell(msg_cell)?;
+                if message.is_internal() {
+                    let key = (message.lt().unwrap_or_default(), self.seqno);
+                    self.seqno += 1;
+                    self.messages.insert(
+                        key,
+                        CascadeMessage {
+                            parent: Some(index),
+                            message,
+                        },
+                    );
+                }
+            }
+            Ok(true)
+        })?;
+
+        self.transactions.push(CascadeTransaction {
+            parent: message.parent,
+            raw,
+        });
+        Ok(())
+    }
+
+    pub fn finish(self) -> CascadeOutput {
+        let mut pending = self.pending;
+        pending.extend(self.messages.into_values().map(|item| item.message));
+
+        CascadeOutput {
+            transactions: self.transactions,
+            pending,
+            failed: self.failed,
+            truncated: self.truncated,
+        }
+    }
+}
+
+pub(crate) struct CascadeOutput {
+    pub transactions: Vec<CascadeTransaction>,
+    /// Messages which were not delivered (including the ones left after truncation).
+    pub pending: Vec<ton_block::Message>,
+    /// Messages which failed to execute.
+    pub failed: Vec<(ton_block::Message, anyhow::Error)>,
+    /// Whether the message limit was reached.
+    pub truncated: bool,
+}
+
+/// Executes the root message and all internal messages produced by it.
+pub(crate) fn execute_cascade<F>(
+    root: &ton_block::Message,
+    max_messages: usize,
+    mut execute: F,
+) -> anyhow::Result<CascadeOutput>
+where
+    F: FnMut(&ton_block::Message) -> anyhow::Result<Option<nt::transport::models::RawTransaction>>,
+{
+    let mut cascade = MessageCascade::new(root.clone(), max_messages);
+    while let Some(message) = cascade.next() {
+        let result = execute(&message.message);
+        cascade.complete(message, result)?;
+    }
+    Ok(cascade.finish())
+}
+
+/// Returns a copy of the config with gas limits replaced for both workchains.
+fn override_gas_limit(
+    config: &ton_executor::BlockchainConfig,
+    gas_limit: u64,
+) -> anyhow::Result<ton_executor::BlockchainConfig> {
+    use ton_block::ConfigParamEnum;
+
+    let mut params = config.raw_config().clone();
+    for index in [20, 21] {
+        let mut prices = match params.config(index)? {
+            Some(
+                ConfigParamEnum::ConfigParam20(prices) | ConfigParamEnum::ConfigParam21(prices),
+            ) => prices,
+            _ => continue,
+        };
+        prices.gas_limit = gas_limit;
+        prices.special_gas_limit = gas_limit;
+
+        params.set_config(if index == 20 {
+            ConfigParamEnum::ConfigParam20(prices)
+        } else {
+            ConfigParamEnum::ConfigParam21(prices)
+        })?;
+    }
+
+    ton_executor::BlockchainConfig::with_config(params, config.global_id())
+}
+
+/// Context of the executor errors with the gas used before the failure.
+#[derive(Debug)]
+struct ComputePhaseFailed {
+    gas_used: u64,
+}
+
+impl std::fmt::Display for ComputePhaseFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "compute phase failed after using {} gas", self.gas_used)
+    }
+}
+
+fn compute_phase_exit_code(error: &anyhow::Error) -> Option<i32> {
+    match error.downcast_ref::<ton_executor::ExecutorError>()? {
+        ton_executor::ExecutorError::NoAcceptError(exit_code, _) => Some(*exit_code),
+        ton_executor::ExecutorError::TvmExceptionCode(code) => Some(*code as i32),
+        _ => None,
+    }
+}
+
+fn is_compute_phase_error(error: &anyhow::Error) -> bool {
+    compute_phase_exit_code(error).is_some()
+}
+
+/// Context of the errors of the root message of a local cascade.
+#[derive(Debug, Default)]
+pub(crate) struct RootExecutionFailed {
+    /// Aborted root transaction, if the message was accepted.
+    pub transaction: Option<nt::transport::models::RawTransaction>,
+}
+
+impl std::fmt::Display for RootExecutionFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.transaction {
+            Some(transaction) => write!(f, "transaction {:x} aborted", transaction.hash),
+            None => f.write_str("failed to execute message"),
+        }
+    }
+}
 
-        let params = ton_executor::ExecuteParams {
-            block_unixtime,
-            block_lt,
-            last_tr_lt: Arc::new(AtomicU64::new(block_lt)),
-            seed_block: ton_types::UInt256::from(rand::thread_rng().gen::<[u8; 32]>()),
-            behavior_modifiers: Some(executor.behavior_modifiers()),
-            ..Default::default()
-        };
+pub(crate) fn handle_execution_error(error: anyhow::Error) -> PyErr {
+    let exit_code = compute_phase_exit_code(&error);
+    let gas_used = error
+        .downcast_ref::<ComputePhaseFailed>()
+        .map(|context| context.gas_used);
+    let transaction = error
+        .downcast_ref::<RootExecutionFailed>()
+        .and_then(|context| context.transaction.clone());
+
+    let error = execution_error(format!("{error:#}"), exit_code, gas_used);
+    match transaction {
+        Some(transaction) => with_error_attrs(error, |py, value| {
+            let transaction = Transaction::try_from(transaction)?;
+            value.setattr("transaction", transaction.into_py(py))
+        }),
+        None => error,
+    }
+}
 
-        let transaction = executor
-            .execute_with_params(Some(&message.data), &mut account, params)
-            .handle_runtime_error()?;
-        let hash = transaction.hash().handle_runtime_error()?;
+/// VM behavior flags applied on top of the executor defaults.
+///
+/// NOTE: other executor flags (e.g. the signature id) are derived from
+/// the blockchain config, so they are not exposed to keep emulation
+/// consistent with the network.
+#[derive(Default, Clone)]
+#[pyclass]
+pub struct BehaviorModifiers {
+    chksig_always_succeed: bool,
+}
 
-        if executor
-            .config()
-            .has_capability(ton_block::GlobalCapabilities::CapFastStorageStat)
-        {
-            account.update_storage_stat_fast().handle_runtime_error()?;
-        } else {
-            account.update_storage_stat().handle_runtime_error()?;
+#[pymethods]
+impl BehaviorModifiers {
+    #[new]
+    fn new(chksig_always_succeed: Option<bool>) -> Self {
+        Self {
+            chksig_always_succeed: chksig_always_succeed.unwrap_or_default(),
         }
+    }
 
-        let account_state = match account {
-            ton_block::Account::AccountNone => None,
-            ton_block::Account::Account(state) => Some(AccountState(state)),
-        };
+    #[getter]
+    fn get_chksig_always_succeed(&self) -> bool {
+        self.chksig_always_succeed
+    }
 
-        Ok((
-            Transaction::try_from(nt::transport::models::RawTransaction {
-                data: transaction,
-                hash,
-            })?,
-            account_state,
-        ))
+    #[setter]
+    fn set_chksig_always_succeed(&mut self, value: bool) {
+        self.chksig_always_succeed = value;
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "<TransactionExecutor check_signature={}>",
-            DisplayBool(self.check_signature)
+            "<BehaviorModifiers chksig_always_succeed={}>",
+            DisplayBool(self.chksig_always_succeed)
         )
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.chksig_always_succeed,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
 #[derive(Clone)]
@@ -126,7 +1210,7 @@ pub struct ContractAbi(Arc<SharedContractAbi>);
 impl ContractAbi {
     #[staticmethod]
     fn from_file(path: PathBuf) -> PyResult<Self> {
-        let abi = std::fs::read_to_string(path).handle_runtime_error()?;
+        let abi = std::fs::read_to_string(path)?;
         Self::new(&abi)
     }
 
@@ -134,26 +1218,43 @@ impl ContractAbi {
     fn new(abi: &str) -> PyResult<Self> {
         let contract =
             ton_abi::Contract::load(std::io::Cursor::new(abi.trim())).handle_value_error()?;
+        let source = Arc::<str>::from(abi.trim());
 
         let functions = contract
             .functions
             .iter()
-            .map(|(name, abi)| (name.clone(), FunctionAbi(Arc::new(abi.clone()))))
+            .map(|(name, abi)| {
+                (
+                    name.clone(),
+                    FunctionAbi(Arc::new(abi.clone()), source.clone()),
+                )
+            })
             .collect();
 
         let events = contract
             .events
             .iter()
-            .map(|(name, abi)| (name.clone(), EventAbi(Arc::new(abi.clone()))))
+            .map(|(name, abi)| {
+                (
+                    name.clone(),
+                    EventAbi(Arc::new(abi.clone()), source.clone()),
+                )
+            })
             .collect();
 
         let getters = contract
             .getters
             .iter()
-            .map(|(name, abi)| (name.clone(), GetterAbi(Arc::new(abi.clone()))))
+            .map(|(name, abi)| {
+                (
+                    name.clone(),
+                    GetterAbi(Arc::new(abi.clone()), source.clone()),
+                )
+            })
             .collect();
 
         let shared = Arc::new(SharedContractAbi {
+            source,
             contract,
             functions,
             events,
@@ -249,7 +1350,7 @@ impl ContractAbi {
                 }
             },
         };
-        let data = ton_types::SliceData::load_cell(data).handle_value_error()?;
+        let data = ton_types::SliceData::load_cell(data).handle_cell_error()?;
 
         let contract = &self.0.contract;
         let tokens = ton_abi::TokenValue::decode_params(
@@ -258,7 +1359,7 @@ impl ContractAbi {
             &contract.abi_version,
             allow_partial.unwrap_or_default(),
         )
-        .handle_value_error()?;
+        .handle_abi_decode_error()?;
         convert_tokens(py, tokens)
     }
 
@@ -272,7 +1373,7 @@ impl ContractAbi {
         let contract = &self.0.contract;
         let tx = &transaction.0.data;
 
-        let Some(in_msg) = tx.read_in_msg().handle_runtime_error()? else {
+        let Some(in_msg) = tx.read_in_msg().handle_cell_error()? else {
             return Ok(None);
         };
         let Some(in_msg_body) = in_msg.body() else {
@@ -294,7 +1395,7 @@ impl ContractAbi {
         let input = function
             .0
             .decode_input(in_msg_body, in_msg.is_internal(), false)
-            .handle_runtime_error()?;
+            .handle_abi_decode_error()?;
 
         let mut output = None;
         let mut events = Vec::new();
@@ -321,12 +1422,12 @@ impl ContractAbi {
 
                 Ok(true)
             })
-            .handle_runtime_error()?;
+            .handle_abi_decode_error()?;
 
         let output = match output {
             Some(x) => x,
             None if !function.0.has_output() => Default::default(),
-            None => return Err(PyRuntimeError::new_err("No output messages produced")),
+            None => return Err(AbiDecodeError::new_err("No output messages produced")),
         };
 
         let events = events
@@ -384,13 +1485,25 @@ impl ContractAbi {
 
                 Ok(true)
             })
-            .handle_runtime_error()?;
+            .handle_abi_decode_error()?;
 
         events
             .into_iter()
             .map(|(event, input)| PyResult::Ok((event.clone(), convert_tokens(py, input)?)))
             .collect::<PyResult<Vec<_>>>()
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.0.source.as_ref(),))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
 fn encode_init_data_dict(
@@ -411,32 +1524,28 @@ fn encode_init_data_dict(
                 .append_raw(public_key.0.as_bytes(), 256)
                 .unwrap(),
         )
-        .handle_runtime_error()?;
+        .handle_abi_encode_error()?;
     }
 
     if !contract.data.is_empty() {
         for (param_name, param) in &contract.data {
             let value = match data.get_item(param_name) {
-                Some(value) => parse_token(&param.value.kind, value)?,
-                None => {
-                    return Err(PyValueError::new_err(format!(
-                        "Param '{param_name}' not found"
-                    )))
-                }
+                Some(value) => parse_named_token(param_name, &param.value.kind, value)?,
+                None => return Err(param_not_found(param_name)),
             };
 
             let builder = value
                 .pack_into_chain(&contract.abi_version)
-                .handle_runtime_error()?;
+                .handle_abi_encode_error()?;
 
             map.set_builder(serialize_state_init_data_key(param.key), &builder)
-                .handle_runtime_error()?;
+                .handle_abi_encode_error()?;
         }
     }
 
     map.write_to_new_cell()
         .and_then(ton_types::BuilderData::into_cell)
-        .handle_runtime_error()
+        .handle_abi_encode_error()
         .map(Cell)
 }
 
@@ -470,9 +1579,9 @@ fn decode_init_data_dict<'a>(
         }
     };
 
-    let data = ton_types::SliceData::load_cell_ref(&data.0).handle_value_error()?;
+    let data = ton_types::SliceData::load_cell_ref(&data.0).handle_cell_error()?;
 
-    let tokens = contract.decode_init_data(data).handle_value_error()?;
+    let tokens = contract.decode_init_data(data).handle_abi_decode_error()?;
     Ok((pubkey, convert_tokens(py, tokens)?))
 }
 
@@ -482,19 +1591,19 @@ fn update_init_data_plain(
     mut public_key: Option<&PublicKey>,
     existing_data: Cell,
 ) -> PyResult<Cell> {
-    let data_slice = ton_types::SliceData::load_cell(existing_data.0).handle_value_error()?;
+    let data_slice = ton_types::SliceData::load_cell(existing_data.0).handle_cell_error()?;
     let old_values = ton_abi::TokenValue::decode_params(
         &contract.fields,
         data_slice,
         &contract.abi_version,
         false,
     )
-    .handle_value_error()?;
+    .handle_abi_decode_error()?;
 
     let mut result = Vec::with_capacity(old_values.len());
     for (field, old_value) in std::iter::zip(&contract.fields, old_values) {
         let mut token = match data.get_item(&field.name) {
-            Some(value) => Some(parse_token(&field.kind, value)?),
+            Some(value) => Some(parse_named_token(&field.name, &field.kind, value)?),
             None => None,
         };
 
@@ -529,7 +1638,7 @@ fn update_init_data_plain(
     ton_abi::TokenValue::pack_values_into_chain(&result, Vec::new(), &contract.abi_version)
         .and_then(ton_types::BuilderData::into_cell)
         .map(Cell)
-        .handle_runtime_error()
+        .handle_abi_encode_error()
 }
 
 fn encode_init_data_plain(
@@ -540,7 +1649,8 @@ fn encode_init_data_plain(
     let mut init_fields = HashMap::default();
     for field in &contract.fields {
         if let Some(value) = data.get_item(&field.name) {
-            init_fields.insert(field.name.clone(), parse_token(&field.kind, value)?);
+            let value = parse_named_token(&field.name, &field.kind, value)?;
+            init_fields.insert(field.name.clone(), value);
         }
     }
 
@@ -565,7 +1675,7 @@ fn encode_init_data_plain(
         .encode_storage_fields(init_fields)
         .and_then(ton_types::BuilderData::into_cell)
         .map(Cell)
-        .handle_runtime_error()
+        .handle_abi_encode_error()
 }
 
 fn decode_init_data_plain<'a>(
@@ -575,14 +1685,14 @@ fn decode_init_data_plain<'a>(
 ) -> PyResult<(Option<PublicKey>, &'a PyDict)> {
     use nt::abi::UnpackAbi;
 
-    let data_slice = ton_types::SliceData::load_cell(data.0.clone()).handle_value_error()?;
+    let data_slice = ton_types::SliceData::load_cell(data.0.clone()).handle_cell_error()?;
     let mut values = ton_abi::TokenValue::decode_params(
         &contract.fields,
         data_slice,
         &contract.abi_version,
         false,
     )
-    .handle_value_error()?;
+    .handle_abi_decode_error()?;
 
     values.retain(|item| contract.init_fields.contains(&item.name));
 
@@ -604,7 +1714,58 @@ fn decode_init_data_plain<'a>(
 
 const PUBKEY_FIELD: &str = "_pubkey";
 
+/// Reduces an ABI item to a lookup in the contract ABI it was loaded from
+/// (e.g. `ContractAbi(source).function(name)`).
+fn reduce_contract_item(
+    py: Python<'_>,
+    source: &str,
+    method: &str,
+    name: &str,
+) -> PyResult<PyReduce> {
+    let source = contract_item_abi(source, method, name)?;
+    let contract = Py::new(py, ContractAbi::new(&source)?)?;
+    Ok((contract.getattr(py, method)?, (name,).into_py(py)))
+}
+
+/// Builds a contract ABI JSON which contains only the specified item
+/// and the common fields (version and headers).
+fn contract_item_abi(source: &str, method: &str, name: &str) -> PyResult<String> {
+    use serde_json::Value;
+
+    const COMMON_FIELDS: &[&str] = &["ABI version", "version", "header"];
+
+    let items_field = match method {
+        "function" => "functions",
+        "event" => "events",
+        "getter" => "getters",
+        _ => return Err(PyValueError::new_err(format!("Unknown ABI item: {method}"))),
+    };
+
+    let abi =
+        serde_json::from_str::<serde_json::Map<String, Value>>(source).handle_value_error()?;
+    let item = abi
+        .get(items_field)
+        .and_then(Value::as_array)
+        .and_then(|items| {
+            items
+                .iter()
+                .find(|item| item.get("name").and_then(Value::as_str) == Some(name))
+        })
+        .ok_or_else(|| PyValueError::new_err(format!("ABI {method} '{name}' not found")))?;
+
+    let mut result = serde_json::Map::new();
+    for field in COMMON_FIELDS {
+        if let Some(value) = abi.get(*field) {
+            result.insert((*field).to_owned(), value.clone());
+        }
+    }
+    result.insert(items_field.to_owned(), Value::Array(vec![item.clone()]));
+
+    serde_json::to_string(&result).handle_value_error()
+}
+
 struct SharedContractAbi {
+    source: Arc<str>,
     contract: ton_abi::Contract,
     functions: FastHashMap<String, FunctionAbi>,
     events: FastHashMap<String, EventAbi>,
@@ -621,7 +1782,7 @@ enum DataOrState<'a> {
 
 #[derive(Clone)]
 #[pyclass(subclass)]
-pub struct FunctionAbi(Arc<ton_abi::Function>);
+pub struct FunctionAbi(Arc<ton_abi::Function>, Arc<str>);
 
 #[pymethods]
 impl FunctionAbi {
@@ -652,6 +1813,7 @@ impl FunctionAbi {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn call(
         &self,
         py: Python<'_>,
@@ -660,9 +1822,15 @@ impl FunctionAbi {
         responsible: Option<bool>,
         clock: Option<&Clock>,
         config: Option<BlockchainConfig>,
+        trace: Option<TraceOptions>,
     ) -> PyResult<ExecutionOutput> {
         use nt::abi::FunctionExt;
 
+        let responsible = matches!(responsible, Some(true));
+        if let Some(trace) = trace {
+            return self.call_traced(py, account_state, input, responsible, clock, config, trace);
+        }
+
         let input = parse_tokens(&self.0.inputs, input)?;
         let clock = match clock {
             Some(clock) => clock.as_ref(),
@@ -674,18 +1842,13 @@ impl FunctionAbi {
             None => nt::abi::BriefBlockchainConfig::default(),
         };
 
-        let responsible = matches!(responsible, Some(true));
-        let execution_output = self
-            .0
-            .run_local_ext(
-                clock,
-                account_state.0.clone(),
-                &input,
-                responsible,
-                &config,
-                &[],
-            )
-            .handle_runtime_error()?;
+        let account = account_state.0.clone();
+        let execution_output = py
+            .allow_threads(|| {
+                self.0
+                    .run_local_ext(clock, account, &input, responsible, &config, &[])
+            })
+            .map_err(handle_execution_error)?;
 
         Ok(ExecutionOutput {
             exit_code: execution_output.result_code,
@@ -693,6 +1856,7 @@ impl FunctionAbi {
                 .tokens
                 .map(|tokens| PyResult::Ok(convert_tokens(py, tokens)?.into_py(py)))
                 .transpose()?,
+            trace: None,
         })
     }
 
@@ -744,7 +1908,7 @@ impl FunctionAbi {
                 true,
                 address.map(|addr| addr.0.clone()),
             )
-            .handle_runtime_error()?;
+            .handle_abi_encode_error()?;
 
         Ok(UnsignedBody {
             abi_version: self.0.abi_version,
@@ -786,7 +1950,7 @@ impl FunctionAbi {
 
         message.set_body(body);
 
-        let hash = message.hash().handle_runtime_error()?;
+        let hash = message.hash().handle_cell_error()?;
 
         Ok(Message {
             data: message,
@@ -799,8 +1963,8 @@ impl FunctionAbi {
         let input = self
             .0
             .encode_internal_input(&tokens)
-            .handle_runtime_error()?;
-        input.into_cell().map(Cell).handle_runtime_error()
+            .handle_abi_encode_error()?;
+        input.into_cell().map(Cell).handle_abi_encode_error()
     }
 
     fn decode_transaction(
@@ -812,20 +1976,20 @@ impl FunctionAbi {
 
         let tx = &transaction.0.data;
 
-        let Some(in_msg) = tx.read_in_msg().handle_runtime_error()? else {
-            return Err(PyRuntimeError::new_err(
+        let Some(in_msg) = tx.read_in_msg().handle_cell_error()? else {
+            return Err(AbiDecodeError::new_err(
                 "Transaction without incoming message",
             ));
         };
         let Some(in_msg_body) = in_msg.body() else {
-            return Err(PyRuntimeError::new_err("Incoming message without body"));
+            return Err(AbiDecodeError::new_err("Incoming message without body"));
         };
 
         let input = self
             .0
             .decode_input(in_msg_body, in_msg.is_internal(), false)
-            .handle_runtime_error()?;
-        let output = self.0.parse(tx).handle_runtime_error()?;
+            .handle_abi_decode_error()?;
+        let output = self.0.parse(tx).handle_abi_decode_error()?;
 
         Ok(FunctionCall {
             input: convert_tokens(py, input)?.into_py(py),
@@ -841,10 +2005,10 @@ impl FunctionAbi {
         allow_partial: Option<bool>,
     ) -> PyResult<&'a PyDict> {
         let abi = self.0.as_ref();
-        let body = ton_types::SliceData::load_cell_ref(&message_body.0).handle_value_error()?;
+        let body = ton_types::SliceData::load_cell_ref(&message_body.0).handle_cell_error()?;
         let values = abi
             .decode_input(body, internal, matches!(allow_partial, Some(true)))
-            .handle_runtime_error()?;
+            .handle_abi_decode_error()?;
 
         convert_tokens(py, values)
     }
@@ -856,17 +2020,29 @@ impl FunctionAbi {
         allow_partial: Option<bool>,
     ) -> PyResult<&'a PyDict> {
         let abi = self.0.as_ref();
-        let body = ton_types::SliceData::load_cell_ref(&message_body.0).handle_value_error()?;
+        let body = ton_types::SliceData::load_cell_ref(&message_body.0).handle_cell_error()?;
         let values = if matches!(allow_partial, Some(true)) {
             abi.decode_output_partial(body, false)
         } else {
             abi.decode_output(body, false)
         }
-        .handle_runtime_error()?;
+        .handle_abi_decode_error()?;
 
         convert_tokens(py, values)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_contract_item(py, &self.1, "function", &self.0.name)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<FunctionAbi name='{}', input_id=0x{:08x}, output_id=0x{:08x}>",
@@ -890,6 +2066,140 @@ impl FunctionAbi {
     }
 }
 
+impl FunctionAbi {
+    /// Runs the function locally as an unsigned external message,
+    /// or as an internal message for responsible functions.
+    ///
+    /// The output is decoded from the first external outbound message with
+    /// the function output id, or from the first internal outbound message
+    /// (the answer) for responsible functions.
+    #[allow(clippy::too_many_arguments)]
+    fn call_traced(
+        &self,
+        py: Python<'_>,
+        account_state: &AccountState,
+        input: &PyDict,
+        responsible: bool,
+        clock: Option<&Clock>,
+        config: Option<BlockchainConfig>,
+        trace: TraceOptions,
+    ) -> PyResult<ExecutionOutput> {
+        use ton_vm::stack::StackItem;
+
+        let account = &account_state.0;
+        let dst = Address(account.addr.clone());
+        let (mut message, body, selector) = if responsible {
+            let body = self.encode_internal_input(input)?;
+            let message = ton_block::Message::with_int_header(
+                ton_block::InternalMessageHeader::with_addresses(
+                    Default::default(),
+                    dst.0,
+                    Default::default(),
+                ),
+            );
+            (message, body, 0)
+        } else {
+            let body = self
+                .encode_external_input(input, None, None, Some(&dst), clock)?
+                .without_signature()?;
+            let message =
+                ton_block::Message::with_ext_in_header(ton_block::ExternalInboundMessageHeader {
+                    dst: dst.0,
+                    ..Default::default()
+                });
+            (message, body, -1)
+        };
+        message.set_body(ton_types::SliceData::load_cell_ref(&body.0).handle_cell_error()?);
+        let message = message.serialize().handle_cell_error()?;
+
+        let stack = vec![
+            StackItem::int(account.storage.balance.grams.as_u128()),
+            StackItem::int(0),
+            StackItem::Cell(message),
+            StackItem::Slice(ton_types::SliceData::load_cell(body.0).handle_cell_error()?),
+            StackItem::int(selector),
+        ];
+
+        let clock = match clock {
+            Some(clock) => clock.as_ref(),
+            None => &nt::utils::SimpleClock,
+        };
+        let config = match &config {
+            Some(config) => nt::abi::BriefBlockchainConfig::from(config.as_ref()),
+            None => nt::abi::BriefBlockchainConfig::default(),
+        };
+
+        let tracer = Arc::new(VmTracer::new(Some(&trace))?);
+        let result =
+            py.allow_threads(|| run_traced_call(account, stack, clock, &config, tracer.clone()));
+        let steps = tracer.take()?;
+        let output = match result {
+            Ok(output) => output,
+            Err(e) => return Err(with_trace(handle_execution_error(e), steps)),
+        };
+
+        let tokens = match output.exit_code {
+            0 | 1 => self.find_output(output.actions, responsible)?,
+            _ => None,
+        };
+
+        Ok(ExecutionOutput {
+            exit_code: output.exit_code,
+            output: tokens
+                .map(|tokens| PyResult::Ok(convert_tokens(py, tokens)?.into_py(py)))
+                .transpose()?,
+            trace: Some(steps),
+        })
+    }
+
+    fn find_output(
+        &self,
+        actions: Option<ton_types::Cell>,
+        responsible: bool,
+    ) -> PyResult<Option<Vec<ton_abi::Token>>> {
+        let Some(actions) = actions else {
+            return Ok(None);
+        };
+        let actions = ton_block::OutActions::construct_from_cell(actions).handle_cell_error()?;
+        for action in actions {
+            let ton_block::OutAction::SendMsg { out_msg, .. } = action else {
+                continue;
+            };
+            let Some(mut body) = out_msg.body() else {
+                continue;
+            };
+
+            if responsible {
+                // NOTE: the answer id is chosen by the caller, so it is skipped
+                if !out_msg.is_internal() || body.get_next_u32().is_err() {
+                    continue;
+                }
+                let tokens = ton_abi::TokenValue::decode_params(
+                    &self.0.outputs,
+                    body,
+                    &self.0.abi_version,
+                    false,
+                )
+                .handle_abi_decode_error()?;
+                return Ok(Some(tokens));
+            }
+
+            if !out_msg.is_outbound_external() {
+                continue;
+            }
+            if body.clone().get_next_u32().ok() != Some(self.0.output_id) {
+                continue;
+            }
+            let tokens = self
+                .0
+                .decode_output(body, false)
+                .handle_abi_decode_error()?;
+            return Ok(Some(tokens));
+        }
+        Ok(None)
+    }
+}
+
 #[derive(Clone)]
 #[pyclass]
 pub struct FunctionAbiWithArgs {
@@ -916,10 +2226,11 @@ impl FunctionAbiWithArgs {
         responsible: Option<bool>,
         clock: Option<&Clock>,
         config: Option<BlockchainConfig>,
+        trace: Option<TraceOptions>,
     ) -> PyResult<ExecutionOutput> {
         let input = self.args.as_ref(py);
         self.abi
-            .call(py, account_state, input, responsible, clock, config)
+            .call(py, account_state, input, responsible, clock, config, trace)
     }
 
     fn encode_external_message(
@@ -1009,6 +2320,8 @@ impl FunctionAbiWithArgs {
 pub struct ExecutionOutput {
     exit_code: i32,
     output: Option<Py<PyDict>>,
+    /// VM steps, collected only for traced calls.
+    trace: Option<Vec<VmStep>>,
 }
 
 #[pymethods]
@@ -1045,7 +2358,7 @@ const DEFAULT_TIMEOUT: u32 = 60;
 
 #[derive(Clone)]
 #[pyclass]
-pub struct EventAbi(Arc<ton_abi::Event>);
+pub struct EventAbi(Arc<ton_abi::Event>, Arc<str>);
 
 #[pymethods]
 impl EventAbi {
@@ -1071,16 +2384,28 @@ impl EventAbi {
         if !message.data.is_outbound_external() {
             return Err(PyValueError::new_err("Message is not an external outbound"));
         }
-        let values = self.0.decode_input(body).handle_runtime_error()?;
+        let values = self.0.decode_input(body).handle_abi_decode_error()?;
         convert_tokens(py, values)
     }
 
     fn decode_message_body<'a>(&self, py: Python<'a>, message_body: &Cell) -> PyResult<&'a PyDict> {
-        let body = ton_types::SliceData::load_cell_ref(&message_body.0).handle_value_error()?;
-        let values = self.0.decode_input(body).handle_runtime_error()?;
+        let body = ton_types::SliceData::load_cell_ref(&message_body.0).handle_cell_error()?;
+        let values = self.0.decode_input(body).handle_abi_decode_error()?;
         convert_tokens(py, values)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_contract_item(py, &self.1, "event", &self.0.name)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!("<EventAbi name='{}', id=0x{:08x}>", self.0.name, self.0.id)
     }
@@ -1103,7 +2428,7 @@ impl EventAbi {
 
 #[derive(Clone)]
 #[pyclass(subclass)]
-pub struct GetterAbi(Arc<ton_abi::Function>);
+pub struct GetterAbi(Arc<ton_abi::Function>, Arc<str>);
 
 #[pymethods]
 impl GetterAbi {
@@ -1119,13 +2444,14 @@ impl GetterAbi {
     }
 
     // TODO: Use function id.
-    fn call<'a>(
+    fn call(
         &self,
-        py: Python<'a>,
+        py: Python<'_>,
         account_state: &AccountState,
         input: &PyDict,
         clock: Option<&Clock>,
         config: Option<BlockchainConfig>,
+        trace: Option<TraceOptions>,
     ) -> PyResult<ExecutionOutput> {
         let input = parse_tokens(&self.0.inputs, input)?;
         let clock = match clock {
@@ -1133,7 +2459,7 @@ impl GetterAbi {
             None => &nt::utils::SimpleClock,
         };
 
-        let input = input
+        let mut input = input
             .into_iter()
             .map(|item| token_to_stack_item(item.value))
             .collect::<PyResult<Vec<_>>>()?;
@@ -1143,42 +2469,64 @@ impl GetterAbi {
             None => nt::abi::BriefBlockchainConfig::default(),
         };
 
-        let ctx = nt::abi::ExecutionContext {
-            clock,
-            account_stuff: &account_state.0,
-            // TODO: Add support for libraries
-            libraries: &[],
+        if let Some(trace) = trace {
+            input.push(ton_vm::stack::StackItem::int(self.method_id()));
+
+            let tracer = Arc::new(VmTracer::new(Some(&trace))?);
+            let result = py.allow_threads(|| {
+                run_traced_call(&account_state.0, input, clock, &config, tracer.clone())
+            });
+            let steps = tracer.take()?;
+            let output = match result {
+                Ok(output) => output,
+                Err(e) => return Err(with_trace(handle_execution_error(e), steps)),
+            };
+
+            return Ok(ExecutionOutput {
+                exit_code: output.exit_code,
+                output: self.decode_stack(py, output.exit_code, output.stack)?,
+                trace: Some(steps),
+            });
         }
-        .run_getter_ext(self.0.name.as_str(), &input, &config, &Default::default())
-        .handle_runtime_error()?;
+
+        let ctx = py
+            .allow_threads(|| {
+                nt::abi::ExecutionContext {
+                    clock,
+                    account_stuff: &account_state.0,
+                    // TODO: Add support for libraries
+                    libraries: &[],
+                }
+                .run_getter_ext(
+                    self.0.name.as_str(),
+                    &input,
+                    &config,
+                    &Default::default(),
+                )
+            })
+            .map_err(handle_execution_error)?;
 
         drop(input);
 
         Ok(ExecutionOutput {
             exit_code: ctx.exit_code,
-            output: (ctx.exit_code == 0)
-                .then(|| {
-                    if ctx.stack.len() != self.0.outputs.len() {
-                        return Err(PyRuntimeError::new_err("Output stack size mismatch"));
-                    }
-
-                    let outputs = self
-                        .0
-                        .outputs
-                        .iter()
-                        .zip(ctx.stack)
-                        .map(|(param, value)| {
-                            let value = stack_item_to_token(&param.kind, &value)?;
-                            Ok(ton_abi::Token::new(&param.name, value))
-                        })
-                        .collect::<PyResult<Vec<_>>>()?;
-
-                    Ok(convert_tokens(py, outputs)?.into_py(py))
-                })
-                .transpose()?,
+            output: self.decode_stack(py, ctx.exit_code, ctx.stack)?,
+            trace: None,
         })
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_contract_item(py, &self.1, "getter", &self.0.name)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<GetterAbi name='{}', method_id=0x{:08x}>",
@@ -1202,6 +2550,36 @@ impl GetterAbi {
     }
 }
 
+impl GetterAbi {
+    fn decode_stack(
+        &self,
+        py: Python<'_>,
+        exit_code: i32,
+        stack: Vec<ton_vm::stack::StackItem>,
+    ) -> PyResult<Option<Py<PyDict>>> {
+        if exit_code != 0 {
+            return Ok(None);
+        }
+        if stack.len() != self.0.outputs.len() {
+            return Err(AbiDecodeError::new_err("Output stack size mismatch"));
+        }
+
+        let outputs = self
+            .0
+            .outputs
+            .iter()
+            .zip(stack)
+            .map(|(param, value)| {
+                let value = stack_item_to_token(&param.kind, &value)
+                    .map_err(|e| abi_decode_error(param.name.clone(), e.value(py).to_string()))?;
+                Ok(ton_abi::Token::new(&param.name, value))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(Some(convert_tokens(py, outputs)?.into_py(py)))
+    }
+}
+
 #[pyclass(extends = Message)]
 pub struct SignedExternalMessage {
     pub expire_at: u32,
@@ -1223,13 +2601,13 @@ impl SignedExternalMessage {
             });
 
         if let Some(body) = body {
-            message.set_body(ton_types::SliceData::load_cell(body.0).handle_value_error()?);
+            message.set_body(ton_types::SliceData::load_cell(body.0).handle_cell_error()?);
         }
         if let Some(state_init) = state_init {
             message.set_state_init(state_init.0.clone())
         }
 
-        let hash = message.hash().handle_runtime_error()?;
+        let hash = message.hash().handle_cell_error()?;
 
         Ok(PyClassInitializer::from(Message {
             data: message,
@@ -1248,6 +2626,31 @@ impl SignedExternalMessage {
         (slf.into_super().clone(), expire_at)
     }
 
+    fn __reduce__(slf: PyRef<'_, Self>, py: Python<'_>) -> PyReduce {
+        let expire_at = slf.expire_at;
+        let message = slf.into_super();
+        let args = (
+            message.dst(),
+            expire_at,
+            message.body(),
+            message.state_init(),
+        );
+        reduce_new::<Self>(py, args)
+    }
+
+    fn __copy__(slf: PyRef<'_, Self>, py: Python<'_>) -> PyResult<Py<Self>> {
+        let expire_at = slf.expire_at;
+        let message = slf.into_super().clone();
+        Py::new(
+            py,
+            PyClassInitializer::from(message).add_subclass(SignedExternalMessage { expire_at }),
+        )
+    }
+
+    fn __deepcopy__(slf: PyRef<'_, Self>, py: Python<'_>, _memo: &PyAny) -> PyResult<Py<Self>> {
+        Self::__copy__(slf, py)
+    }
+
     fn __repr__(slf: PyRef<'_, Self>) -> String {
         let expire_at = slf.expire_at;
         let message = slf.into_super();
@@ -1258,6 +2661,7 @@ impl SignedExternalMessage {
     }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct UnsignedExternalMessage {
     dst: ton_block::MsgAddressInt,
@@ -1277,11 +2681,11 @@ impl UnsignedExternalMessage {
             message.set_state_init(state_init.0.clone())
         }
 
-        let body = ton_types::SliceData::load_cell(body.0).handle_runtime_error()?;
+        let body = ton_types::SliceData::load_cell(body.0).handle_cell_error()?;
 
         message.set_body(body);
 
-        let hash = message.hash().handle_runtime_error()?;
+        let hash = message.hash().handle_cell_error()?;
 
         Py::new(
             py,
@@ -1298,6 +2702,25 @@ impl UnsignedExternalMessage {
 
 #[pymethods]
 impl UnsignedExternalMessage {
+    #[new]
+    fn new(dst: Address, body: UnsignedBody, state_init: Option<StateInit>) -> Self {
+        Self {
+            dst: dst.0,
+            state_init,
+            body,
+        }
+    }
+
+    #[getter]
+    fn dst(&self) -> Address {
+        Address(self.dst.clone())
+    }
+
+    #[getter]
+    fn body(&self) -> UnsignedBody {
+        self.body.clone()
+    }
+
     #[getter]
     fn hash<'a>(&self, py: Python<'a>) -> &'a PyBytes {
         self.body.hash(py)
@@ -1335,7 +2758,7 @@ impl UnsignedExternalMessage {
         self.fill_body(py, self.body.with_signature(signature)?)
     }
 
-    fn with_fake_signature(&self, py: Python<'_>) -> PyResult<Py<SignedExternalMessage>> {
+    pub fn with_fake_signature(&self, py: Python<'_>) -> PyResult<Py<SignedExternalMessage>> {
         self.fill_body(py, self.body.with_fake_signature()?)
     }
 
@@ -1343,6 +2766,19 @@ impl UnsignedExternalMessage {
         self.fill_body(py, self.body.without_signature()?)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        let args = (self.dst(), self.body.clone(), self.state_init.clone());
+        reduce_new::<Self>(py, args)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<UnsignedExternalMessage hash='{:x}', expire_at={}>",
@@ -1351,6 +2787,7 @@ impl UnsignedExternalMessage {
     }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct UnsignedBody {
     abi_version: ton_abi::contract::AbiVersion,
@@ -1363,13 +2800,37 @@ impl UnsignedBody {
     fn fill_signature(&self, signature: Option<&[u8]>) -> PyResult<Cell> {
         let payload =
             ton_abi::Function::fill_sign(&self.abi_version, signature, None, self.payload.clone())
-                .handle_runtime_error()?;
-        payload.into_cell().handle_runtime_error().map(Cell)
+                .handle_abi_encode_error()?;
+        payload.into_cell().handle_abi_encode_error().map(Cell)
     }
 }
 
 #[pymethods]
 impl UnsignedBody {
+    #[new]
+    fn new(abi_version: AbiVersion, payload: Cell, hash: &[u8], expire_at: u32) -> PyResult<Self> {
+        Ok(Self {
+            abi_version: abi_version.0,
+            payload: ton_types::BuilderData::from_cell(&payload.0),
+            hash: ton_types::UInt256::from_bytes(hash, "hash")?,
+            expire_at,
+        })
+    }
+
+    #[getter]
+    fn abi_version(&self) -> AbiVersion {
+        AbiVersion(self.abi_version)
+    }
+
+    #[getter]
+    fn payload(&self) -> PyResult<Cell> {
+        self.payload
+            .clone()
+            .into_cell()
+            .handle_cell_error()
+            .map(Cell)
+    }
+
     #[getter]
     fn hash<'a>(&self, py: Python<'a>) -> &'a PyBytes {
         PyBytes::new(py, self.hash.as_slice())
@@ -1397,6 +2858,24 @@ impl UnsignedBody {
         self.fill_signature(None)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let args = (
+            self.abi_version(),
+            self.payload()?,
+            self.hash(py),
+            self.expire_at,
+        );
+        Ok(reduce_new::<Self>(py, args))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<UnsignedBody hash='{:x}', expire_at={}>",
@@ -1411,6 +2890,68 @@ pub struct AbiParam {
     pub param: ton_abi::ParamType,
 }
 
+#[pymethods]
+impl AbiParam {
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        use ton_abi::ParamType;
+
+        let param = |param: &ParamType| {
+            Py::new(
+                py,
+                AbiParam {
+                    param: param.clone(),
+                },
+            )
+        };
+
+        Ok(match &self.param {
+            ParamType::Uint(size) => reduce_new::<AbiUint>(py, (*size,)),
+            ParamType::Int(size) => reduce_new::<AbiInt>(py, (*size,)),
+            ParamType::VarUint(size) => reduce_new::<AbiVarUint>(py, (*size,)),
+            ParamType::VarInt(size) => reduce_new::<AbiVarInt>(py, (*size,)),
+            ParamType::Bool => reduce_new::<AbiBool>(py, ()),
+            ParamType::Tuple(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| Ok((item.name.clone(), param(&item.kind)?)))
+                    .collect::<PyResult<Vec<_>>>()?;
+                reduce_new::<AbiTuple>(py, (items,))
+            }
+            ParamType::Array(value_type) => reduce_new::<AbiArray>(py, (param(value_type)?,)),
+            ParamType::FixedArray(value_type, len) => {
+                reduce_new::<AbiFixedArray>(py, (param(value_type)?, *len))
+            }
+            ParamType::Cell => reduce_new::<AbiCell>(py, ()),
+            ParamType::Map(key_type, value_type) => {
+                reduce_new::<AbiMap>(py, (param(key_type)?, param(value_type)?))
+            }
+            ParamType::Address => reduce_new::<AbiAddress>(py, ()),
+            ParamType::AddressStd => reduce_new::<AbiAddressStd>(py, ()),
+            ParamType::Bytes => reduce_new::<AbiBytes>(py, ()),
+            ParamType::FixedBytes(len) => reduce_new::<AbiFixedBytes>(py, (*len,)),
+            ParamType::String => reduce_new::<AbiString>(py, ()),
+            ParamType::Token => reduce_new::<AbiToken>(py, ()),
+            ParamType::Optional(value_type) => reduce_new::<AbiOptional>(py, (param(value_type)?,)),
+            ParamType::Ref(value_type) => reduce_new::<AbiRef>(py, (param(value_type)?,)),
+            _ => {
+                return Err(PyTypeError::new_err(format!(
+                    "Unsupported ABI param type: {}",
+                    self.param.type_signature()
+                )))
+            }
+        })
+    }
+
+    fn __copy__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let (new, args) = self.__reduce__(py)?;
+        new.call1(py, args.as_ref(py))
+    }
+
+    fn __deepcopy__(&self, py: Python<'_>, _memo: &PyAny) -> PyResult<PyObject> {
+        self.__copy__(py)
+    }
+}
+
 macro_rules! define_abi_types {
     ($($ident:ident = |$($arg:ident: $arg_ty:ty),*| $res:expr),*$(,)?) => {$(
         #[pyclass(extends = AbiParam)]
@@ -1507,6 +3048,18 @@ impl AbiVersion {
         self.0.to_string()
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.0.major, self.0.minor))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!("AbiVersion({}, {})", self.0.major, self.0.minor)
     }
@@ -1524,19 +3077,51 @@ pub fn parse_tokens(params: &[ton_abi::Param], value: &PyDict) -> PyResult<Vec<t
     let mut result = Vec::with_capacity(params.len());
     for param in params {
         let value = match value.get_item(param.name.as_str()) {
-            Some(value) => parse_token(&param.kind, value)?,
-            None => {
-                return Err(PyRuntimeError::new_err(format!(
-                    "Param '{}' not found",
-                    param.name
-                )));
-            }
+            Some(value) => parse_named_token(&param.name, &param.kind, value)?,
+            None => return Err(param_not_found(&param.name)),
         };
         result.push(ton_abi::Token::new(&param.name, value));
     }
     Ok(result)
 }
 
+fn parse_named_token(
+    name: &str,
+    param: &ton_abi::ParamType,
+    value: &PyAny,
+) -> PyResult<ton_abi::TokenValue> {
+    parse_token(param, value).map_err(|e| with_param_path(value.py(), name, e))
+}
+
+fn param_not_found(name: &str) -> PyErr {
+    abi_encode_error(name.to_owned(), format!("Param '{name}' not found"))
+}
+
+/// Converts the error into `AbiEncodeError` and prepends the param name to its path.
+fn with_param_path(py: Python<'_>, segment: &str, error: PyErr) -> PyErr {
+    let is_encode_error = match AbiEncodeError::type_object(py) {
+        Ok(ty) => error.is_instance(py, ty),
+        Err(e) => return e,
+    };
+    if !is_encode_error {
+        return abi_encode_error(segment.to_owned(), error.value(py).to_string());
+    }
+
+    let value = error.value(py);
+    let path = match value
+        .getattr("path")
+        .and_then(|path| path.extract::<Option<String>>())
+    {
+        Ok(Some(path)) if path.starts_with('[') => format!("{segment}{path}"),
+        Ok(Some(path)) => format!("{segment}.{path}"),
+        _ => segment.to_owned(),
+    };
+    match value.setattr("path", path) {
+        Ok(()) => error,
+        Err(e) => e,
+    }
+}
+
 fn parse_token(param: &ton_abi::ParamType, value: &PyAny) -> PyResult<ton_abi::TokenValue> {
     use pyo3::types::*;
 
@@ -1606,8 +3191,8 @@ fn parse_token(param: &ton_abi::ParamType, value: &PyAny) -> PyResult<ton_abi::T
         ton_abi::ParamType::Array(ty) => {
             let list = value.extract::<&PyList>()?;
             let mut values = Vec::with_capacity(list.len());
-            for value in list {
-                values.push(parse_token(ty.as_ref(), value)?);
+            for (i, value) in list.into_iter().enumerate() {
+                values.push(parse_named_token(&format!("[{i}]"), ty.as_ref(), value)?);
             }
             ton_abi::TokenValue::Array(*ty.clone(), values)
         }
@@ -1618,8 +3203,8 @@ fn parse_token(param: &ton_abi::ParamType, value: &PyAny) -> PyResult<ton_abi::T
                 return Err(PyValueError::new_err("Invalid fixed array length"));
             }
             let mut values = Vec::with_capacity(list_len);
-            for value in list {
-                values.push(parse_token(ty.as_ref(), value)?);
+            for (i, value) in list.into_iter().enumerate() {
+                values.push(parse_named_token(&format!("[{i}]"), ty.as_ref(), value)?);
             }
             ton_abi::TokenValue::FixedArray(*ty.clone(), values)
         }
@@ -1630,8 +3215,9 @@ fn parse_token(param: &ton_abi::ParamType, value: &PyAny) -> PyResult<ton_abi::T
         ton_abi::ParamType::Map(key_ty, value_ty) => {
             let list = value.extract::<&PyList>()?;
             let mut result = BTreeMap::new();
-            for item in list {
-                let (key, value) = parse_map_entry_token(key_ty, value_ty, item)?;
+            for (i, item) in list.into_iter().enumerate() {
+                let (key, value) = parse_map_entry_token(key_ty, value_ty, item)
+                    .map_err(|e| with_param_path(item.py(), &format!("[{i}]"), e))?;
                 result.insert(key, value);
             }
             ton_abi::TokenValue::Map(*key_ty.clone(), *value_ty.clone(), result)
@@ -1678,7 +3264,7 @@ fn parse_token(param: &ton_abi::ParamType, value: &PyAny) -> PyResult<ton_abi::T
                 value.extract::<u128>()?
             };
 
-            let value = ton_block::Grams::new(value).handle_runtime_error()?;
+            let value = ton_block::Grams::new(value).handle_abi_encode_error()?;
             ton_abi::TokenValue::Token(value)
         }
         ton_abi::ParamType::Time => value.extract::<u64>().map(ton_abi::TokenValue::Time)?,
@@ -1687,8 +3273,8 @@ fn parse_token(param: &ton_abi::ParamType, value: &PyAny) -> PyResult<ton_abi::T
             let value = if value.is_none() {
                 None
             } else {
-                let value = hex::decode(value.extract::<&str>()?).handle_runtime_error()?;
-                Some(ed25519_dalek::PublicKey::from_bytes(&value).handle_runtime_error()?)
+                let value = hex::decode(value.extract::<&str>()?).handle_abi_encode_error()?;
+                Some(ed25519_dalek::PublicKey::from_bytes(&value).handle_abi_encode_error()?)
             };
             ton_abi::TokenValue::PublicKey(value)
         }
@@ -1830,7 +3416,7 @@ fn convert_addr_token(py: Python, addr: ton_block::MsgAddress) -> PyResult<PyObj
     Ok(Address(match addr {
         ton_block::MsgAddress::AddrStd(addr) => ton_block::MsgAddressInt::AddrStd(addr),
         ton_block::MsgAddress::AddrVar(addr) => ton_block::MsgAddressInt::AddrVar(addr),
-        _ => return Err(PyRuntimeError::new_err("Unsupported address type")),
+        _ => return Err(AbiDecodeError::new_err("Unsupported address type")),
     })
     .into_py(py))
 }
@@ -1993,9 +3579,9 @@ impl TupleReader {
     }
 
     pub fn read_address(&mut self) -> PyResult<Address> {
-        let mut cs = ton_types::SliceData::load_cell(self.read_cell()?.0).handle_value_error()?;
+        let mut cs = ton_types::SliceData::load_cell(self.read_cell()?.0).handle_cell_error()?;
         let mut addr = ton_block::MsgAddressInt::default();
-        addr.read_from(&mut cs).handle_value_error()?;
+        addr.read_from(&mut cs).handle_cell_error()?;
         Ok(Address(addr))
     }
 
@@ -2003,9 +3589,9 @@ impl TupleReader {
         let Some(cell) = self.read_cell_opt()? else {
             return Ok(None);
         };
-        let mut cs = ton_types::SliceData::load_cell(cell.0).handle_value_error()?;
+        let mut cs = ton_types::SliceData::load_cell(cell.0).handle_cell_error()?;
         let mut addr = ton_block::MsgAddressIntOrNone::default();
-        addr.read_from(&mut cs).handle_value_error()?;
+        addr.read_from(&mut cs).handle_cell_error()?;
         Ok(match addr {
             ton_block::MsgAddressIntOrNone::None => None,
             ton_block::MsgAddressIntOrNone::Some(addr) => Some(Address(addr)),
@@ -2086,7 +3672,7 @@ fn parse_bytes_from_cell<'a>(py: Python<'a>, cell: ton_types::Cell) -> Option<&'
 fn parse_string_from_cell(mut cell: ton_types::Cell) -> PyResult<String> {
     let mut buffer = Vec::new();
 
-    let mut cs = ton_types::SliceData::load_cell(cell).handle_value_error()?;
+    let mut cs = ton_types::SliceData::load_cell(cell).handle_cell_error()?;
     loop {
         let bit_len = cs.remaining_bits();
         if !bit_len.is_multiple_of(8) {
@@ -2104,8 +3690,8 @@ fn parse_string_from_cell(mut cell: ton_types::Cell) -> PyResult<String> {
         }
 
         if refs > 0 {
-            cell = cs.checked_drain_reference().handle_value_error()?;
-            cs = ton_types::SliceData::load_cell(cell).handle_value_error()?;
+            cell = cs.checked_drain_reference().handle_cell_error()?;
+            cs = ton_types::SliceData::load_cell(cell).handle_cell_error()?;
         } else {
             break;
         }
@@ -2117,9 +3703,9 @@ fn parse_string_from_cell(mut cell: ton_types::Cell) -> PyResult<String> {
 fn bytes_to_cell(bytes: &[u8]) -> PyResult<ton_types::Cell> {
     let bit_len = bytes.len() * 8;
     ton_types::BuilderData::with_raw(bytes.into(), bit_len)
-        .handle_value_error()?
+        .handle_cell_error()?
         .into_cell()
-        .handle_value_error()
+        .handle_cell_error()
 }
 
 fn bytes_to_cell_chain(bytes: &[u8]) -> PyResult<ton_types::Cell> {
@@ -2135,13 +3721,13 @@ fn bytes_to_cell_chain(bytes: &[u8]) -> PyResult<ton_types::Cell> {
     for chunk in chunks.iter().rev() {
         let mut builder =
             ton_types::BuilderData::with_raw(chunk.as_slice().into(), chunks.len() * 8)
-                .handle_value_error()?;
+                .handle_cell_error()?;
         if let Some(child) = child.take() {
             builder
                 .checked_append_reference(child)
-                .handle_value_error()?;
+                .handle_cell_error()?;
         }
-        child = Some(builder.into_cell().handle_value_error()?);
+        child = Some(builder.into_cell().handle_cell_error()?);
     }
 
     Ok(child.unwrap_or_default())
@@ -2311,15 +3897,17 @@ fn stack_item_to_token(
         },
         // FIXME: Properly hande all possible type combinations.
         StackItem::Cell(value) => {
-            let slice = ton_types::SliceData::load_cell(value.clone()).handle_value_error()?;
-            read_token_value(&param, slice).handle_value_error()?
+            let slice = ton_types::SliceData::load_cell(value.clone()).handle_cell_error()?;
+            read_token_value(&param, slice).handle_abi_decode_error()?
+        }
+        StackItem::Slice(value) => {
+            read_token_value(param, value.clone()).handle_abi_decode_error()?
         }
-        StackItem::Slice(value) => read_token_value(param, value.clone()).handle_value_error()?,
         StackItem::Builder(arc) => {
-            let cell = arc.as_ref().clone().into_cell().handle_value_error()?;
-            let slice = ton_types::SliceData::load_cell(cell).handle_value_error()?;
+            let cell = arc.as_ref().clone().into_cell().handle_cell_error()?;
+            let slice = ton_types::SliceData::load_cell(cell).handle_cell_error()?;
 
-            read_token_value(param, slice).handle_value_error()?
+            read_token_value(param, slice).handle_abi_decode_error()?
         }
         StackItem::Continuation(_) => {
             return Err(PyValueError::new_err(
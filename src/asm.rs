@@ -10,10 +10,10 @@ pub struct Asm;
 impl Asm {
     #[staticmethod]
     pub fn compile(asm: &str) -> PyResult<Cell> {
-        let code = everscale_asm::Code::assemble(asm).handle_runtime_error()?;
+        let code = everscale_asm::Code::assemble(asm).handle_value_error()?;
         let code = everscale_types::boc::Boc::encode(code);
         ton_types::deserialize_tree_of_cells(&mut code.as_slice())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Cell)
     }
 }
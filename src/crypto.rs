@@ -6,7 +6,7 @@ use pyo3::types::PyBytes;
 use rand::Rng;
 use sha2::{Digest, Sha256};
 
-use crate::util::{Encoding, HandleError};
+use crate::util::{reduce_new, reduce_with, Encoding, HandleError, PyReduce};
 
 const CAP_SIGNATURE_WITH_ID: u64 = 0x4000000;
 const CAP_SIGNATURE_DOMAIN: u64 = 0x800000000;
@@ -79,6 +79,18 @@ impl SignatureContext {
         PyBytes::new(py, &res)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.global_id, self.capabilities))
+    }
+
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        *self
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "SignatureContext(global_id={}, capabilities={})",
@@ -87,6 +99,7 @@ impl SignatureContext {
     }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct PublicKey(pub ed25519_dalek::PublicKey);
 
@@ -149,6 +162,18 @@ impl PublicKey {
         hex::encode(self.0.as_bytes())
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_with::<Self>(py, "from_bytes", (self.to_bytes(py),))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!("PublicKey('{}')", hex::encode(self.0.as_bytes()))
     }
@@ -165,6 +190,16 @@ impl PublicKey {
 #[pyclass]
 pub struct KeyPair(pub ed25519_dalek::Keypair);
 
+impl Clone for KeyPair {
+    fn clone(&self) -> Self {
+        let secret = ed25519_dalek::SecretKey::from_bytes(self.0.secret.as_bytes()).unwrap();
+        Self(ed25519_dalek::Keypair {
+            secret,
+            public: self.0.public,
+        })
+    }
+}
+
 #[pymethods]
 impl KeyPair {
     #[staticmethod]
@@ -216,6 +251,20 @@ impl KeyPair {
         Ok(self.0.public.verify(&data, &signature.0).is_ok())
     }
 
+    /// Reduces the key pair to its secret key, so the pickled bytes
+    /// must be stored as securely as the key itself.
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.secret_key(py),))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __hash__(&self) -> u64 {
         u64::from_le_bytes(self.0.public.as_bytes()[..8].try_into().unwrap())
     }
@@ -225,6 +274,7 @@ impl KeyPair {
     }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct Signature(pub ed25519_dalek::Signature);
 
@@ -255,6 +305,18 @@ impl Signature {
         PyBytes::new(py, self.0.as_ref())
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_with::<Self>(py, "from_bytes", (self.to_bytes(py),))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         use ed25519_dalek::ed25519::signature::Signature;
         format!("Signature('{}')", hex::encode(self.0.as_bytes()))
@@ -282,6 +344,23 @@ impl Seed {
     fn __str__(&self) -> String {
         self.0.join(" ")
     }
+
+    /// Reduces the seed to a constructor call of its actual class.
+    fn __reduce__(slf: &PyCell<Self>) -> PyReduce {
+        let py = slf.py();
+        let phrase = slf.borrow().0.join(" ");
+        (slf.get_type().into_py(py), (phrase,).into_py(py))
+    }
+
+    fn __copy__(slf: &PyCell<Self>) -> PyResult<PyObject> {
+        let py = slf.py();
+        let (new, args) = Self::__reduce__(slf);
+        new.call1(py, args.as_ref(py))
+    }
+
+    fn __deepcopy__(slf: &PyCell<Self>, _memo: &PyAny) -> PyResult<PyObject> {
+        Self::__copy__(slf)
+    }
 }
 
 #[pyclass(extends = Seed)]
@@ -314,7 +393,7 @@ impl LegacySeed {
 
         let words = &self_.as_ref().0;
         if words.len() != Self::WORD_COUNT {
-            return Err(PyRuntimeError::new_err("Invalid legacy seed"));
+            return Err(PyValueError::new_err("Invalid legacy seed"));
         }
 
         let phrase = words.join(" ");
@@ -364,7 +443,7 @@ impl Bip39Seed {
     fn derive(self_: PyRef<'_, Self>, path: Option<&str>) -> PyResult<KeyPair> {
         let words = &self_.as_ref().0;
         let phrase = words.join(" ");
-        let mnemonic = bip39::Mnemonic::from_phrase(&phrase, LANGUAGE).handle_runtime_error()?;
+        let mnemonic = bip39::Mnemonic::from_phrase(&phrase, LANGUAGE).handle_value_error()?;
         let hd = bip39::Seed::new(&mnemonic, "");
         let seed_bytes = hd.as_bytes();
 
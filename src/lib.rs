@@ -15,20 +15,38 @@ mod util;
 
 /// Rust bindings to the nekoton.
 #[pymodule]
-fn nekoton(_py: Python, m: &PyModule) -> PyResult<()> {
+fn nekoton(py: Python, m: &PyModule) -> PyResult<()> {
     pyo3_log::init();
 
+    util::add_error_types(py, m)?;
+
     // Transport
     m.add_class::<Transport>()?;
+    m.add_class::<SyncTransport>()?;
+    m.add_class::<BlockingIter>()?;
     m.add_class::<GqlTransport>()?;
     m.add_class::<GqlExprPart>()?;
     m.add_class::<JrpcTransport>()?;
     m.add_class::<ProtoTransport>()?;
+    m.add_class::<LocalTransport>()?;
+    m.add_class::<CustomTransport>()?;
+    m.add_class::<RecordingTransport>()?;
+    m.add_class::<ReplayTransport>()?;
+    m.add_class::<PendingMessage>()?;
     m.add_class::<AccountStatesAsyncIter>()?;
     m.add_class::<AccountTransactionsAsyncIter>()?;
+    m.add_class::<AccountsWatcher>()?;
+    m.add_class::<TransactionsIter>()?;
+    m.add_class::<AccountsByCodeHashIter>()?;
     m.add_class::<TransactionsBatchInfo>()?;
+    m.add_class::<SubscriptionOptions>()?;
+    m.add("SubscriptionLagged", py.get_type::<SubscriptionLagged>())?;
     m.add_class::<TraceTransaction>()?;
     m.add_class::<Clock>()?;
+    m.add_class::<RetryPolicy>()?;
+    m.add_class::<CacheConfig>()?;
+    m.add_class::<CacheStats>()?;
+    m.add_class::<EstimatedFees>()?;
 
     // Models
     m.add_class::<BlockchainConfig>()?;
@@ -60,6 +78,11 @@ fn nekoton(_py: Python, m: &PyModule) -> PyResult<()> {
 
     // Abi
     m.add_class::<TransactionExecutor>()?;
+    m.add_class::<TreeExecutionOutput>()?;
+    m.add_class::<BehaviorModifiers>()?;
+    m.add_class::<ExecutionOverrides>()?;
+    m.add_class::<VmStep>()?;
+    m.add_class::<TraceOptions>()?;
     m.add_class::<ContractAbi>()?;
     m.add_class::<FunctionAbi>()?;
     m.add_class::<FunctionAbiWithArgs>()?;
@@ -9,7 +9,10 @@ use ton_types::IBitstring;
 
 use crate::abi::{convert_tokens, parse_tokens, AbiParam, AbiVersion};
 use crate::crypto::{PublicKey, Signature, SignatureContext};
-use crate::util::{make_hasher, py_none, Encoding, HandleError};
+use crate::util::{
+    make_hasher, py_none, reduce_enum, reduce_new, reduce_with, CellError, DisplayBool, Encoding,
+    HandleError, PyReduce, PyReduceWithState,
+};
 
 #[derive(Clone)]
 #[pyclass]
@@ -17,6 +20,19 @@ pub struct BlockchainConfig(Arc<ton_executor::BlockchainConfig>);
 
 #[pymethods]
 impl BlockchainConfig {
+    #[staticmethod]
+    fn from_params_dict_cell(params: Cell, global_id: i32) -> PyResult<Self> {
+        let mut config = ton_block::ConfigParams {
+            config_addr: Default::default(),
+            config_params: ton_types::HashmapE::with_hashmap(32, Some(params.0)),
+        };
+        config.config_addr = config.config_address().handle_value_error()?;
+
+        ton_executor::BlockchainConfig::with_config(config, global_id)
+            .handle_value_error()
+            .map(Self::from)
+    }
+
     #[getter]
     fn global_id(&self) -> i32 {
         self.0.global_id()
@@ -55,36 +71,36 @@ impl BlockchainConfig {
     #[getter]
     fn config_address(&self) -> PyResult<Address> {
         let config = self.0.raw_config();
-        let addr = config.config_address().handle_runtime_error()?;
+        let addr = config.config_address().handle_cell_error()?;
         ton_block::MsgAddressInt::with_standart(None, -1, addr.into())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Address)
     }
 
     #[getter]
     fn elector_address(&self) -> PyResult<Address> {
         let config = self.0.raw_config();
-        let addr = config.elector_address().handle_runtime_error()?;
+        let addr = config.elector_address().handle_cell_error()?;
         ton_block::MsgAddressInt::with_standart(None, -1, addr.into())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Address)
     }
 
     #[getter]
     fn minter_address(&self) -> PyResult<Address> {
         let config = self.0.raw_config();
-        let addr = config.minter_address().handle_runtime_error()?;
+        let addr = config.minter_address().handle_cell_error()?;
         ton_block::MsgAddressInt::with_standart(None, -1, addr.into())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Address)
     }
 
     #[getter]
     fn fee_collector_address(&self) -> PyResult<Address> {
         let config = self.0.raw_config();
-        let addr = config.fee_collector_address().handle_runtime_error()?;
+        let addr = config.fee_collector_address().handle_cell_error()?;
         ton_block::MsgAddressInt::with_standart(None, -1, addr.into())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Address)
     }
 
@@ -96,13 +112,11 @@ impl BlockchainConfig {
             .serialize()
             .and_then(ton_types::SliceData::load_cell)
             .unwrap();
-        Ok(
-            if let Some(value) = config.get(key).handle_runtime_error()? {
-                value.remaining_references() != 0
-            } else {
-                false
-            },
-        )
+        Ok(if let Some(value) = config.get(key).handle_cell_error()? {
+            value.remaining_references() != 0
+        } else {
+            false
+        })
     }
 
     fn get_raw_param(&self, index: u32) -> PyResult<Option<Cell>> {
@@ -111,7 +125,7 @@ impl BlockchainConfig {
             .serialize()
             .and_then(ton_types::SliceData::load_cell)
             .unwrap();
-        let value = config.get(key).handle_runtime_error()?;
+        let value = config.get(key).handle_cell_error()?;
         Ok(value.and_then(|slice| slice.reference_opt(0)).map(Cell))
     }
 
@@ -120,10 +134,26 @@ impl BlockchainConfig {
         config
             .config_params
             .serialize()
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Cell)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let params = match self.0.raw_config().config_params.data() {
+            Some(params) => Cell(params.clone()),
+            None => return Err(CellError::new_err("Empty config params")),
+        };
+        reduce_with::<Self>(py, "from_params_dict_cell", (params, self.global_id()))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<BlockchainConfig global_id={} capabilities=0x{:016x}, global_version=0x{}>",
@@ -147,11 +177,33 @@ impl AsRef<ton_executor::BlockchainConfig> for BlockchainConfig {
     }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct AccountState(pub ton_block::AccountStuff);
 
 #[pymethods]
 impl AccountState {
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, bytes: &[u8]) -> PyResult<Self> {
+        let account = py
+            .allow_threads(|| ton_block::Account::construct_from_bytes(bytes))
+            .handle_cell_error()?;
+        match account {
+            ton_block::Account::Account(stuff) => Ok(Self(stuff)),
+            ton_block::Account::AccountNone => Err(CellError::new_err("Account does not exist")),
+        }
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        let bytes = py.allow_threads(|| {
+            let cell = ton_block::Account::Account(self.0.clone())
+                .serialize()
+                .handle_cell_error()?;
+            ton_types::serialize_toc(&cell).handle_cell_error()
+        })?;
+        Ok(PyBytes::new(py, &bytes))
+    }
+
     #[getter]
     fn storage_used(&self) -> StorageUsed {
         StorageUsed(self.0.storage_stat.used.clone())
@@ -206,6 +258,19 @@ impl AccountState {
         }
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<AccountState balance={}, {:?}>",
@@ -215,11 +280,23 @@ impl AccountState {
     }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct StorageUsed(ton_block::StorageUsed);
 
 #[pymethods]
 impl StorageUsed {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        ton_block::StorageUsed::construct_from_bytes(bytes)
+            .handle_cell_error()
+            .map(Self)
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        write_to_bytes(py, &self.0)
+    }
+
     #[getter]
     fn cells(&self) -> u64 {
         self.0.cells.as_u64()
@@ -230,6 +307,19 @@ impl StorageUsed {
         self.0.bits.as_u64()
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!("<StorageUsed cells={}, bits={}>", self.cells(), self.bits(),)
     }
@@ -243,7 +333,7 @@ impl TryFrom<nt::transport::models::RawTransaction> for Transaction {
     type Error = PyErr;
 
     fn try_from(value: nt::transport::models::RawTransaction) -> Result<Self, Self::Error> {
-        let descr = value.data.read_description().handle_runtime_error()?;
+        let descr = value.data.read_description().handle_cell_error()?;
         Ok(Self(Arc::new(SharedTransaction {
             hash: value.hash,
             data: value.data,
@@ -257,8 +347,8 @@ impl TryFrom<ton_types::Cell> for Transaction {
 
     fn try_from(value: ton_types::Cell) -> Result<Self, Self::Error> {
         let hash = value.repr_hash();
-        let data = ton_block::Transaction::construct_from_cell(value).handle_runtime_error()?;
-        let descr = data.read_description().handle_runtime_error()?;
+        let data = ton_block::Transaction::construct_from_cell(value).handle_cell_error()?;
+        let descr = data.read_description().handle_cell_error()?;
         Ok(Self(Arc::new(SharedTransaction { data, descr, hash })))
     }
 }
@@ -266,9 +356,11 @@ impl TryFrom<ton_types::Cell> for Transaction {
 #[pymethods]
 impl Transaction {
     #[staticmethod]
-    fn from_bytes(mut bytes: &[u8]) -> PyResult<Self> {
-        let cell = ton_types::deserialize_tree_of_cells(&mut bytes).handle_runtime_error()?;
-        Self::try_from(cell)
+    fn from_bytes(py: Python<'_>, mut bytes: &[u8]) -> PyResult<Self> {
+        py.allow_threads(|| {
+            let cell = ton_types::deserialize_tree_of_cells(&mut bytes).handle_cell_error()?;
+            Self::try_from(cell)
+        })
     }
 
     #[staticmethod]
@@ -277,10 +369,10 @@ impl Transaction {
     }
 
     #[staticmethod]
-    fn decode(value: &str, encoding: Option<&str>) -> PyResult<Self> {
+    fn decode(py: Python<'_>, value: &str, encoding: Option<&str>) -> PyResult<Self> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
         let bytes = encoding.decode_bytes(value)?;
-        Self::from_bytes(&bytes)
+        Self::from_bytes(py, &bytes)
     }
 
     #[getter]
@@ -299,7 +391,7 @@ impl Transaction {
                     TransactionType::Tock
                 })
             }
-            _ => Err(PyRuntimeError::new_err("Unsupported transaction type")),
+            _ => Err(CellError::new_err("Unsupported transaction type")),
         }
     }
 
@@ -383,7 +475,7 @@ impl Transaction {
         match &self.0.descr {
             ton_block::TransactionDescr::Ordinary(descr) => Ok(descr.destroyed),
             ton_block::TransactionDescr::TickTock(descr) => Ok(descr.destroyed),
-            _ => Err(PyRuntimeError::new_err("Unsupported transaction type")),
+            _ => Err(CellError::new_err("Unsupported transaction type")),
         }
     }
 
@@ -396,7 +488,7 @@ impl Transaction {
             ton_block::TransactionDescr::TickTock(descr) => {
                 Ok(Some(TransactionStoragePhase(descr.storage.clone())))
             }
-            _ => Err(PyRuntimeError::new_err("Unsupported transaction type")),
+            _ => Err(CellError::new_err("Unsupported transaction type")),
         }
     }
 
@@ -407,16 +499,16 @@ impl Transaction {
                 Ok(descr.credit_ph.clone().map(TransactionCreditPhase))
             }
             ton_block::TransactionDescr::TickTock(_) => Ok(None),
-            _ => Err(PyRuntimeError::new_err("Unsupported transaction type")),
+            _ => Err(CellError::new_err("Unsupported transaction type")),
         }
     }
 
     #[getter]
-    fn compute_phase(&self) -> PyResult<Option<TransactionComputePhase>> {
+    pub fn compute_phase(&self) -> PyResult<Option<TransactionComputePhase>> {
         let compute_phase = match &self.0.descr {
             ton_block::TransactionDescr::Ordinary(descr) => &descr.compute_ph,
             ton_block::TransactionDescr::TickTock(descr) => &descr.compute_ph,
-            _ => return Err(PyRuntimeError::new_err("Unsupported transaction type")),
+            _ => return Err(CellError::new_err("Unsupported transaction type")),
         };
 
         Ok(match compute_phase {
@@ -426,11 +518,11 @@ impl Transaction {
     }
 
     #[getter]
-    fn action_phase(&self) -> PyResult<Option<TransactionActionPhase>> {
+    pub fn action_phase(&self) -> PyResult<Option<TransactionActionPhase>> {
         let action = match &self.0.descr {
             ton_block::TransactionDescr::Ordinary(descr) => &descr.action,
             ton_block::TransactionDescr::TickTock(descr) => &descr.action,
-            _ => return Err(PyRuntimeError::new_err("Unsupported transaction type")),
+            _ => return Err(CellError::new_err("Unsupported transaction type")),
         };
         Ok(action.clone().map(TransactionActionPhase))
     }
@@ -447,12 +539,10 @@ impl Transaction {
 
     pub fn get_in_msg(&self) -> PyResult<Message> {
         let Some(msg_cell) = self.0.data.in_msg_cell() else {
-            return Err(PyRuntimeError::new_err(
-                "Transaction without incoming message",
-            ));
+            return Err(CellError::new_err("Transaction without incoming message"));
         };
         let hash = msg_cell.repr_hash();
-        let data = ton_block::Message::construct_from_cell(msg_cell).handle_runtime_error()?;
+        let data = ton_block::Message::construct_from_cell(msg_cell).handle_cell_error()?;
         Ok(Message { data, hash })
     }
 
@@ -468,24 +558,41 @@ impl Transaction {
                 result.push(Message { data, hash });
                 Ok(true)
             })
-            .handle_runtime_error()?;
+            .handle_cell_error()?;
         Ok(result)
     }
 
-    fn encode(&self, encoding: Option<&str>) -> PyResult<String> {
+    fn encode(&self, py: Python<'_>, encoding: Option<&str>) -> PyResult<String> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
-        let cell = self.0.data.serialize().handle_runtime_error()?;
-        encoding.encode_cell(&cell)
+        py.allow_threads(|| {
+            let cell = self.0.data.serialize().handle_cell_error()?;
+            encoding.encode_cell(&cell)
+        })
     }
 
     fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
-        let cell = self.0.data.serialize().handle_runtime_error()?;
-        let bytes = ton_types::serialize_toc(&cell).handle_runtime_error()?;
+        let bytes = py.allow_threads(|| {
+            let cell = self.0.data.serialize().handle_cell_error()?;
+            ton_types::serialize_toc(&cell).handle_cell_error()
+        })?;
         Ok(PyBytes::new(py, &bytes))
     }
 
     fn build_cell(&self) -> PyResult<Cell> {
-        self.0.data.serialize().handle_runtime_error().map(Cell)
+        self.0.data.serialize().handle_cell_error().map(Cell)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
     }
 
     fn __repr__(&self) -> String {
@@ -511,11 +618,23 @@ pub struct SharedTransaction {
     pub hash: ton_types::UInt256,
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct TransactionStoragePhase(ton_block::TrStoragePhase);
 
 #[pymethods]
 impl TransactionStoragePhase {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        ton_block::TrStoragePhase::construct_from_bytes(bytes)
+            .handle_cell_error()
+            .map(Self)
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        write_to_bytes(py, &self.0)
+    }
+
     #[getter]
     fn storage_fees_collected(&self) -> Tokens {
         self.0.storage_fees_collected.into()
@@ -530,13 +649,38 @@ impl TransactionStoragePhase {
     fn status_change(&self) -> AccountStatusChange {
         self.0.status_change.clone().into()
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct TransactionCreditPhase(ton_block::TrCreditPhase);
 
 #[pymethods]
 impl TransactionCreditPhase {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        ton_block::TrCreditPhase::construct_from_bytes(bytes)
+            .handle_cell_error()
+            .map(Self)
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        write_to_bytes(py, &self.0)
+    }
+
     #[getter]
     fn due_fees_collected(&self) -> Option<Tokens> {
         self.0.due_fees_collected.map(Tokens::from)
@@ -546,13 +690,41 @@ impl TransactionCreditPhase {
     fn credit(&self) -> Tokens {
         self.0.credit.grams.into()
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct TransactionComputePhase(ton_block::TrComputePhaseVm);
 
 #[pymethods]
 impl TransactionComputePhase {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        match ton_block::TrComputePhase::construct_from_bytes(bytes).handle_cell_error()? {
+            ton_block::TrComputePhase::Vm(phase) => Ok(Self(phase)),
+            ton_block::TrComputePhase::Skipped(_) => {
+                Err(CellError::new_err("Skipped compute phase"))
+            }
+        }
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        write_to_bytes(py, &ton_block::TrComputePhase::Vm(self.0.clone()))
+    }
+
     #[getter]
     fn success(&self) -> bool {
         self.0.success
@@ -574,7 +746,7 @@ impl TransactionComputePhase {
     }
 
     #[getter]
-    fn gas_used(&self) -> u64 {
+    pub fn gas_used(&self) -> u64 {
         self.0.gas_used.as_u64()
     }
 
@@ -617,13 +789,38 @@ impl TransactionComputePhase {
     fn vm_final_state_hash<'a>(&self, py: Python<'a>) -> &'a PyBytes {
         PyBytes::new(py, self.0.vm_final_state_hash.as_slice())
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct TransactionActionPhase(ton_block::TrActionPhase);
 
 #[pymethods]
 impl TransactionActionPhase {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        ton_block::TrActionPhase::construct_from_bytes(bytes)
+            .handle_cell_error()
+            .map(Self)
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        write_to_bytes(py, &self.0)
+    }
+
     #[getter]
     fn success(&self) -> bool {
         self.0.success
@@ -688,13 +885,39 @@ impl TransactionActionPhase {
     fn action_list_hash<'a>(&self, py: Python<'a>) -> &'a PyBytes {
         PyBytes::new(py, self.0.action_list_hash.as_slice())
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
+#[derive(Clone)]
 #[pyclass]
 pub struct TransactionBouncePhase(ton_block::TrBouncePhaseOk);
 
 #[pymethods]
 impl TransactionBouncePhase {
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        match ton_block::TrBouncePhase::construct_from_bytes(bytes).handle_cell_error()? {
+            ton_block::TrBouncePhase::Ok(phase) => Ok(Self(phase)),
+            _ => Err(CellError::new_err("Unsuccessful bounce phase")),
+        }
+    }
+
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        write_to_bytes(py, &ton_block::TrBouncePhase::Ok(self.0.clone()))
+    }
+
     #[getter]
     fn msg_fees(&self) -> Tokens {
         self.0.msg_fees.into()
@@ -704,6 +927,19 @@ impl TransactionBouncePhase {
     fn fwd_fees(&self) -> Tokens {
         self.0.fwd_fees.into()
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -736,6 +972,18 @@ impl TransactionType {
     fn __richcmp__(&self, other: &Self, op: pyo3::basic::CompareOp) -> bool {
         op.matches(self.cmp(other))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_enum::<Self>(py, &format!("{:?}", self))
+    }
+
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        *self
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -775,6 +1023,18 @@ impl AccountStatus {
     fn __richcmp__(&self, other: &Self, op: pyo3::basic::CompareOp) -> bool {
         op.matches(self.cmp(other))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_enum::<Self>(py, &format!("{:?}", self))
+    }
+
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        *self
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -812,6 +1072,18 @@ impl AccountStatusChange {
     fn __richcmp__(&self, other: &Self, op: pyo3::basic::CompareOp) -> bool {
         op.matches(self.cmp(other))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_enum::<Self>(py, &format!("{:?}", self))
+    }
+
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        *self
+    }
 }
 
 #[derive(Clone)]
@@ -826,7 +1098,16 @@ impl TryFrom<ton_types::Cell> for Message {
 
     fn try_from(msg_cell: ton_types::Cell) -> Result<Self, Self::Error> {
         let hash = msg_cell.repr_hash();
-        let data = ton_block::Message::construct_from_cell(msg_cell).handle_value_error()?;
+        let data = ton_block::Message::construct_from_cell(msg_cell).handle_cell_error()?;
+        Ok(Self { data, hash })
+    }
+}
+
+impl TryFrom<ton_block::Message> for Message {
+    type Error = PyErr;
+
+    fn try_from(data: ton_block::Message) -> Result<Self, Self::Error> {
+        let hash = data.hash().handle_cell_error()?;
         Ok(Self { data, hash })
     }
 }
@@ -834,9 +1115,11 @@ impl TryFrom<ton_types::Cell> for Message {
 #[pymethods]
 impl Message {
     #[staticmethod]
-    fn from_bytes(mut bytes: &[u8]) -> PyResult<Self> {
-        let cell = ton_types::deserialize_tree_of_cells(&mut bytes).handle_runtime_error()?;
-        Self::try_from(cell)
+    fn from_bytes(py: Python<'_>, mut bytes: &[u8]) -> PyResult<Self> {
+        py.allow_threads(|| {
+            let cell = ton_types::deserialize_tree_of_cells(&mut bytes).handle_cell_error()?;
+            Self::try_from(cell)
+        })
     }
 
     #[staticmethod]
@@ -845,10 +1128,10 @@ impl Message {
     }
 
     #[staticmethod]
-    fn decode(value: &str, encoding: Option<&str>) -> PyResult<Self> {
+    fn decode(py: Python<'_>, value: &str, encoding: Option<&str>) -> PyResult<Self> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
         let bytes = encoding.decode_bytes(value)?;
-        Self::from_bytes(&bytes)
+        Self::from_bytes(py, &bytes)
     }
 
     #[new]
@@ -883,7 +1166,7 @@ impl Message {
         };
 
         if let Some(body) = body {
-            message.set_body(ton_types::SliceData::load_cell(body.0).handle_value_error()?);
+            message.set_body(ton_types::SliceData::load_cell(body.0).handle_cell_error()?);
         }
         if let Some(state_init) = state_init {
             message.set_state_init(state_init.0);
@@ -971,7 +1254,7 @@ impl Message {
     }
 
     #[getter]
-    fn dst(&self) -> Option<Address> {
+    pub fn dst(&self) -> Option<Address> {
         self.data.dst().map(Address)
     }
 
@@ -989,7 +1272,7 @@ impl Message {
     }
 
     #[getter]
-    fn body(&self) -> Option<Cell> {
+    pub fn body(&self) -> Option<Cell> {
         self.data
             .body()
             .map(ton_types::SliceData::into_cell)
@@ -997,24 +1280,41 @@ impl Message {
     }
 
     #[getter]
-    fn state_init(&self) -> Option<StateInit> {
+    pub fn state_init(&self) -> Option<StateInit> {
         self.data.state_init().cloned().map(StateInit)
     }
 
-    fn encode(&self, encoding: Option<&str>) -> PyResult<String> {
+    fn encode(&self, py: Python<'_>, encoding: Option<&str>) -> PyResult<String> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
-        let cell = self.data.serialize().handle_runtime_error()?;
-        encoding.encode_cell(&cell)
+        py.allow_threads(|| {
+            let cell = self.data.serialize().handle_cell_error()?;
+            encoding.encode_cell(&cell)
+        })
     }
 
     fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
-        let cell = self.data.serialize().handle_runtime_error()?;
-        let bytes = ton_types::serialize_toc(&cell).handle_runtime_error()?;
+        let bytes = py.allow_threads(|| {
+            let cell = self.data.serialize().handle_cell_error()?;
+            ton_types::serialize_toc(&cell).handle_cell_error()
+        })?;
         Ok(PyBytes::new(py, &bytes))
     }
 
     fn build_cell(&self) -> PyResult<Cell> {
-        self.data.serialize().handle_runtime_error().map(Cell)
+        self.data.serialize().handle_cell_error().map(Cell)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
     }
 
     fn __repr__(&self) -> String {
@@ -1041,6 +1341,7 @@ impl MessageHeader {
     }
 }
 
+#[derive(Clone)]
 #[pyclass(extends = MessageHeader)]
 pub struct InternalMessageHeader(pub ton_block::InternalMessageHeader);
 
@@ -1134,8 +1435,41 @@ impl InternalMessageHeader {
     pub fn created_lt(&self) -> u64 {
         self.0.created_lt
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        let src = match &self.0.src {
+            ton_block::MsgAddressIntOrNone::Some(addr) => Some(Address(addr.clone())),
+            ton_block::MsgAddressIntOrNone::None => None,
+        };
+        let args = (
+            self.value(),
+            self.dst(),
+            src,
+            self.0.ihr_disabled,
+            self.0.bounce,
+            self.0.bounced,
+            self.ihr_fee(),
+            self.fwd_fee(),
+            self.0.created_lt,
+            self.created_at(),
+        );
+        reduce_new::<Self>(py, args)
+    }
+
+    fn __copy__(&self, py: Python<'_>) -> PyResult<Py<Self>> {
+        let base = MessageHeader(MessageType::Internal);
+        Py::new(
+            py,
+            PyClassInitializer::from(base).add_subclass(self.clone()),
+        )
+    }
+
+    fn __deepcopy__(&self, py: Python<'_>, _memo: &PyAny) -> PyResult<Py<Self>> {
+        self.__copy__(py)
+    }
 }
 
+#[derive(Clone)]
 #[pyclass(extends = MessageHeader)]
 pub struct ExternalInMessageHeader(ton_block::ExternalInboundMessageHeader);
 
@@ -1163,8 +1497,25 @@ impl ExternalInMessageHeader {
     pub fn import_fee(&self) -> Tokens {
         self.0.import_fee.into()
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.dst(), self.import_fee()))
+    }
+
+    fn __copy__(&self, py: Python<'_>) -> PyResult<Py<Self>> {
+        let base = MessageHeader(MessageType::ExternalIn);
+        Py::new(
+            py,
+            PyClassInitializer::from(base).add_subclass(self.clone()),
+        )
+    }
+
+    fn __deepcopy__(&self, py: Python<'_>, _memo: &PyAny) -> PyResult<Py<Self>> {
+        self.__copy__(py)
+    }
 }
 
+#[derive(Clone)]
 #[pyclass(extends = MessageHeader)]
 pub struct ExternalOutMessageHeader(ton_block::ExtOutMessageHeader);
 
@@ -1209,6 +1560,26 @@ impl ExternalOutMessageHeader {
     pub fn created_lt(&self) -> u64 {
         self.0.created_lt
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        let src = match &self.0.src {
+            ton_block::MsgAddressIntOrNone::Some(addr) => Some(Address(addr.clone())),
+            ton_block::MsgAddressIntOrNone::None => None,
+        };
+        reduce_new::<Self>(py, (src, self.0.created_lt, self.created_at()))
+    }
+
+    fn __copy__(&self, py: Python<'_>) -> PyResult<Py<Self>> {
+        let base = MessageHeader(MessageType::ExternalOut);
+        Py::new(
+            py,
+            PyClassInitializer::from(base).add_subclass(self.clone()),
+        )
+    }
+
+    fn __deepcopy__(&self, py: Python<'_>, _memo: &PyAny) -> PyResult<Py<Self>> {
+        self.__copy__(py)
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -1236,6 +1607,18 @@ impl MessageType {
     fn __richcmp__(&self, other: &Self, op: pyo3::basic::CompareOp) -> bool {
         op.matches(self.cmp(other))
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_enum::<Self>(py, &format!("{:?}", self))
+    }
+
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        *self
+    }
 }
 
 #[derive(Clone)]
@@ -1246,7 +1629,7 @@ impl StateInit {
     fn expect_code(&self) -> PyResult<ton_types::Cell> {
         match &self.0.code {
             Some(code) => Ok(code.clone()),
-            None => Err(PyRuntimeError::new_err("StateInit has no code")),
+            None => Err(CellError::new_err("StateInit has no code")),
         }
     }
 }
@@ -1254,8 +1637,8 @@ impl StateInit {
 #[pymethods]
 impl StateInit {
     #[staticmethod]
-    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
-        ton_block::StateInit::construct_from_bytes(bytes)
+    fn from_bytes(py: Python<'_>, bytes: &[u8]) -> PyResult<Self> {
+        py.allow_threads(|| ton_block::StateInit::construct_from_bytes(bytes))
             .handle_value_error()
             .map(Self)
     }
@@ -1268,17 +1651,18 @@ impl StateInit {
     }
 
     #[staticmethod]
-    fn decode(value: &str, encoding: Option<&str>) -> PyResult<Self> {
+    fn decode(py: Python<'_>, value: &str, encoding: Option<&str>) -> PyResult<Self> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
         let bytes = encoding.decode_bytes(value)?;
-        Self::from_bytes(&bytes)
+        Self::from_bytes(py, &bytes)
     }
 
     #[new]
-    fn new(code: Option<Cell>, data: Option<Cell>) -> Self {
+    fn new(code: Option<Cell>, data: Option<Cell>, special: Option<(bool, bool)>) -> Self {
         Self(ton_block::StateInit {
             code: code.map(|Cell(code)| code),
             data: data.map(|Cell(cell)| cell),
+            special: special.map(|(tick, tock)| ton_block::TickTock { tick, tock }),
             ..Default::default()
         })
     }
@@ -1309,17 +1693,29 @@ impl StateInit {
         self.0.data = data.map(|Cell(data)| data);
     }
 
+    /// Tick and tock flags of the special account.
+    #[getter]
+    fn get_special(&self) -> Option<(bool, bool)> {
+        let special = self.0.special.as_ref()?;
+        Some((special.tick, special.tock))
+    }
+
+    #[setter]
+    fn set_special(&mut self, special: Option<(bool, bool)>) {
+        self.0.special = special.map(|(tick, tock)| ton_block::TickTock { tick, tock });
+    }
+
     /// Adds the specified salt to the code of this state init.
     fn set_code_salt(&mut self, salt: &Cell) -> PyResult<()> {
         self.0.code = nt::abi::set_code_salt(self.expect_code()?, salt.0.clone())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Some)?;
         Ok(())
     }
 
     /// Tries to extract a salt from the code of this state init.
     fn get_code_salt(&self) -> PyResult<Option<Cell>> {
-        let salt = nt::abi::get_code_salt(self.expect_code()?).handle_runtime_error()?;
+        let salt = nt::abi::get_code_salt(self.expect_code()?).handle_cell_error()?;
         Ok(salt.map(Cell))
     }
 
@@ -1330,24 +1726,41 @@ impl StateInit {
             workchain.unwrap_or_default(),
             cell.0.repr_hash().into(),
         )
-        .handle_runtime_error()
+        .handle_cell_error()
         .map(Address)
     }
 
-    fn encode(&self, encoding: Option<&str>) -> PyResult<String> {
+    fn encode(&self, py: Python<'_>, encoding: Option<&str>) -> PyResult<String> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
-        let cell = self.0.serialize().handle_runtime_error()?;
-        encoding.encode_cell(&cell)
+        py.allow_threads(|| {
+            let cell = self.0.serialize().handle_cell_error()?;
+            encoding.encode_cell(&cell)
+        })
     }
 
     fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
-        let cell = self.0.serialize().handle_runtime_error()?;
-        let bytes = ton_types::serialize_toc(&cell).handle_runtime_error()?;
+        let bytes = py.allow_threads(|| {
+            let cell = self.0.serialize().handle_cell_error()?;
+            ton_types::serialize_toc(&cell).handle_cell_error()
+        })?;
         Ok(PyBytes::new(py, &bytes))
     }
 
     fn build_cell(&self) -> PyResult<Cell> {
-        self.0.serialize().handle_runtime_error().map(Cell)
+        self.0.serialize().handle_cell_error().map(Cell)
+    }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
     }
 
     fn __repr__(&self) -> String {
@@ -1360,10 +1773,20 @@ impl StateInit {
             }
         }
 
+        let special = match &self.0.special {
+            Some(special) => Cow::Owned(format!(
+                "({}, {})",
+                DisplayBool(special.tick),
+                DisplayBool(special.tock)
+            )),
+            None => Cow::Borrowed("None"),
+        };
+
         format!(
-            "<StateInit code_hash='{}', data_hash='{}'>",
+            "<StateInit code_hash='{}', data_hash='{}', special={}>",
             field_repr(&self.0.code),
             field_repr(&self.0.data),
+            special,
         )
     }
 }
@@ -1426,7 +1849,7 @@ impl Address {
     }
 
     fn as_cell(&self) -> PyResult<Cell> {
-        self.0.serialize().handle_value_error().map(Cell)
+        self.0.serialize().handle_cell_error().map(Cell)
     }
 
     fn as_slice(&self) -> PyResult<CellSlice> {
@@ -1445,6 +1868,18 @@ impl Address {
         self.0.to_string()
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        reduce_new::<Self>(py, (self.0.to_string(),))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!("Address('{}')", self.0)
     }
@@ -1464,16 +1899,16 @@ pub struct Cell(pub ton_types::Cell);
 
 impl Cell {
     pub fn try_from_struct(value: &dyn Serializable) -> PyResult<Self> {
-        value.serialize().handle_runtime_error().map(Self)
+        value.serialize().handle_cell_error().map(Self)
     }
 }
 
 #[pymethods]
 impl Cell {
     #[staticmethod]
-    fn from_bytes(mut bytes: &[u8]) -> PyResult<Self> {
-        ton_types::deserialize_tree_of_cells(&mut bytes)
-            .handle_value_error()
+    fn from_bytes(py: Python<'_>, mut bytes: &[u8]) -> PyResult<Self> {
+        py.allow_threads(|| ton_types::deserialize_tree_of_cells(&mut bytes))
+            .handle_cell_error()
             .map(Self)
     }
 
@@ -1497,13 +1932,14 @@ impl Cell {
 
         nt::abi::pack_into_cell(&tokens, abi_version)
             .map(Self)
-            .handle_runtime_error()
+            .handle_abi_encode_error()
     }
 
     #[staticmethod]
-    fn decode(value: &str, encoding: Option<&str>) -> PyResult<Self> {
+    fn decode(py: Python<'_>, value: &str, encoding: Option<&str>) -> PyResult<Self> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
-        encoding.decode_cell(value.trim()).map(Self)
+        py.allow_threads(|| encoding.decode_cell(value.trim()))
+            .map(Self)
     }
 
     /// Constructs a new empty cell.
@@ -1530,18 +1966,20 @@ impl Cell {
 
     fn as_slice(&self) -> PyResult<CellSlice> {
         Ok(CellSlice {
-            slice: ton_types::SliceData::load_cell(self.0.clone()).handle_value_error()?,
+            slice: ton_types::SliceData::load_cell(self.0.clone()).handle_cell_error()?,
             cell: self.clone(),
         })
     }
 
-    fn encode(&self, encoding: Option<&str>) -> PyResult<String> {
+    fn encode(&self, py: Python<'_>, encoding: Option<&str>) -> PyResult<String> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
-        encoding.encode_cell(&self.0)
+        py.allow_threads(|| encoding.encode_cell(&self.0))
     }
 
     fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
-        let bytes = ton_types::serialize_toc(&self.0).handle_runtime_error()?;
+        let bytes = py
+            .allow_threads(|| ton_types::serialize_toc(&self.0))
+            .handle_cell_error()?;
         Ok(PyBytes::new(py, &bytes))
     }
 
@@ -1563,10 +2001,10 @@ impl Cell {
         };
 
         let allow_partial = allow_partial.unwrap_or_default();
-        let data = ton_types::SliceData::load_cell_ref(&self.0).handle_runtime_error()?;
+        let data = ton_types::SliceData::load_cell_ref(&self.0).handle_cell_error()?;
 
         let tokens = nt::abi::unpack_from_cell(&params, data, allow_partial, abi_version)
-            .handle_runtime_error()?;
+            .handle_abi_decode_error()?;
 
         convert_tokens(py, tokens)
     }
@@ -1575,16 +2013,29 @@ impl Cell {
     /// returns a new cell with the salt added to it.
     fn with_code_salt(&self, salt: &Cell) -> PyResult<Cell> {
         nt::abi::set_code_salt(self.0.clone(), salt.0.clone())
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Cell)
     }
 
     /// Tries to interpret this cell as a salted code and tries to extract the salt from it.
     fn get_code_salt(&self) -> PyResult<Option<Cell>> {
-        let salt = nt::abi::get_code_salt(self.0.clone()).handle_runtime_error()?;
+        let salt = nt::abi::get_code_salt(self.0.clone()).handle_cell_error()?;
         Ok(salt.map(Cell))
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<Cell repr_hash='{:x}', bits={}, refs={}>",
@@ -1634,14 +2085,28 @@ pub struct CellSlice {
 
 #[pymethods]
 impl CellSlice {
+    #[new]
+    fn new(
+        cell: Cell,
+        bits_offset: Option<usize>,
+        refs_offset: Option<usize>,
+        bits: Option<usize>,
+        refs: Option<usize>,
+    ) -> PyResult<Self> {
+        let mut slice = cell.as_slice()?;
+        slice.advance(bits_offset, refs_offset)?;
+        slice.shrink(bits, refs)?;
+        Ok(slice)
+    }
+
     fn advance(&mut self, bits: Option<usize>, refs: Option<usize>) -> PyResult<()> {
         if let Some(bits) = bits {
-            self.slice.move_by(bits).handle_value_error()?;
+            self.slice.move_by(bits).handle_cell_error()?;
         }
 
         if let Some(refs) = refs {
             for _ in 0..refs {
-                self.slice.checked_drain_reference().handle_value_error()?;
+                self.slice.checked_drain_reference().handle_cell_error()?;
             }
         }
 
@@ -1651,7 +2116,7 @@ impl CellSlice {
     pub fn shrink(&mut self, bits: Option<usize>, refs: Option<usize>) -> PyResult<()> {
         if let Some(bits) = bits {
             if bits > self.slice.remaining_bits() {
-                return Err(ton_types::ExceptionCode::CellUnderflow).handle_value_error()?;
+                return Err(ton_types::ExceptionCode::CellUnderflow).handle_cell_error()?;
             }
 
             self.slice.shrink_data(..bits);
@@ -1659,7 +2124,7 @@ impl CellSlice {
 
         if let Some(refs) = refs {
             if refs > self.slice.remaining_references() {
-                return Err(ton_types::ExceptionCode::CellUnderflow).handle_value_error()?;
+                return Err(ton_types::ExceptionCode::CellUnderflow).handle_cell_error()?;
             }
 
             self.slice.shrink_references(..refs);
@@ -1690,8 +2155,7 @@ impl CellSlice {
 
     #[getter]
     fn refs_offset(&self) -> usize {
-        let total_refs = self.slice.cell().references_count();
-        self.slice.remaining_references() - total_refs
+        self.slice.get_references().start
     }
 
     fn is_empty(&self) -> bool {
@@ -1711,11 +2175,11 @@ impl CellSlice {
     }
 
     fn get_bit(&self, offset: usize) -> PyResult<bool> {
-        self.slice.get_bit(offset).handle_value_error()
+        self.slice.get_bit(offset).handle_cell_error()
     }
 
     fn get_u8(&self, offset: usize) -> PyResult<u8> {
-        self.slice.get_byte(offset).handle_value_error()
+        self.slice.get_byte(offset).handle_cell_error()
     }
 
     fn get_i8(&self, offset: usize) -> PyResult<i8> {
@@ -1726,7 +2190,7 @@ impl CellSlice {
         let mut value: u16 = 0;
         for i in 0..2 {
             value |=
-                (self.slice.get_byte(offset + 8 * i).handle_value_error()? as u16) << (8 * (1 - i));
+                (self.slice.get_byte(offset + 8 * i).handle_cell_error()? as u16) << (8 * (1 - i));
         }
         Ok(value)
     }
@@ -1739,7 +2203,7 @@ impl CellSlice {
         let mut value: u32 = 0;
         for i in 0..4 {
             value |=
-                (self.slice.get_byte(offset + 8 * i).handle_value_error()? as u32) << (8 * (3 - i));
+                (self.slice.get_byte(offset + 8 * i).handle_cell_error()? as u32) << (8 * (3 - i));
         }
         Ok(value)
     }
@@ -1752,7 +2216,7 @@ impl CellSlice {
         let mut value: u64 = 0;
         for i in 0..8 {
             value |=
-                (self.slice.get_byte(offset + 8 * i).handle_value_error()? as u64) << (8 * (7 - i));
+                (self.slice.get_byte(offset + 8 * i).handle_cell_error()? as u64) << (8 * (7 - i));
         }
         Ok(value)
     }
@@ -1764,7 +2228,7 @@ impl CellSlice {
     fn get_u128(&self, offset: usize) -> PyResult<u128> {
         let mut value: u128 = 0;
         for i in 0..16 {
-            value |= (self.slice.get_byte(offset + 8 * i).handle_value_error()? as u128)
+            value |= (self.slice.get_byte(offset + 8 * i).handle_cell_error()? as u128)
                 << (8 * (15 - i));
         }
         Ok(value)
@@ -1777,9 +2241,9 @@ impl CellSlice {
     fn get_u256(&self, offset: usize) -> PyResult<num_bigint::BigUint> {
         let mut value = num_bigint::BigUint::default();
         for i in 0..32 {
-            value |= num_bigint::BigUint::from(
-                self.slice.get_byte(offset + 8 * i).handle_value_error()?,
-            ) << (8 * (31 - i));
+            value |=
+                num_bigint::BigUint::from(self.slice.get_byte(offset + 8 * i).handle_cell_error()?)
+                    << (8 * (31 - i));
         }
         Ok(value)
     }
@@ -1787,7 +2251,7 @@ impl CellSlice {
     fn get_public_key(&self, offset: usize) -> PyResult<PublicKey> {
         let mut bytes = [0; 32];
         for (i, byte) in bytes.iter_mut().enumerate() {
-            *byte = self.slice.get_byte(offset + 8 * i).handle_value_error()?;
+            *byte = self.slice.get_byte(offset + 8 * i).handle_cell_error()?;
         }
         PublicKey::from_bytes(&bytes)
     }
@@ -1795,7 +2259,7 @@ impl CellSlice {
     fn get_signature(&self, offset: usize) -> PyResult<Signature> {
         let mut bytes = [0; 64];
         for (i, byte) in bytes.iter_mut().enumerate() {
-            *byte = self.slice.get_byte(offset + 8 * i).handle_value_error()?;
+            *byte = self.slice.get_byte(offset + 8 * i).handle_cell_error()?;
         }
         Signature::from_bytes(&bytes)
     }
@@ -1803,24 +2267,21 @@ impl CellSlice {
     fn get_bytes<'a>(&self, offset: usize, size: usize, py: Python<'a>) -> PyResult<&'a PyBytes> {
         let mut bytes = Vec::with_capacity(size);
         for i in 0..size {
-            bytes.push(self.slice.get_byte(offset + 8 * i).handle_value_error()?);
+            bytes.push(self.slice.get_byte(offset + 8 * i).handle_cell_error()?);
         }
         Ok(PyBytes::new(py, &bytes))
     }
 
     fn get_reference(&self, offset: usize) -> PyResult<Cell> {
-        self.slice
-            .reference(offset)
-            .handle_runtime_error()
-            .map(Cell)
+        self.slice.reference(offset).handle_cell_error().map(Cell)
     }
 
     fn load_bit(&mut self) -> PyResult<bool> {
-        self.slice.get_next_bit().handle_runtime_error()
+        self.slice.get_next_bit().handle_cell_error()
     }
 
     fn load_u8(&mut self) -> PyResult<u8> {
-        self.slice.get_next_byte().handle_runtime_error()
+        self.slice.get_next_byte().handle_cell_error()
     }
 
     fn load_i8(&mut self) -> PyResult<i8> {
@@ -1828,7 +2289,7 @@ impl CellSlice {
     }
 
     fn load_u16(&mut self) -> PyResult<u16> {
-        self.slice.get_next_u16().handle_runtime_error()
+        self.slice.get_next_u16().handle_cell_error()
     }
 
     fn load_i16(&mut self) -> PyResult<i16> {
@@ -1836,7 +2297,7 @@ impl CellSlice {
     }
 
     fn load_u32(&mut self) -> PyResult<u32> {
-        self.slice.get_next_u32().handle_runtime_error()
+        self.slice.get_next_u32().handle_cell_error()
     }
 
     fn load_i32(&mut self) -> PyResult<i32> {
@@ -1844,7 +2305,7 @@ impl CellSlice {
     }
 
     fn load_u64(&mut self) -> PyResult<u64> {
-        self.slice.get_next_u64().handle_runtime_error()
+        self.slice.get_next_u64().handle_cell_error()
     }
 
     fn load_i64(&mut self) -> PyResult<i64> {
@@ -1852,7 +2313,7 @@ impl CellSlice {
     }
 
     fn load_u128(&mut self) -> PyResult<u128> {
-        self.slice.get_next_u128().handle_runtime_error()
+        self.slice.get_next_u128().handle_cell_error()
     }
 
     fn load_i128(&mut self) -> PyResult<i128> {
@@ -1860,32 +2321,51 @@ impl CellSlice {
     }
 
     fn load_u256(&mut self) -> PyResult<num_bigint::BigUint> {
-        let bytes = self.slice.get_next_bytes(32).handle_value_error()?;
+        let bytes = self.slice.get_next_bytes(32).handle_cell_error()?;
         Ok(num_bigint::BigUint::from_bytes_be(&bytes))
     }
 
     fn load_public_key(&mut self) -> PyResult<PublicKey> {
-        let bytes = self.slice.get_next_bytes(32).handle_value_error()?;
+        let bytes = self.slice.get_next_bytes(32).handle_cell_error()?;
         PublicKey::from_bytes(&bytes)
     }
 
     fn load_signature(&mut self) -> PyResult<Signature> {
-        let bytes = self.slice.get_next_bytes(64).handle_value_error()?;
+        let bytes = self.slice.get_next_bytes(64).handle_cell_error()?;
         Signature::from_bytes(&bytes)
     }
 
     fn load_bytes<'a>(&mut self, size: usize, py: Python<'a>) -> PyResult<&'a PyBytes> {
-        let bytes = self.slice.get_next_bytes(size).handle_runtime_error()?;
+        let bytes = self.slice.get_next_bytes(size).handle_cell_error()?;
         Ok(PyBytes::new(py, &bytes))
     }
 
     fn load_reference(&mut self) -> PyResult<Cell> {
         self.slice
             .checked_drain_reference()
-            .handle_runtime_error()
+            .handle_cell_error()
             .map(Cell)
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyReduce {
+        let args = (
+            self.cell.clone(),
+            self.bits_offset(),
+            self.refs_offset(),
+            self.bits(),
+            self.refs(),
+        );
+        reduce_new::<Self>(py, args)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "<CellSlice cell={}, bits={}..{}, refs={}..{}>",
@@ -1950,7 +2430,7 @@ impl CellBuilder {
         let mut builder = self.builder.clone();
         if self.is_exotic {
             if builder.length_in_bits() < 8 {
-                return Err("Not enough data for an exotic cell").handle_value_error();
+                return Err("Not enough data for an exotic cell").handle_cell_error();
             }
 
             let mut children_mask = ton_types::LevelMask::default();
@@ -1958,18 +2438,17 @@ impl CellBuilder {
                 children_mask |= child.level_mask();
             }
 
-            let cell_type =
-                ton_types::CellType::try_from(builder.data()[0]).handle_value_error()?;
+            let cell_type = ton_types::CellType::try_from(builder.data()[0]).handle_cell_error()?;
 
             let level_mask = match cell_type {
                 ton_types::CellType::PrunedBranch => {
                     if builder.length_in_bits() < 16 {
-                        return Err(ton_types::ExceptionCode::CellUnderflow).handle_value_error();
+                        return Err(ton_types::ExceptionCode::CellUnderflow).handle_cell_error();
                     }
 
                     let raw_mask = builder.data()[1];
                     if raw_mask > 0b111 {
-                        return Err("Invalid pruned branch mask").handle_value_error();
+                        return Err("Invalid pruned branch mask").handle_cell_error();
                     }
                     ton_types::LevelMask::with_mask(raw_mask)
                 }
@@ -1979,7 +2458,7 @@ impl CellBuilder {
                 }
                 _ => {
                     return Err(format!("Incorrect type of exotic cell: {cell_type}"))
-                        .handle_value_error();
+                        .handle_cell_error();
                 }
             };
 
@@ -1987,7 +2466,7 @@ impl CellBuilder {
             builder.set_level_mask(level_mask);
         }
 
-        builder.into_cell().handle_value_error().map(Cell)
+        builder.into_cell().handle_cell_error().map(Cell)
     }
 
     fn store_zeros(&mut self, bits: usize) -> PyResult<()> {
@@ -2001,67 +2480,67 @@ impl CellBuilder {
     }
 
     fn store_bit_zero(&mut self) -> PyResult<()> {
-        self.builder.append_bit_zero().handle_value_error()?;
+        self.builder.append_bit_zero().handle_cell_error()?;
         Ok(())
     }
 
     fn store_bit_one(&mut self) -> PyResult<()> {
-        self.builder.append_bit_one().handle_value_error()?;
+        self.builder.append_bit_one().handle_cell_error()?;
         Ok(())
     }
 
     fn store_bit(&mut self, value: bool) -> PyResult<()> {
-        self.builder.append_bit_bool(value).handle_value_error()?;
+        self.builder.append_bit_bool(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_u8(&mut self, value: u8) -> PyResult<()> {
-        self.builder.append_u8(value).handle_value_error()?;
+        self.builder.append_u8(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_i8(&mut self, value: i8) -> PyResult<()> {
-        self.builder.append_i8(value).handle_value_error()?;
+        self.builder.append_i8(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_u16(&mut self, value: u16) -> PyResult<()> {
-        self.builder.append_u16(value).handle_value_error()?;
+        self.builder.append_u16(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_i16(&mut self, value: i16) -> PyResult<()> {
-        self.builder.append_i16(value).handle_value_error()?;
+        self.builder.append_i16(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_u32(&mut self, value: u32) -> PyResult<()> {
-        self.builder.append_u32(value).handle_value_error()?;
+        self.builder.append_u32(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_i32(&mut self, value: i32) -> PyResult<()> {
-        self.builder.append_i32(value).handle_value_error()?;
+        self.builder.append_i32(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_u64(&mut self, value: u64) -> PyResult<()> {
-        self.builder.append_u64(value).handle_value_error()?;
+        self.builder.append_u64(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_i64(&mut self, value: i64) -> PyResult<()> {
-        self.builder.append_i64(value).handle_value_error()?;
+        self.builder.append_i64(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_u128(&mut self, value: u128) -> PyResult<()> {
-        self.builder.append_u128(value).handle_value_error()?;
+        self.builder.append_u128(value).handle_cell_error()?;
         Ok(())
     }
 
     fn store_i128(&mut self, value: i128) -> PyResult<()> {
-        self.builder.append_i128(value).handle_value_error()?;
+        self.builder.append_i128(value).handle_cell_error()?;
         Ok(())
     }
 
@@ -2074,7 +2553,7 @@ impl CellBuilder {
 
     fn store_int(&mut self, value: num_bigint::BigInt, bits: usize) -> PyResult<()> {
         if bits > self.builder.bits_free() {
-            return Err(ton_types::ExceptionCode::CellOverflow).handle_value_error();
+            return Err(ton_types::ExceptionCode::CellOverflow).handle_cell_error();
         }
 
         let vec = value.to_signed_bytes_be();
@@ -2094,15 +2573,15 @@ impl CellBuilder {
 
             self.builder
                 .append_raw(&vec_padding, diff)
-                .handle_value_error()?;
+                .handle_cell_error()?;
             self.builder
                 .append_raw(&vec, bits - diff)
-                .handle_value_error()?;
+                .handle_cell_error()?;
         } else {
             let number_bits = value.bits();
             if number_bits > bits as u64 {
                 return Err(format!("Too many bits in value to fit into: {number_bits}"))
-                    .handle_value_error();
+                    .handle_cell_error();
             }
 
             let offset = vec_bits_length - bits;
@@ -2110,10 +2589,10 @@ impl CellBuilder {
 
             self.builder
                 .append_raw(&[first_byte], 8 - offset % 8)
-                .handle_value_error()?;
+                .handle_cell_error()?;
             self.builder
                 .append_raw(&vec[offset / 8 + 1..], vec[offset / 8 + 1..].len() * 8)
-                .handle_value_error()?;
+                .handle_cell_error()?;
         };
 
         Ok(())
@@ -2133,30 +2612,30 @@ impl CellBuilder {
 
     fn store_raw(&mut self, bytes: &[u8], bits: usize) -> PyResult<()> {
         if bits > self.builder.bits_free() {
-            return Err(ton_types::ExceptionCode::CellOverflow).handle_value_error();
+            return Err(ton_types::ExceptionCode::CellOverflow).handle_cell_error();
         }
-        self.builder.append_raw(bytes, bits).handle_value_error()?;
+        self.builder.append_raw(bytes, bits).handle_cell_error()?;
         Ok(())
     }
 
     fn store_reference(&mut self, cell: Cell) -> PyResult<()> {
         self.builder
             .checked_append_reference(cell.0.clone())
-            .handle_value_error()?;
+            .handle_cell_error()?;
         Ok(())
     }
 
     fn store_builder(&mut self, value: &CellBuilder) -> PyResult<()> {
         self.builder
             .append_builder(&value.builder)
-            .handle_value_error()?;
+            .handle_cell_error()?;
         Ok(())
     }
 
     fn store_slice(&mut self, value: &CellSlice) -> PyResult<()> {
         self.builder
             .append_builder(&ton_types::BuilderData::from_slice(&value.slice))
-            .handle_value_error()?;
+            .handle_cell_error()?;
         Ok(())
     }
 
@@ -2184,7 +2663,7 @@ impl CellBuilder {
             max_refs: self.builder.references_used(),
         }];
         let builder = ton_abi::TokenValue::pack_values_into_chain(&tokens, cells, &abi_version)
-            .handle_value_error()?;
+            .handle_cell_error()?;
         self.builder = builder;
 
         Ok(())
@@ -2198,6 +2677,28 @@ impl CellBuilder {
             self.is_exotic,
         )
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduceWithState> {
+        // NOTE: the builder is stored as an ordinary cell, exotic cells are built on demand
+        let data = self.builder.clone().into_cell().handle_cell_error()?;
+        let (new, args) = reduce_new::<Self>(py, ());
+        Ok((new, args, (Cell(data), self.is_exotic).into_py(py)))
+    }
+
+    fn __setstate__(&mut self, state: (Cell, bool)) -> PyResult<()> {
+        let (Cell(data), is_exotic) = state;
+        self.builder = ton_types::BuilderData::from_cell(&data).handle_cell_error()?;
+        self.is_exotic = is_exotic;
+        Ok(())
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
 }
 
 #[derive(Default, Copy, Clone)]
@@ -2351,6 +2852,18 @@ impl Tokens {
         self.to_string()
     }
 
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        reduce_with::<Self>(py, "from_nano", (self.0,))
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        self.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!("Tokens.from_nano({})", self.0)
     }
@@ -2407,13 +2920,27 @@ pub struct TransactionTree {
 }
 
 impl TransactionTree {
+    pub(crate) fn new(
+        py: Python<'_>,
+        root: Transaction,
+        children: Vec<Py<TransactionTree>>,
+    ) -> PyResult<Py<Self>> {
+        let mut tree = Self {
+            root: Py::new(py, root)?,
+            children_raw: children,
+            children: py_none(),
+        };
+        tree.finalize(py);
+        Py::new(py, tree)
+    }
+
     fn unpack(py: Python<'_>, mut slice: ton_types::SliceData) -> PyResult<Py<Self>> {
-        if !slice.get_next_bit().handle_value_error()? {
-            return Err(PyValueError::new_err("Invalid transaction tree node"));
+        if !slice.get_next_bit().handle_cell_error()? {
+            return Err(CellError::new_err("Invalid transaction tree node"));
         }
 
         let mut root = {
-            let cell = slice.checked_drain_reference().handle_value_error()?;
+            let cell = slice.checked_drain_reference().handle_cell_error()?;
             Self::unpack_only_root(py, cell)?
         };
 
@@ -2439,8 +2966,8 @@ impl TransactionTree {
         root: &mut TransactionTree,
     ) -> PyResult<()> {
         while let Ok(bit) = slice.get_next_bit() {
-            let cell = slice.checked_drain_reference().handle_runtime_error()?;
-            let slice = ton_types::SliceData::load_cell(cell).handle_runtime_error()?;
+            let cell = slice.checked_drain_reference().handle_cell_error()?;
+            let slice = ton_types::SliceData::load_cell(cell).handle_cell_error()?;
 
             if bit {
                 root.children_raw.push(Self::unpack(py, slice)?);
@@ -2454,17 +2981,78 @@ impl TransactionTree {
     fn finalize(&mut self, py: Python<'_>) {
         self.children = PyList::new(py, &self.children_raw).into_py(py);
     }
+
+    fn pack(&self, py: Python<'_>) -> PyResult<ton_types::Cell> {
+        let root = self
+            .root
+            .borrow(py)
+            .0
+            .data
+            .serialize()
+            .handle_cell_error()?;
+
+        let mut builder = ton_types::BuilderData::new();
+        builder.append_bit_one().handle_cell_error()?;
+        builder.checked_append_reference(root).handle_cell_error()?;
+        Self::pack_children(py, &self.children_raw, builder)
+    }
+
+    fn pack_children(
+        py: Python<'_>,
+        children: &[Py<TransactionTree>],
+        mut builder: ton_types::BuilderData,
+    ) -> PyResult<ton_types::Cell> {
+        // Keep the last reference for the continuation if not all children fit
+        let free = builder.references_free();
+        let (inline, rest) = if children.len() > free {
+            children.split_at(free - 1)
+        } else {
+            (children, &[][..])
+        };
+
+        for child in inline {
+            let child = child.borrow(py).pack(py)?;
+            builder.append_bit_one().handle_cell_error()?;
+            builder
+                .checked_append_reference(child)
+                .handle_cell_error()?;
+        }
+
+        if !rest.is_empty() {
+            let rest = Self::pack_children(py, rest, ton_types::BuilderData::new())?;
+            builder.append_bit_zero().handle_cell_error()?;
+            builder.checked_append_reference(rest).handle_cell_error()?;
+        }
+
+        builder.into_cell().handle_cell_error()
+    }
 }
 
 #[pymethods]
 impl TransactionTree {
     #[staticmethod]
     fn from_bytes(py: Python<'_>, mut bytes: &[u8]) -> PyResult<Py<Self>> {
-        let cell = ton_types::deserialize_tree_of_cells(&mut bytes).handle_runtime_error()?;
-        let slice = ton_types::SliceData::load_cell(cell).handle_runtime_error()?;
+        let cell = py
+            .allow_threads(|| ton_types::deserialize_tree_of_cells(&mut bytes))
+            .handle_cell_error()?;
+        let slice = ton_types::SliceData::load_cell(cell).handle_cell_error()?;
         TransactionTree::unpack(py, slice)
     }
 
+    fn to_bytes<'a>(&self, py: Python<'a>) -> PyResult<&'a PyBytes> {
+        let cell = self.pack(py)?;
+        let bytes = py
+            .allow_threads(|| ton_types::serialize_toc(&cell))
+            .handle_cell_error()?;
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    fn encode(&self, py: Python<'_>, encoding: Option<&str>) -> PyResult<String> {
+        let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
+        let cell = self.pack(py)?;
+        py.allow_threads(|| encoding.encode_cell(&cell))
+    }
+
     #[staticmethod]
     fn decode(py: Python<'_>, value: &str, encoding: Option<&str>) -> PyResult<Py<Self>> {
         let encoding = Encoding::from_optional_param(encoding, Encoding::Base64)?;
@@ -2487,6 +3075,26 @@ impl TransactionTree {
         queue.push_front(Py::from(slf));
         Py::new(py, TransactionTreeIter { queue })
     }
+
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<PyReduce> {
+        let bytes = self.to_bytes(py)?;
+        reduce_with::<Self>(py, "from_bytes", (bytes,))
+    }
+
+    fn __copy__(&self, py: Python<'_>) -> PyResult<Py<Self>> {
+        let root = self.root.borrow(py).clone();
+        TransactionTree::new(py, root, self.children_raw.clone())
+    }
+
+    fn __deepcopy__(&self, py: Python<'_>, memo: &PyAny) -> PyResult<Py<Self>> {
+        let root = self.root.borrow(py).clone();
+        let children = self
+            .children_raw
+            .iter()
+            .map(|child| child.borrow(py).__deepcopy__(py, memo))
+            .collect::<PyResult<Vec<_>>>()?;
+        TransactionTree::new(py, root, children)
+    }
 }
 
 #[pyclass]
@@ -2515,3 +3123,9 @@ impl TransactionTreeIter {
         node
     }
 }
+
+fn write_to_bytes<'a, T: Serializable>(py: Python<'a>, value: &T) -> PyResult<&'a PyBytes> {
+    let cell = value.serialize().handle_cell_error()?;
+    let bytes = ton_types::serialize_toc(&cell).handle_cell_error()?;
+    Ok(PyBytes::new(py, &bytes))
+}
@@ -1,38 +1,98 @@
 use std::collections::VecDeque;
+use std::future::Future;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 
+use futures_util::StreamExt;
 use nt::core::models;
-use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration, PyValueError};
+use pyo3::exceptions::{
+    PyConnectionError, PyRuntimeError, PyStopAsyncIteration, PyTimeoutError, PyValueError,
+};
 use pyo3::prelude::*;
 use pyo3::types::PyString;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{broadcast, oneshot, watch, Notify};
+use tokio::sync::{broadcast, mpsc, oneshot, watch, Notify};
 use tokio_util::sync::{CancellationToken, DropGuard};
-use ton_block::Deserializable;
+use ton_block::{Deserializable, Serializable};
 
-use crate::abi::SignedExternalMessage;
+use crate::abi::{
+    execute_ordinary_transaction, handle_execution_error, ExecutionParams, RootExecutionFailed,
+    SignedExternalMessage, UnsignedExternalMessage, TRANSACTION_LT_OFFSET,
+};
 use crate::crypto::SignatureContext;
-use crate::models::{AccountState, Address, BlockchainConfig, Message, Transaction};
+use crate::models::{
+    AccountState, Address, BlockchainConfig, Message, Tokens, Transaction, TransactionActionPhase,
+    TransactionComputePhase, TransactionTree,
+};
 use crate::util::*;
 
+mod block_walking;
+mod cache;
+mod custom;
+mod failover;
+mod local;
+mod recording;
+
 #[pyclass(subclass)]
 pub struct Transport(Arc<TransportState>);
 
 struct TransportState {
     clock: Clock,
     handle: TransportHandle,
+    /// Transport of the subscriptions which shares the cache.
+    subscription_handle: TransportHandle,
+    options: TransportOptions,
+    cache: Option<Arc<cache::TransportCache>>,
+    block_walker: Option<Arc<block_walking::BlockWalker>>,
+    subscription_options: parking_lot::RwLock<SubscriptionOptions>,
     subscriptions: Arc<tokio::sync::Mutex<SubscriptionsMap>>,
     _drop_guard: DropGuard,
 }
 
 impl TransportState {
-    fn new(clock: Clock, handle: TransportHandle) -> Arc<Self> {
+    fn new(
+        clock: Clock,
+        handle: TransportHandle,
+        options: TransportOptions,
+    ) -> PyResult<Arc<Self>> {
         let cancellation_token = CancellationToken::new();
 
+        let cache = options
+            .cache
+            .as_ref()
+            .map(|config| Arc::new(config.build()));
+        let block_walker = match (&handle, options.polling_mode) {
+            (_, block_walking::PollingMode::Polling) => None,
+            (TransportHandle::GraphQl(transport), block_walking::PollingMode::BlockWalking) => {
+                Some(Arc::new(block_walking::BlockWalker::new(transport.clone())))
+            }
+            (_, block_walking::PollingMode::BlockWalking) => {
+                return Err(PyValueError::new_err(
+                    "Block walking is only supported by the GQL transport",
+                ))
+            }
+        };
+
+        let subscription_handle = match &cache {
+            Some(cache) => TransportHandle::Cached(Arc::new(cache::CachedTransport::new(
+                handle.clone(),
+                cache.clone(),
+            ))),
+            None => handle.clone(),
+        };
+
         let shared = Arc::new(Self {
             clock,
             handle,
+            subscription_handle,
+            options,
+            cache,
+            block_walker,
+            subscription_options: Default::default(),
             subscriptions: Default::default(),
             _drop_guard: cancellation_token.clone().drop_guard(),
         });
@@ -57,7 +117,410 @@ impl TransportState {
             }
         });
 
-        shared
+        Ok(shared)
+    }
+
+    async fn request<T, F, Fut>(
+        &self,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+        f: F,
+    ) -> PyResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let retry_policy = retry_policy
+            .or(self.options.retry_policy.as_ref())
+            .unwrap_or(&RetryPolicy::NONE);
+
+        let timeout = timeout.or(self.options.timeout);
+        with_timeout(timeout, async {
+            retry_policy.run(f).await.map_err(handle_transport_error)
+        })
+        .await
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<nt::transport::models::RawContractState> {
+        if let Some(state) = self
+            .cache
+            .as_ref()
+            .and_then(|c| c.get_contract_state(address))
+        {
+            return Ok(state);
+        }
+
+        let state = self
+            .request(retry_policy, timeout, || {
+                self.handle.as_ref().get_contract_state(address)
+            })
+            .await?;
+
+        if let Some(cache) = &self.cache {
+            cache.store_contract_state(address, &state);
+        }
+        Ok(state)
+    }
+
+    async fn get_transaction(
+        &self,
+        hash: &ton_types::UInt256,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Option<nt::transport::models::RawTransaction>> {
+        if let Some(tx) = self.cache.as_ref().and_then(|c| c.get_transaction(hash)) {
+            return Ok(Some(tx));
+        }
+
+        let tx = self
+            .request(retry_policy, timeout, || {
+                self.handle.as_ref().get_transaction(hash)
+            })
+            .await?;
+
+        if let (Some(cache), Some(tx)) = (&self.cache, &tx) {
+            cache.store_transaction(tx);
+        }
+        Ok(tx)
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Option<nt::transport::models::RawTransaction>> {
+        if let Some(tx) = self
+            .cache
+            .as_ref()
+            .and_then(|c| c.get_dst_transaction(message_hash))
+        {
+            return Ok(Some(tx));
+        }
+
+        let tx = self
+            .request(retry_policy, timeout, || {
+                self.handle.as_ref().get_dst_transaction(message_hash)
+            })
+            .await?;
+
+        // NOTE: only found transactions are cached because
+        // the message can still be processed later
+        if let (Some(cache), Some(tx)) = (&self.cache, &tx) {
+            cache.store_dst_transaction(message_hash, tx);
+        }
+        Ok(tx)
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        force: bool,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<ton_executor::BlockchainConfig> {
+        if !force {
+            if let Some(config) = self.cache.as_ref().and_then(|c| c.get_blockchain_config()) {
+                return Ok(config);
+            }
+        }
+
+        let config = self
+            .request(retry_policy, timeout, || {
+                self.handle
+                    .as_ref()
+                    .get_blockchain_config(self.clock.as_ref(), force)
+            })
+            .await?;
+
+        if let Some(cache) = &self.cache {
+            cache.store_blockchain_config(&config);
+        }
+        Ok(config)
+    }
+
+    async fn get_capabilities(
+        &self,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<nt::transport::models::NetworkCapabilities> {
+        self.request(retry_policy, timeout, || {
+            self.handle.as_ref().get_capabilities(self.clock.as_ref())
+        })
+        .await
+    }
+
+    async fn get_account_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Option<AccountState>> {
+        let state = self
+            .get_contract_state(address, retry_policy, timeout)
+            .await?;
+
+        Ok(match state {
+            nt::transport::models::RawContractState::NotExists { .. } => None,
+            nt::transport::models::RawContractState::Exists(state) => {
+                Some(AccountState(state.account))
+            }
+        })
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        continuation: Option<ton_block::MsgAddressInt>,
+        limit: Option<u8>,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Vec<Address>> {
+        const DEFAULT_LIMIT: u8 = 50;
+
+        let limit = limit.unwrap_or(DEFAULT_LIMIT);
+        let addresses = self
+            .request(retry_policy, timeout, || {
+                self.handle
+                    .as_ref()
+                    .get_accounts_by_code_hash(code_hash, limit, &continuation)
+            })
+            .await?;
+
+        Ok(addresses.into_iter().map(Address).collect())
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        lt: Option<u64>,
+        limit: Option<u8>,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Vec<Transaction>> {
+        const DEFAULT_LIMIT: u8 = 50;
+
+        let lt = lt.unwrap_or(u64::MAX);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+        let raw_transactions = self
+            .get_raw_transactions(address, lt, limit, retry_policy, timeout)
+            .await?;
+
+        raw_transactions
+            .into_iter()
+            .map(Transaction::try_from)
+            .collect()
+    }
+
+    /// Fetches a page of account transactions, stores them in the cache by hash.
+    async fn get_raw_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        lt: u64,
+        limit: u8,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Vec<nt::transport::models::RawTransaction>> {
+        let raw_transactions = self
+            .request(retry_policy, timeout, || {
+                self.handle.as_ref().get_transactions(address, lt, limit)
+            })
+            .await?;
+
+        if let Some(cache) = &self.cache {
+            raw_transactions
+                .iter()
+                .for_each(|tx| cache.store_transaction(tx));
+        }
+        Ok(raw_transactions)
+    }
+
+    async fn send_external_message(
+        &self,
+        message: ExternalMessage,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Option<Transaction>> {
+        let ExternalMessage {
+            message,
+            expire_at,
+            dst,
+        } = message;
+
+        // NOTE: transport-wide timeout is not used here because
+        // waiting for the transaction is already limited by `expire_at`
+        with_timeout(timeout, async {
+            self.check_expiration(expire_at)?;
+            let subscription = self.get_subscription(dst).await?;
+
+            // TODO: add watchdog timer to delay subscription drop
+
+            let retry_policy = retry_policy.or(self.options.retry_policy.as_ref());
+            subscription
+                .send_message(&message, expire_at, retry_policy)
+                .await
+        })
+        .await
+    }
+
+    async fn estimate_fees(
+        &self,
+        message: ton_block::Message,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<EstimatedFees> {
+        let dst = match message.dst() {
+            Some(dst) if message.is_inbound_external() => dst,
+            _ => return Err(PyValueError::new_err("Expected external inbound message")),
+        };
+
+        let (config, account) = tokio::try_join!(
+            self.get_blockchain_config(false, retry_policy, timeout),
+            self.get_account_state(&dst, retry_policy, timeout),
+        )?;
+
+        let (last_trans_lt, mut account) = match account {
+            None => (0, ton_block::Account::AccountNone),
+            Some(state) => (
+                state.0.storage.last_trans_lt,
+                ton_block::Account::Account(state.0),
+            ),
+        };
+
+        let params = ExecutionParams {
+            block_unixtime: nt::utils::Clock::now_sec_u64(self.clock.as_ref()) as u32,
+            block_lt: last_trans_lt + TRANSACTION_LT_OFFSET,
+            check_signature: false,
+            ..Default::default()
+        };
+
+        let transaction = tokio::task::spawn_blocking(move || {
+            execute_ordinary_transaction(&config, &message, &mut account, params)
+        })
+        .await
+        .map_err(|e| ExecutionError::new_err(e.to_string()))?
+        .map_err(handle_execution_error)?;
+
+        let transaction = Transaction::try_from(transaction.raw)?;
+        let out_msgs = transaction.get_out_msgs()?;
+        Ok(EstimatedFees {
+            transaction,
+            out_msgs,
+        })
+    }
+
+    async fn export_pending_messages(&self) -> PyResult<Vec<StoredPendingMessage>> {
+        let subscriptions = self
+            .subscriptions
+            .lock()
+            .await
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+
+        let mut result = Vec::new();
+        for subscription in subscriptions {
+            for entry in subscription.state.pending_messages.iter() {
+                let cell = entry.message.data.serialize().handle_cell_error()?;
+                result.push(StoredPendingMessage {
+                    hash: hex::encode(entry.key().as_slice()),
+                    expire_at: entry.expire_at,
+                    boc: Encoding::Base64.encode_cell(&cell)?,
+                });
+            }
+        }
+        Ok(result)
+    }
+
+    async fn resume_pending_message(
+        &self,
+        stored: StoredPendingMessage,
+        rebroadcast: bool,
+        retry_policy: Option<&RetryPolicy>,
+    ) -> PyResult<PendingMessage> {
+        let message = Message::try_from(Encoding::Base64.decode_cell(&stored.boc)?)?;
+        if hex::encode(message.hash.as_slice()) != stored.hash {
+            return Err(PyValueError::new_err("Pending message hash mismatch"));
+        }
+
+        let dst = match message.data.header() {
+            ton_block::CommonMsgInfo::ExtInMsgInfo(info) => info.dst.clone(),
+            _ => return Err(PyValueError::new_err("Expected external inbound message")),
+        };
+        let subscription = self.get_subscription(dst.clone()).await?;
+
+        // The message could have been delivered while the process was down
+        let result = if let Some(tx) = self
+            .get_dst_transaction(&message.hash, retry_policy, None)
+            .await?
+        {
+            PendingMessageResult::Received(Some(Transaction::try_from(tx)?))
+        } else if stored.expire_at as u64 <= self.clock.now_sec() {
+            PendingMessageResult::Received(None)
+        } else if rebroadcast {
+            let rx = subscription
+                .broadcast(&message, stored.expire_at, retry_policy)
+                .await?;
+            PendingMessageResult::Waiting(rx)
+        } else {
+            // NOTE: the message could have already reached the network, so it is
+            // not sent again. Only its transaction or expiration is awaited.
+            let rx = subscription.track(&message, stored.expire_at)?;
+            PendingMessageResult::Waiting(rx)
+        };
+
+        Ok(PendingMessage::new(
+            message.hash,
+            stored.expire_at,
+            dst,
+            subscription,
+            result,
+        ))
+    }
+
+    async fn send_external_message_nowait(
+        &self,
+        message: ExternalMessage,
+        retry_policy: Option<&RetryPolicy>,
+        timeout: Option<Duration>,
+    ) -> PyResult<PendingMessage> {
+        let ExternalMessage {
+            message,
+            expire_at,
+            dst,
+        } = message;
+
+        let timeout = timeout.or(self.options.timeout);
+        with_timeout(timeout, async {
+            self.check_expiration(expire_at)?;
+            let subscription = self.get_subscription(dst.clone()).await?;
+
+            let retry_policy = retry_policy.or(self.options.retry_policy.as_ref());
+            let rx = subscription
+                .broadcast(&message, expire_at, retry_policy)
+                .await?;
+
+            Ok(PendingMessage::new(
+                message.hash,
+                expire_at,
+                dst,
+                subscription,
+                PendingMessageResult::Waiting(rx),
+            ))
+        })
+        .await
+    }
+
+    fn check_expiration(&self, expire_at: u32) -> PyResult<()> {
+        if expire_at as u64 <= self.clock.now_sec() {
+            return Err(MessageExpired::new_err("Message expired"));
+        }
+        Ok(())
     }
 
     async fn get_subscription(
@@ -68,6 +531,8 @@ impl TransportState {
 
         log::debug!("Requesting subscription for {address}");
 
+        let options = self.subscription_options.read().clone();
+
         let mut subscriptions = self.subscriptions.lock().await;
         let subscription = match subscriptions.entry(address.clone()) {
             hash_map::Entry::Occupied(mut entry) => {
@@ -78,21 +543,26 @@ impl TransportState {
                     log::debug!("Recreating subscription for {address}");
                     let subscription = SharedSubscription::subscribe(
                         self.clock.clone(),
-                        self.handle.clone(),
+                        self.subscription_handle.clone(),
+                        self.block_walker.clone(),
+                        &options,
                         address,
                     )
-                    .await
-                    .handle_runtime_error()?;
+                    .await?;
                     entry.insert(Arc::downgrade(&subscription));
                     subscription
                 }
             }
             hash_map::Entry::Vacant(entry) => {
                 log::debug!("Creating subscription for {address}");
-                let subscription =
-                    SharedSubscription::subscribe(self.clock.clone(), self.handle.clone(), address)
-                        .await
-                        .handle_runtime_error()?;
+                let subscription = SharedSubscription::subscribe(
+                    self.clock.clone(),
+                    self.subscription_handle.clone(),
+                    self.block_walker.clone(),
+                    &options,
+                    address,
+                )
+                .await?;
                 entry.insert(Arc::downgrade(&subscription));
                 subscription
             }
@@ -104,6 +574,84 @@ impl TransportState {
 
 type SubscriptionsMap = FastHashMap<ton_block::MsgAddressInt, Weak<SharedSubscription>>;
 
+#[derive(Default, Clone)]
+struct TransportOptions {
+    retry_policy: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    cache: Option<CacheConfig>,
+    polling_mode: block_walking::PollingMode,
+}
+
+async fn with_timeout<T, Fut>(timeout: Option<Duration>, fut: Fut) -> PyResult<T>
+where
+    Fut: Future<Output = PyResult<T>>,
+{
+    match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, fut).await {
+            Ok(result) => result,
+            Err(_) => Err(Timeout::new_err("Request timed out")),
+        },
+        None => fut.await,
+    }
+}
+
+/// Converts a failed transport request into `TransportError`.
+///
+/// NOTE: the retriable flag only depends on the error kind,
+/// so it is the same regardless of the retry policy used.
+fn handle_transport_error(error: anyhow::Error) -> PyErr {
+    // Messages sent to the local transport are executed right away
+    if error.is::<RootExecutionFailed>() {
+        return handle_execution_error(error);
+    }
+
+    let endpoint = error
+        .downcast_ref::<failover::FailedEndpoint>()
+        .map(|e| e.url.clone());
+    let retriable = is_transport_level_error(&error);
+    transport_error(&error, endpoint, retriable)
+}
+
+/// Whether the request failed because of the network or the endpoint state
+/// rather than being rejected by the endpoint.
+fn is_transport_level_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|e| {
+        if let Some(e) = e.downcast_ref::<reqwest::Error>() {
+            return e.is_connect()
+                || e.is_timeout()
+                || matches!(e.status(), Some(status) if status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS);
+        }
+        if let Some(e) = e.downcast_ref::<PyErr>() {
+            // Errors raised by the custom transport handler
+            return Python::with_gil(|py| {
+                e.is_instance_of::<PyConnectionError>(py) || e.is_instance_of::<PyTimeoutError>(py)
+            });
+        }
+        e.is::<std::io::Error>() || e.is::<tokio::time::error::Elapsed>()
+    })
+}
+
+/// Whether the request has not reached the endpoint.
+fn is_connection_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|e| {
+        if let Some(e) = e.downcast_ref::<reqwest::Error>() {
+            return e.is_connect();
+        }
+        if let Some(e) = e.downcast_ref::<PyErr>() {
+            return Python::with_gil(|py| e.is_instance_of::<PyConnectionError>(py));
+        }
+        false
+    })
+}
+
+fn parse_timeout(timeout: Option<f64>) -> PyResult<Option<Duration>> {
+    timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .handle_value_error()
+}
+
 #[pymethods]
 impl Transport {
     #[getter]
@@ -111,6 +659,26 @@ impl Transport {
         self.0.clock.clone()
     }
 
+    #[getter]
+    pub fn get_subscription_options(&self) -> SubscriptionOptions {
+        self.0.subscription_options.read().clone()
+    }
+
+    /// Options are applied to subscriptions created after the update.
+    #[setter]
+    pub fn set_subscription_options(&self, options: SubscriptionOptions) {
+        *self.0.subscription_options.write() = options;
+    }
+
+    #[getter]
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        let cache = self.0.cache.as_ref()?;
+        Some(CacheStats {
+            hits: cache.hits(),
+            misses: cache.misses(),
+        })
+    }
+
     pub fn check_connection<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
         let handle = self.0.handle.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
@@ -118,53 +686,142 @@ impl Transport {
         })
     }
 
+    #[getter]
+    pub fn sync(&self) -> SyncTransport {
+        SyncTransport(self.0.clone())
+    }
+
     pub fn send_external_message<'a>(
         &self,
         py: Python<'a>,
         message: PyRef<'a, SignedExternalMessage>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
     ) -> PyResult<&'a PyAny> {
-        let expire_at = message.expire_at;
-        let message = message.into_super().clone();
+        let timeout = parse_timeout(timeout)?;
+        let message = ExternalMessage::new(message)?;
 
-        let dst = {
-            let ton_block::CommonMsgInfo::ExtInMsgInfo(info) = message.data.header() else {
-                return Err(PyValueError::new_err("Expected external outbound message"));
-            };
-            info.dst.clone()
-        };
+        let shared = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            shared
+                .send_external_message(message, retry_policy.as_ref(), timeout)
+                .await
+        })
+    }
+
+    pub fn send_external_message_nowait<'a>(
+        &self,
+        py: Python<'a>,
+        message: PyRef<'a, SignedExternalMessage>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
+        let message = ExternalMessage::new(message)?;
+
+        let shared = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            shared
+                .send_external_message_nowait(message, retry_policy.as_ref(), timeout)
+                .await
+        })
+    }
+
+    /// Emulates the message on top of the latest destination account state.
+    pub fn estimate_fees<'a>(
+        &self,
+        py: Python<'a>,
+        message: FeesEstimationMessage<'a>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
+        let message = message.into_message(py)?;
 
         let shared = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let subscription = shared.get_subscription(dst).await?;
+            shared
+                .estimate_fees(message, retry_policy.as_ref(), timeout)
+                .await
+        })
+    }
 
-            // TODO: add watchdog timer to delay subscription drop
+    /// Returns all messages which are still waiting for the transaction as a JSON string.
+    pub fn export_pending_messages<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let shared = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let messages = shared.export_pending_messages().await?;
+            serde_json::to_string(&messages).handle_value_error()
+        })
+    }
 
-            subscription.send_message(&message, expire_at).await
+    /// Resumes tracking of the exported pending messages.
+    ///
+    /// Returns a list with either a pending message or an exception for each entry.
+    pub fn import_pending_messages<'a>(
+        &self,
+        py: Python<'a>,
+        data: &str,
+        rebroadcast: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+    ) -> PyResult<&'a PyAny> {
+        let messages =
+            serde_json::from_str::<Vec<StoredPendingMessage>>(data).handle_value_error()?;
+        let rebroadcast = rebroadcast.unwrap_or_default();
+
+        let shared = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let retry_policy = retry_policy.or_else(|| shared.options.retry_policy.clone());
+
+            let mut result = Vec::with_capacity(messages.len());
+            for stored in messages {
+                result.push(
+                    shared
+                        .resume_pending_message(stored, rebroadcast, retry_policy.as_ref())
+                        .await,
+                );
+            }
+
+            Ok(Python::with_gil(|py| {
+                result
+                    .into_iter()
+                    .map(|item| match item {
+                        Ok(message) => message.into_py(py),
+                        Err(e) => e.into_value(py).into_py(py),
+                    })
+                    .collect::<Vec<PyObject>>()
+            }))
         })
     }
 
-    pub fn get_signature_id<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+    pub fn get_signature_id<'a>(
+        &self,
+        py: Python<'a>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
         let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let capabilities = state
-                .handle
-                .as_ref()
-                .get_capabilities(state.clock.as_ref())
-                .await
-                .handle_runtime_error()?;
+                .get_capabilities(retry_policy.as_ref(), timeout)
+                .await?;
             Ok(capabilities.signature_id())
         })
     }
 
-    pub fn get_signature_context<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+    pub fn get_signature_context<'a>(
+        &self,
+        py: Python<'a>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
         let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let capabilities = state
-                .handle
-                .as_ref()
-                .get_capabilities(state.clock.as_ref())
-                .await
-                .handle_runtime_error()?;
+                .get_capabilities(retry_policy.as_ref(), timeout)
+                .await?;
             Ok(SignatureContext {
                 global_id: capabilities.global_id,
                 capabilities: capabilities.raw,
@@ -176,36 +833,34 @@ impl Transport {
         &self,
         py: Python<'a>,
         force: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
     ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
         let force = force.unwrap_or_default();
         let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
             let config = state
-                .handle
-                .as_ref()
-                .get_blockchain_config(state.clock.as_ref(), force)
-                .await
-                .handle_runtime_error()?;
+                .get_blockchain_config(force, retry_policy.as_ref(), timeout)
+                .await?;
 
             Ok(BlockchainConfig::from(config))
         })
     }
 
-    pub fn get_account_state<'a>(&self, py: Python<'a>, address: Address) -> PyResult<&'a PyAny> {
-        let handle = self.0.handle.clone();
+    pub fn get_account_state<'a>(
+        &self,
+        py: Python<'a>,
+        address: Address,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
+        let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let state = handle
-                .as_ref()
-                .get_contract_state(&address.0)
+            state
+                .get_account_state(&address.0, retry_policy.as_ref(), timeout)
                 .await
-                .handle_runtime_error()?;
-
-            Ok(match state {
-                nt::transport::models::RawContractState::NotExists { .. } => None,
-                nt::transport::models::RawContractState::Exists(state) => {
-                    Some(AccountState(state.account))
-                }
-            })
         })
     }
 
@@ -215,24 +870,24 @@ impl Transport {
         code_hash: &[u8],
         continuation: Option<Address>,
         limit: Option<u8>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
     ) -> PyResult<&'a PyAny> {
-        const DEFAULT_LIMIT: u8 = 50;
-
+        let timeout = parse_timeout(timeout)?;
         let code_hash = ton_types::UInt256::from_bytes(code_hash, "code hash")?;
+        let continuation = continuation.map(|Address(addr)| addr);
 
-        let handle = self.0.handle.clone();
+        let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let addresses = handle
-                .as_ref()
+            state
                 .get_accounts_by_code_hash(
                     &code_hash,
-                    limit.unwrap_or(DEFAULT_LIMIT),
-                    &continuation.map(|Address(addr)| addr),
+                    continuation,
+                    limit,
+                    retry_policy.as_ref(),
+                    timeout,
                 )
                 .await
-                .handle_runtime_error()?;
-
-            Ok(addresses.into_iter().map(Address).collect::<Vec<_>>())
         })
     }
 
@@ -240,21 +895,19 @@ impl Transport {
         &self,
         py: Python<'a>,
         transaction_hash: &[u8],
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
     ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
         let transaction_hash =
             ton_types::UInt256::from_bytes(transaction_hash, "transaction hash")?;
 
-        let handle = self.0.handle.clone();
+        let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            match handle
-                .as_ref()
-                .get_transaction(&transaction_hash)
-                .await
-                .handle_runtime_error()?
-            {
-                None => Ok(None),
-                Some(tx) => Transaction::try_from(tx).map(Some),
-            }
+            let tx = state
+                .get_transaction(&transaction_hash, retry_policy.as_ref(), timeout)
+                .await?;
+            tx.map(Transaction::try_from).transpose()
         })
     }
 
@@ -262,20 +915,18 @@ impl Transport {
         &self,
         py: Python<'a>,
         message_hash: MessageOrHash<'a>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
     ) -> PyResult<&'a PyAny> {
+        let timeout = parse_timeout(timeout)?;
         let message_hash = message_hash.try_into()?;
 
-        let handle = self.0.handle.clone();
+        let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            match handle
-                .as_ref()
-                .get_dst_transaction(&message_hash)
-                .await
-                .handle_runtime_error()?
-            {
-                None => Ok(None),
-                Some(tx) => Transaction::try_from(tx).map(Some),
-            }
+            let tx = state
+                .get_dst_transaction(&message_hash, retry_policy.as_ref(), timeout)
+                .await?;
+            tx.map(Transaction::try_from).transpose()
         })
     }
 
@@ -285,28 +936,63 @@ impl Transport {
         address: Address,
         lt: Option<u64>,
         limit: Option<u8>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
     ) -> PyResult<&'a PyAny> {
-        const DEFAULT_LIMIT: u8 = 50;
-
-        let handle = self.0.handle.clone();
+        let timeout = parse_timeout(timeout)?;
+        let state = self.0.clone();
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            let raw_transactions = handle
-                .as_ref()
-                .get_transactions(
-                    &address.0,
-                    lt.unwrap_or(u64::MAX),
-                    limit.unwrap_or(DEFAULT_LIMIT),
-                )
+            state
+                .get_transactions(&address.0, lt, limit, retry_policy.as_ref(), timeout)
                 .await
-                .handle_runtime_error()?;
-
-            raw_transactions
-                .into_iter()
-                .map(Transaction::try_from)
-                .collect::<PyResult<Vec<_>>>()
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn iter_transactions(
+        &self,
+        address: Address,
+        from_lt: Option<u64>,
+        to_lt: Option<u64>,
+        since: Option<u32>,
+        until: Option<u32>,
+        order: Option<&str>,
+        limit: Option<usize>,
+        prefetch: Option<usize>,
+    ) -> PyResult<TransactionsIter> {
+        let state = TransactionsIterParams {
+            from_lt,
+            to_lt,
+            since,
+            until,
+            order,
+            limit,
+            prefetch,
+        }
+        .build(&self.0, address)?;
+        Ok(TransactionsIter(Arc::new(tokio::sync::Mutex::new(state))))
+    }
+
+    pub fn iter_accounts_by_code_hash(
+        &self,
+        code_hash: &[u8],
+        with_states: Option<bool>,
+        concurrency: Option<usize>,
+        page_size: Option<u8>,
+        prefetch: Option<usize>,
+    ) -> PyResult<AccountsByCodeHashIter> {
+        let state = AccountsByCodeHashIterParams {
+            with_states,
+            concurrency,
+            page_size,
+            prefetch,
+        }
+        .build(&self.0, code_hash)?;
+        Ok(AccountsByCodeHashIter(Arc::new(tokio::sync::Mutex::new(
+            state,
+        ))))
+    }
+
     pub fn account_states(&self, address: Address) -> AccountStatesAsyncIter {
         AccountStatesAsyncIter(Arc::new(tokio::sync::Mutex::new(
             AccountStatesAsyncIterState::Uninit {
@@ -325,36 +1011,428 @@ impl Transport {
         )))
     }
 
+    pub fn watch_accounts(&self, addresses: Vec<Address>) -> AccountsWatcher {
+        let capacity = self.0.subscription_options.read().capacity;
+        let (items_tx, items_rx) = mpsc::channel(capacity);
+
+        AccountsWatcher(Arc::new(AccountsWatcherState {
+            transport: self.0.clone(),
+            initial: parking_lot::Mutex::new(Some(
+                addresses.into_iter().map(|Address(addr)| addr).collect(),
+            )),
+            accounts: Default::default(),
+            items_tx,
+            items_rx: tokio::sync::Mutex::new(items_rx),
+            cancellation_token: Default::default(),
+        }))
+    }
+
     pub fn trace_transaction<'a>(
         &self,
         py: Python<'a>,
         transaction_hash: TransactionOrHash<'a>,
         yield_root: Option<bool>,
+        timeout: Option<f64>,
+        max_depth: Option<usize>,
+        max_count: Option<usize>,
     ) -> PyResult<TraceTransaction> {
+        const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+        if max_count == Some(0) {
+            return Err(PyValueError::new_err("max_count must be greater than 0"));
+        }
+
         let yield_root = yield_root.unwrap_or_default();
-        let mut queue = Default::default();
-        let root_hash;
-        let root = match &transaction_hash {
+        let timeout = parse_timeout(timeout)?.unwrap_or(DEFAULT_TIMEOUT);
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        let mut state = TraceTransactionState {
+            transport: self.0.clone(),
+            yield_root,
+            root_hash: None,
+            root: None,
+            queue: Default::default(),
+            deadline,
+            max_depth,
+            max_count,
+            truncated: Default::default(),
+            observed: Vec::new(),
+        };
+
+        match &transaction_hash {
             TransactionOrHash::Transaction(tx) => {
-                TraceTransactionState::extract_messages(&tx.0.data, &mut queue)?;
-                root_hash = Some(tx.0.hash);
-                yield_root.then(|| tx.into_py(py))
+                let raw = nt::transport::models::RawTransaction {
+                    hash: tx.0.hash,
+                    data: tx.0.data.clone(),
+                };
+                state.observe(None, 0, &raw)?;
+                state.root = yield_root.then(|| tx.into_py(py));
             }
             TransactionOrHash::Hash(bytes) => {
-                root_hash = Some(ton_types::UInt256::from_bytes(bytes, "transaction hash")?);
-                None
+                state.root_hash = Some(ton_types::UInt256::from_bytes(bytes, "transaction hash")?);
             }
+        }
+
+        Ok(TraceTransaction {
+            truncated: state.truncated.clone(),
+            state: Arc::new(tokio::sync::Mutex::new(state)),
+        })
+    }
+}
+
+struct ExternalMessage {
+    message: Message,
+    expire_at: u32,
+    dst: ton_block::MsgAddressInt,
+}
+
+impl ExternalMessage {
+    fn new(message: PyRef<'_, SignedExternalMessage>) -> PyResult<Self> {
+        let expire_at = message.expire_at;
+        let message = message.into_super().clone();
+
+        let dst = {
+            let ton_block::CommonMsgInfo::ExtInMsgInfo(info) = message.data.header() else {
+                return Err(PyValueError::new_err("Expected external outbound message"));
+            };
+            info.dst.clone()
         };
 
-        Ok(TraceTransaction(Arc::new(tokio::sync::Mutex::new(
-            TraceTransactionState {
-                transport: self.0.clone(),
-                yield_root,
-                root_hash,
-                root,
-                queue,
-            },
-        ))))
+        Ok(Self {
+            message,
+            expire_at,
+            dst,
+        })
+    }
+}
+
+/// Blocking versions of the transport methods.
+///
+/// Requests are executed on the shared runtime while the GIL is released.
+#[pyclass]
+pub struct SyncTransport(Arc<TransportState>);
+
+#[pymethods]
+impl SyncTransport {
+    pub fn check_connection(&self, py: Python<'_>) -> PyResult<()> {
+        block_on(py, self.0.handle.check_local_node_connection())
+    }
+
+    pub fn send_external_message(
+        &self,
+        py: Python<'_>,
+        message: PyRef<'_, SignedExternalMessage>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Option<Transaction>> {
+        let timeout = parse_timeout(timeout)?;
+        let message = ExternalMessage::new(message)?;
+        block_on(
+            py,
+            self.0
+                .send_external_message(message, retry_policy.as_ref(), timeout),
+        )
+    }
+
+    pub fn send_external_message_nowait(
+        &self,
+        py: Python<'_>,
+        message: PyRef<'_, SignedExternalMessage>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<PendingMessage> {
+        let timeout = parse_timeout(timeout)?;
+        let message = ExternalMessage::new(message)?;
+        block_on(
+            py,
+            self.0
+                .send_external_message_nowait(message, retry_policy.as_ref(), timeout),
+        )
+    }
+
+    /// Waits until the transaction is found or the message expires.
+    pub fn wait_pending_message(
+        &self,
+        py: Python<'_>,
+        message: &PendingMessage,
+    ) -> PyResult<Option<Transaction>> {
+        block_on(py, message.wait_impl())
+    }
+
+    pub fn get_signature_id(
+        &self,
+        py: Python<'_>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Option<i32>> {
+        let timeout = parse_timeout(timeout)?;
+        let capabilities = block_on(py, self.0.get_capabilities(retry_policy.as_ref(), timeout))?;
+        Ok(capabilities.signature_id())
+    }
+
+    pub fn get_signature_context(
+        &self,
+        py: Python<'_>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<SignatureContext> {
+        let timeout = parse_timeout(timeout)?;
+        let capabilities = block_on(py, self.0.get_capabilities(retry_policy.as_ref(), timeout))?;
+        Ok(SignatureContext {
+            global_id: capabilities.global_id,
+            capabilities: capabilities.raw,
+        })
+    }
+
+    pub fn estimate_fees(
+        &self,
+        py: Python<'_>,
+        message: FeesEstimationMessage<'_>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<EstimatedFees> {
+        let timeout = parse_timeout(timeout)?;
+        let message = message.into_message(py)?;
+        block_on(
+            py,
+            self.0
+                .estimate_fees(message, retry_policy.as_ref(), timeout),
+        )
+    }
+
+    pub fn get_blockchain_config(
+        &self,
+        py: Python<'_>,
+        force: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<BlockchainConfig> {
+        let timeout = parse_timeout(timeout)?;
+        let force = force.unwrap_or_default();
+        let config = block_on(
+            py,
+            self.0
+                .get_blockchain_config(force, retry_policy.as_ref(), timeout),
+        )?;
+        Ok(BlockchainConfig::from(config))
+    }
+
+    pub fn get_account_state(
+        &self,
+        py: Python<'_>,
+        address: Address,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Option<AccountState>> {
+        let timeout = parse_timeout(timeout)?;
+        block_on(
+            py,
+            self.0
+                .get_account_state(&address.0, retry_policy.as_ref(), timeout),
+        )
+    }
+
+    pub fn get_accounts_by_code_hash(
+        &self,
+        py: Python<'_>,
+        code_hash: &[u8],
+        continuation: Option<Address>,
+        limit: Option<u8>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Vec<Address>> {
+        let timeout = parse_timeout(timeout)?;
+        let code_hash = ton_types::UInt256::from_bytes(code_hash, "code hash")?;
+        let continuation = continuation.map(|Address(addr)| addr);
+        block_on(
+            py,
+            self.0.get_accounts_by_code_hash(
+                &code_hash,
+                continuation,
+                limit,
+                retry_policy.as_ref(),
+                timeout,
+            ),
+        )
+    }
+
+    pub fn get_transaction(
+        &self,
+        py: Python<'_>,
+        transaction_hash: &[u8],
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Option<Transaction>> {
+        let timeout = parse_timeout(timeout)?;
+        let transaction_hash =
+            ton_types::UInt256::from_bytes(transaction_hash, "transaction hash")?;
+        let tx = block_on(
+            py,
+            self.0
+                .get_transaction(&transaction_hash, retry_policy.as_ref(), timeout),
+        )?;
+        tx.map(Transaction::try_from).transpose()
+    }
+
+    pub fn get_dst_transaction(
+        &self,
+        py: Python<'_>,
+        message_hash: MessageOrHash<'_>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Option<Transaction>> {
+        let timeout = parse_timeout(timeout)?;
+        let message_hash = message_hash.try_into()?;
+        let tx = block_on(
+            py,
+            self.0
+                .get_dst_transaction(&message_hash, retry_policy.as_ref(), timeout),
+        )?;
+        tx.map(Transaction::try_from).transpose()
+    }
+
+    pub fn get_transactions(
+        &self,
+        py: Python<'_>,
+        address: Address,
+        lt: Option<u64>,
+        limit: Option<u8>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<Vec<Transaction>> {
+        let timeout = parse_timeout(timeout)?;
+        block_on(
+            py,
+            self.0
+                .get_transactions(&address.0, lt, limit, retry_policy.as_ref(), timeout),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn iter_transactions(
+        &self,
+        address: Address,
+        from_lt: Option<u64>,
+        to_lt: Option<u64>,
+        since: Option<u32>,
+        until: Option<u32>,
+        order: Option<&str>,
+        limit: Option<usize>,
+        prefetch: Option<usize>,
+    ) -> PyResult<BlockingIter> {
+        let state = TransactionsIterParams {
+            from_lt,
+            to_lt,
+            since,
+            until,
+            order,
+            limit,
+            prefetch,
+        }
+        .build(&self.0, address)?;
+        Ok(BlockingIter(Box::new(state)))
+    }
+
+    pub fn iter_accounts_by_code_hash(
+        &self,
+        code_hash: &[u8],
+        with_states: Option<bool>,
+        concurrency: Option<usize>,
+        page_size: Option<u8>,
+        prefetch: Option<usize>,
+    ) -> PyResult<BlockingIter> {
+        let state = AccountsByCodeHashIterParams {
+            with_states,
+            concurrency,
+            page_size,
+            prefetch,
+        }
+        .build(&self.0, code_hash)?;
+        Ok(BlockingIter(Box::new(state)))
+    }
+}
+
+/// Blocking iterator over the pages produced in background.
+#[pyclass]
+pub struct BlockingIter(Box<dyn BlockingPages>);
+
+trait BlockingPages: Send {
+    fn next_blocking(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>>;
+    fn close_blocking(&mut self);
+}
+
+impl<T> BlockingPages for PagedIterState<T>
+where
+    T: IntoPy<PyObject> + Send,
+{
+    fn next_blocking(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let item = block_on(py, self.next())?;
+        Ok(item.map(|item| item.into_py(py)))
+    }
+
+    fn close_blocking(&mut self) {
+        self.close();
+    }
+}
+
+#[pymethods]
+impl BlockingIter {
+    fn close(&mut self) {
+        self.0.close_blocking();
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __exit__(&mut self, _exc_type: &PyAny, _exc_value: &PyAny, _traceback: &PyAny) {
+        self.close();
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.0.next_blocking(py)
+    }
+}
+
+/// Runs the future on the shared runtime with the GIL released.
+///
+/// NOTE: blocking inside a running event loop would stall all its tasks
+/// (and panics on a runtime thread), so it is rejected instead.
+fn block_on<T, Fut>(py: Python<'_>, fut: Fut) -> PyResult<T>
+where
+    T: Send,
+    Fut: Future<Output = PyResult<T>> + Send,
+{
+    if pyo3_asyncio::get_running_loop(py).is_ok() || tokio::runtime::Handle::try_current().is_ok() {
+        return Err(PyRuntimeError::new_err(
+            "SyncTransport can't be used inside a running event loop, use Transport instead",
+        ));
+    }
+    py.allow_threads(|| pyo3_asyncio::tokio::get_runtime().block_on(fut))
+}
+
+#[derive(FromPyObject)]
+pub enum FeesEstimationMessage<'a> {
+    #[pyo3(transparent, annotation = "Message")]
+    Message(PyRef<'a, Message>),
+    #[pyo3(transparent, annotation = "UnsignedExternalMessage")]
+    Unsigned(PyRef<'a, UnsignedExternalMessage>),
+}
+
+impl FeesEstimationMessage<'_> {
+    fn into_message(self, py: Python<'_>) -> PyResult<ton_block::Message> {
+        Ok(match self {
+            Self::Message(message) => message.data.clone(),
+            Self::Unsigned(message) => {
+                let signed = message.with_fake_signature(py)?;
+                let signed = signed.borrow(py);
+                signed.into_super().data.clone()
+            }
+        })
     }
 }
 
@@ -426,7 +1504,7 @@ impl GqlTransport {
                 long_query: false,
             })
             .await
-            .handle_runtime_error()?;
+            .map_err(handle_transport_error)?;
 
         T::extract(&response)
     }
@@ -481,9 +1559,16 @@ impl GqlTransport {
         endpoints: Vec<String>,
         clock: Option<Clock>,
         local: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+        cache: Option<CacheConfig>,
+        polling_mode: Option<&str>,
     ) -> PyResult<PyClassInitializer<Self>> {
         use nekoton_transport::gql::*;
 
+        let timeout = parse_timeout(timeout)?;
+        let polling_mode = block_walking::PollingMode::from_optional_param(polling_mode)?;
+
         let client = GqlClient::new(GqlNetworkSettings {
             endpoints,
             local: local.unwrap_or_default(),
@@ -498,9 +1583,15 @@ impl GqlTransport {
         let transport = Arc::new(nt::transport::gql::GqlTransport::new(client));
         let handle = TransportHandle::GraphQl(transport);
         let clock = clock.unwrap_or_default();
+        let options = TransportOptions {
+            retry_policy,
+            timeout,
+            cache,
+            polling_mode,
+        };
 
         Ok(
-            PyClassInitializer::from(Transport(TransportState::new(clock, handle)))
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
                 .add_subclass(gql),
         )
     }
@@ -541,10 +1632,10 @@ impl GqlTransport {
                     boc: &'a str,
                 }
 
-                let Response { errors, data } =
-                    serde_json::from_str(response).handle_runtime_error()?;
+                let Response { errors, data } = serde_json::from_str(response)
+                    .map_err(|e| TransportError::new_err(e.to_string()))?;
                 if let Some(errors) = errors {
-                    return Err(PyRuntimeError::new_err(
+                    return Err(TransportError::new_err(
                         serde_json::to_string_pretty(&errors).unwrap_or_default(),
                     ));
                 }
@@ -553,7 +1644,7 @@ impl GqlTransport {
                     transactions: Some(transactions),
                 }) = data
                 else {
-                    return Err(PyRuntimeError::new_err("Invalid response"));
+                    return Err(TransportError::new_err("Invalid response"));
                 };
 
                 transactions
@@ -608,10 +1699,10 @@ impl GqlTransport {
                     boc: &'a str,
                 }
 
-                let Response { errors, data } =
-                    serde_json::from_str(response).handle_runtime_error()?;
+                let Response { errors, data } = serde_json::from_str(response)
+                    .map_err(|e| TransportError::new_err(e.to_string()))?;
                 if let Some(errors) = errors {
-                    return Err(PyRuntimeError::new_err(
+                    return Err(TransportError::new_err(
                         serde_json::to_string_pretty(&errors).unwrap_or_default(),
                     ));
                 }
@@ -620,7 +1711,7 @@ impl GqlTransport {
                     messages: Some(messages),
                 }) = data
                 else {
-                    return Err(PyRuntimeError::new_err("Invalid response"));
+                    return Err(TransportError::new_err("Invalid response"));
                 };
 
                 messages
@@ -677,10 +1768,10 @@ impl GqlTransport {
                     id: &'a str,
                 }
 
-                let Response { errors, data } =
-                    serde_json::from_str(response).handle_runtime_error()?;
+                let Response { errors, data } = serde_json::from_str(response)
+                    .map_err(|e| TransportError::new_err(e.to_string()))?;
                 if let Some(errors) = errors {
-                    return Err(PyRuntimeError::new_err(
+                    return Err(TransportError::new_err(
                         serde_json::to_string_pretty(&errors).unwrap_or_default(),
                     ));
                 }
@@ -689,7 +1780,7 @@ impl GqlTransport {
                     accounts: Some(accounts),
                 }) = data
                 else {
-                    return Err(PyRuntimeError::new_err("Invalid response"));
+                    return Err(TransportError::new_err("Invalid response"));
                 };
 
                 accounts
@@ -700,7 +1791,7 @@ impl GqlTransport {
                             Some(boc) => {
                                 let cell = Encoding::Base64.decode_cell(boc)?;
                                 match ton_block::Account::construct_from_cell(cell)
-                                    .handle_runtime_error()?
+                                    .handle_cell_error()?
                                 {
                                     ton_block::Account::Account(state) => Some(AccountState(state)),
                                     ton_block::Account::AccountNone => None,
@@ -783,82 +1874,488 @@ impl GqlExprPart {
     }
 }
 
-#[derive(Copy, Clone)]
+/// Builds a transport over the single `endpoint` or over the list of `endpoints`.
+///
+/// Only several endpoints are wrapped into the [`failover::FailoverTransport`].
+fn build_endpoints_transport<F>(
+    endpoint: Option<String>,
+    endpoints: Option<Vec<String>>,
+    strategy: Option<&str>,
+    health_check_interval: Option<f64>,
+    f: F,
+) -> PyResult<(TransportHandle, Endpoints)>
+where
+    F: Fn(&str) -> PyResult<TransportHandle>,
+{
+    let strategy = failover::SelectionStrategy::from_optional_param(strategy)?;
+    let health_check_interval = match parse_timeout(health_check_interval)? {
+        Some(interval) if interval.is_zero() => {
+            return Err(PyValueError::new_err(
+                "Health check interval must be positive",
+            ))
+        }
+        interval => interval.unwrap_or(Duration::from_secs(10)),
+    };
+
+    let endpoints = match (endpoint, endpoints) {
+        (Some(endpoint), None) => vec![endpoint],
+        (None, Some(endpoints)) => endpoints,
+        (Some(_), Some(_)) => {
+            return Err(PyValueError::new_err(
+                "Either `endpoint` or `endpoints` must be specified, not both",
+            ))
+        }
+        (None, None) => return Err(PyValueError::new_err("No endpoints specified")),
+    };
+    if endpoints.is_empty() {
+        return Err(PyValueError::new_err("No endpoints specified"));
+    }
+
+    if let [endpoint] = endpoints.as_slice() {
+        let handle = f(endpoint)?;
+        return Ok((handle, Endpoints::Single(endpoint.clone())));
+    }
+
+    let endpoints = endpoints
+        .into_iter()
+        .map(|endpoint| {
+            let handle = f(&endpoint)?;
+            Ok((endpoint, handle))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let transport = Arc::new(failover::FailoverTransport::new(endpoints, strategy));
+    transport.spawn_health_checks(health_check_interval);
+    Ok((
+        TransportHandle::Failover(transport.clone()),
+        Endpoints::Failover(transport),
+    ))
+}
+
+/// Endpoints of the [`JrpcTransport`] or [`ProtoTransport`].
+#[derive(Clone)]
+enum Endpoints {
+    Single(String),
+    Failover(Arc<failover::FailoverTransport>),
+}
+
+impl Endpoints {
+    fn list(&self) -> Vec<String> {
+        match self {
+            Self::Single(endpoint) => vec![endpoint.clone()],
+            Self::Failover(transport) => transport.endpoints(),
+        }
+    }
+
+    fn active(&self) -> String {
+        match self {
+            Self::Single(endpoint) => endpoint.clone(),
+            Self::Failover(transport) => transport.active_endpoint(),
+        }
+    }
+}
+
+#[derive(Clone)]
 #[pyclass(subclass, extends = Transport)]
-pub struct JrpcTransport;
+pub struct JrpcTransport(Endpoints);
 
 #[pymethods]
 impl JrpcTransport {
     #[new]
-    fn new(endpoint: &str, clock: Option<Clock>) -> PyResult<PyClassInitializer<Self>> {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        endpoint: Option<String>,
+        clock: Option<Clock>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+        strategy: Option<&str>,
+        health_check_interval: Option<f64>,
+        cache: Option<CacheConfig>,
+        endpoints: Option<Vec<String>>,
+    ) -> PyResult<PyClassInitializer<Self>> {
         use nekoton_transport::jrpc::JrpcClient;
 
-        let client = JrpcClient::new(endpoint).handle_value_error()?;
+        let timeout = parse_timeout(timeout)?;
 
-        let transport = Arc::new(nt::transport::jrpc::JrpcTransport::new(client));
-        let handle = TransportHandle::Jrpc(transport);
+        let (handle, endpoints) = build_endpoints_transport(
+            endpoint,
+            endpoints,
+            strategy,
+            health_check_interval,
+            |endpoint| {
+                let client = JrpcClient::new(endpoint).handle_value_error()?;
+                Ok(TransportHandle::Jrpc(Arc::new(
+                    nt::transport::jrpc::JrpcTransport::new(client),
+                )))
+            },
+        )?;
         let clock = clock.unwrap_or_default();
+        let options = TransportOptions {
+            retry_policy,
+            timeout,
+            cache,
+            ..Default::default()
+        };
 
         Ok(
-            PyClassInitializer::from(Transport(TransportState::new(clock, handle)))
-                .add_subclass(Self),
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
+                .add_subclass(Self(endpoints)),
         )
     }
+
+    #[getter]
+    fn endpoints(&self) -> Vec<String> {
+        self.0.list()
+    }
+
+    #[getter]
+    fn active_endpoint(&self) -> String {
+        self.0.active()
+    }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 #[pyclass(subclass, extends = Transport)]
-pub struct ProtoTransport;
+pub struct ProtoTransport(Endpoints);
 
 #[pymethods]
 impl ProtoTransport {
     #[new]
-    fn new(endpoint: &str, clock: Option<Clock>) -> PyResult<PyClassInitializer<Self>> {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        endpoint: Option<String>,
+        clock: Option<Clock>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+        strategy: Option<&str>,
+        health_check_interval: Option<f64>,
+        cache: Option<CacheConfig>,
+        endpoints: Option<Vec<String>>,
+    ) -> PyResult<PyClassInitializer<Self>> {
         use nekoton_transport::proto::ProtoClient;
 
-        let client = ProtoClient::new(endpoint).handle_value_error()?;
+        let timeout = parse_timeout(timeout)?;
 
-        let transport = Arc::new(nt::transport::proto::ProtoTransport::new(client));
-        let handle = TransportHandle::Proto(transport);
+        let (handle, endpoints) = build_endpoints_transport(
+            endpoint,
+            endpoints,
+            strategy,
+            health_check_interval,
+            |endpoint| {
+                let client = ProtoClient::new(endpoint).handle_value_error()?;
+                Ok(TransportHandle::Proto(Arc::new(
+                    nt::transport::proto::ProtoTransport::new(client),
+                )))
+            },
+        )?;
         let clock = clock.unwrap_or_default();
+        let options = TransportOptions {
+            retry_policy,
+            timeout,
+            cache,
+            ..Default::default()
+        };
 
         Ok(
-            PyClassInitializer::from(Transport(TransportState::new(clock, handle)))
-                .add_subclass(Self),
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
+                .add_subclass(Self(endpoints)),
         )
     }
-}
 
-#[pyclass]
-pub struct AccountStatesAsyncIter(Arc<tokio::sync::Mutex<AccountStatesAsyncIterState>>);
+    #[getter]
+    fn endpoints(&self) -> Vec<String> {
+        self.0.list()
+    }
 
-enum AccountStatesAsyncIterState {
-    Uninit {
-        transport: Arc<TransportState>,
-        address: ton_block::MsgAddressInt,
-    },
-    Active {
-        watch: watch::Receiver<PyObject>,
-        initial: bool,
-        subscription: Arc<SharedSubscription>,
-    },
-    Closed,
+    #[getter]
+    fn active_endpoint(&self) -> String {
+        self.0.active()
+    }
 }
 
+#[derive(Clone)]
+#[pyclass(subclass, extends = Transport)]
+pub struct LocalTransport(Arc<local::LocalBlockchain>);
+
 #[pymethods]
-impl AccountStatesAsyncIter {
-    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
-        let state = self.0.clone();
-        pyo3_asyncio::tokio::future_into_py(py, async move {
-            let mut state = state.lock().await;
-            if let AccountStatesAsyncIterState::Active { subscription, .. } = &*state {
-                log::debug!(
-                    "Closed account states iterator for {}",
-                    subscription.address
-                );
-            }
-            *state = AccountStatesAsyncIterState::Closed;
-            Ok(())
+impl LocalTransport {
+    #[new]
+    fn new(
+        config: Option<BlockchainConfig>,
+        clock: Option<Clock>,
+        check_signature: Option<bool>,
+        fork: Option<PyRef<'_, Transport>>,
+        retry_policy: Option<RetryPolicy>,
+        timeout: Option<f64>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        let clock = clock.unwrap_or_default();
+        let timeout = parse_timeout(timeout)?;
+
+        let fork = fork.map(|transport| local::Fork {
+            transport: transport.0.clone(),
+            retry_policy,
+            timeout,
+        });
+
+        let blockchain = local::LocalBlockchain::new(
+            clock.clone(),
+            config.map(|config| config.as_ref().clone()),
+            check_signature.unwrap_or(true),
+            fork,
+        )
+        .handle_value_error()?;
+        let blockchain = Arc::new(blockchain);
+        let handle = TransportHandle::Local(blockchain.clone());
+        let options = TransportOptions::default();
+
+        Ok(
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
+                .add_subclass(Self(blockchain)),
+        )
+    }
+
+    #[getter]
+    fn accounts(&self) -> Vec<Address> {
+        self.0.accounts().into_iter().map(Address).collect()
+    }
+
+    fn get_account(&self, address: Address) -> Option<AccountState> {
+        self.0.get_account(&address.0).map(AccountState)
+    }
+
+    fn set_account(&self, address: Address, state: Option<&AccountState>) {
+        self.0
+            .set_account(address.0, state.map(|state| state.0.clone()))
+    }
+}
+
+#[derive(Clone)]
+#[pyclass(subclass, extends = Transport)]
+pub struct CustomTransport {
+    handler: PyObject,
+}
+
+#[pymethods]
+impl CustomTransport {
+    #[new]
+    fn new(
+        py: Python<'_>,
+        handler: PyObject,
+        clock: Option<Clock>,
+        max_transactions_per_fetch: Option<u8>,
+        timeout: Option<f64>,
+        cache: Option<CacheConfig>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        const DEFAULT_MAX_TRANSACTIONS_PER_FETCH: u8 = 50;
+
+        let timeout = parse_timeout(timeout)?;
+
+        let locals = pyo3_asyncio::tokio::get_current_locals(py).map_err(|_| {
+            PyRuntimeError::new_err("Custom transport must be created inside a running event loop")
+        })?;
+
+        let transport = Arc::new(custom::PythonTransport::new(
+            handler.clone_ref(py),
+            locals,
+            max_transactions_per_fetch.unwrap_or(DEFAULT_MAX_TRANSACTIONS_PER_FETCH),
+        ));
+        let handle = TransportHandle::Custom(transport);
+        let clock = clock.unwrap_or_default();
+        let options = TransportOptions {
+            timeout,
+            cache,
+            ..Default::default()
+        };
+
+        Ok(
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
+                .add_subclass(Self { handler }),
+        )
+    }
+
+    #[getter]
+    fn handler(&self, py: Python<'_>) -> PyObject {
+        self.handler.clone_ref(py)
+    }
+}
+
+#[derive(Copy, Clone)]
+#[pyclass(subclass, extends = Transport)]
+pub struct RecordingTransport;
+
+#[pymethods]
+impl RecordingTransport {
+    #[new]
+    fn new(transport: PyRef<'_, Transport>, path: PathBuf) -> PyResult<PyClassInitializer<Self>> {
+        // NOTE: blocks are fetched directly from the GQL transport, so they can't be recorded
+        if transport.0.block_walker.is_some() {
+            return Err(PyValueError::new_err(
+                "Block walking transports can't be recorded",
+            ));
+        }
+
+        let inner = transport.0.handle.clone().into();
+        let clock = transport.0.clock.clone();
+        let recording = recording::RecordingTransport::new(inner, clock.clone(), path)
+            .map_err(|e| PyIOError::new_err(format!("{e:#}")))?;
+
+        let handle = TransportHandle::Recording(Arc::new(recording));
+        let options = transport.0.options.clone();
+
+        Ok(
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
+                .add_subclass(Self),
+        )
+    }
+}
+
+#[derive(Copy, Clone)]
+#[pyclass(subclass, extends = Transport)]
+pub struct ReplayTransport;
+
+#[pymethods]
+impl ReplayTransport {
+    #[new]
+    fn new(path: PathBuf, clock: Option<Clock>) -> PyResult<PyClassInitializer<Self>> {
+        let replay = recording::ReplayTransport::new(path, clock)
+            .map_err(|e| PyIOError::new_err(format!("{e:#}")))?;
+        let clock = replay.clock().clone();
+        let handle = TransportHandle::Replay(Arc::new(replay));
+        let options = TransportOptions::default();
+
+        Ok(
+            PyClassInitializer::from(Transport(TransportState::new(clock, handle, options)?))
+                .add_subclass(Self),
+        )
+    }
+}
+
+/// Handle to the broadcasted external message.
+#[pyclass]
+pub struct PendingMessage {
+    hash: ton_types::UInt256,
+    expire_at: u32,
+    dst: ton_block::MsgAddressInt,
+    subscription: Arc<SharedSubscription>,
+    result: Arc<tokio::sync::Mutex<PendingMessageResult>>,
+}
+
+impl PendingMessage {
+    fn new(
+        hash: ton_types::UInt256,
+        expire_at: u32,
+        dst: ton_block::MsgAddressInt,
+        subscription: Arc<SharedSubscription>,
+        result: PendingMessageResult,
+    ) -> Self {
+        Self {
+            hash,
+            expire_at,
+            dst,
+            subscription,
+            result: Arc::new(tokio::sync::Mutex::new(result)),
+        }
+    }
+}
+
+impl PendingMessage {
+    fn wait_impl(&self) -> impl Future<Output = PyResult<Option<Transaction>>> + Send + 'static {
+        let result = self.result.clone();
+        async move {
+            let mut result = result.lock().await;
+            if let PendingMessageResult::Waiting(rx) = &mut *result {
+                *result = match wait_pending_message(rx).await {
+                    Ok(tx) => PendingMessageResult::Received(tx),
+                    Err(e) => PendingMessageResult::Failed(e),
+                };
+            }
+
+            match &*result {
+                PendingMessageResult::Received(tx) => Ok(tx.clone()),
+                PendingMessageResult::Failed(e) => Err(Python::with_gil(|py| e.clone_ref(py))),
+                PendingMessageResult::Waiting(_) => unreachable!(),
+            }
+        }
+    }
+}
+
+enum PendingMessageResult {
+    Waiting(oneshot::Receiver<ReceivedTransaction>),
+    Received(Option<Transaction>),
+    Failed(PyErr),
+}
+
+#[pymethods]
+impl PendingMessage {
+    #[getter]
+    fn hash(&self) -> &[u8] {
+        self.hash.as_slice()
+    }
+
+    #[getter]
+    fn expire_at(&self) -> u32 {
+        self.expire_at
+    }
+
+    #[getter]
+    fn dst(&self) -> Address {
+        Address(self.dst.clone())
+    }
+
+    /// Waits until the transaction is found or the message expires.
+    fn wait<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        pyo3_asyncio::tokio::future_into_py(py, self.wait_impl())
+    }
+
+    /// Stops waiting for the transaction. Returns `False` if the message was already resolved.
+    fn cancel(&self) -> bool {
+        self.subscription
+            .state
+            .pending_messages
+            .remove(&self.hash)
+            .is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<PendingMessage hash='{}', dst='{}', expire_at={}>",
+            hex::encode(self.hash.as_slice()),
+            self.dst,
+            self.expire_at,
+        )
+    }
+}
+
+#[pyclass]
+pub struct AccountStatesAsyncIter(Arc<tokio::sync::Mutex<AccountStatesAsyncIterState>>);
+
+enum AccountStatesAsyncIterState {
+    Uninit {
+        transport: Arc<TransportState>,
+        address: ton_block::MsgAddressInt,
+    },
+    Active {
+        watch: watch::Receiver<PyObject>,
+        initial: bool,
+        subscription: Arc<SharedSubscription>,
+    },
+    Closed,
+}
+
+#[pymethods]
+impl AccountStatesAsyncIter {
+    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+            if let AccountStatesAsyncIterState::Active { subscription, .. } = &*state {
+                log::debug!(
+                    "Closed account states iterator for {}",
+                    subscription.address
+                );
+            }
+            *state = AccountStatesAsyncIterState::Closed;
+            Ok(())
         })
     }
 
@@ -959,12 +2456,96 @@ enum AccountTransactionsAsyncIterState {
         address: ton_block::MsgAddressInt,
     },
     Active {
-        transactions: broadcast::Receiver<PyObject>,
+        receiver: TransactionsReceiver,
         subscription: Arc<SharedSubscription>,
+        lag_mode: LagMode,
     },
     Closed,
 }
 
+impl AccountTransactionsAsyncIterState {
+    async fn activate(
+        transport: &TransportState,
+        address: &ton_block::MsgAddressInt,
+    ) -> PyResult<Self> {
+        let lag_mode = transport.subscription_options.read().lag_mode;
+        let subscription = transport.get_subscription(address.clone()).await?;
+        let receiver = TransactionsReceiver::new(&subscription);
+        log::debug!("Created transactions iterator for {address}");
+
+        Ok(Self::Active {
+            receiver,
+            subscription,
+            lag_mode,
+        })
+    }
+}
+
+/// Transactions broadcast receiver which tracks skipped ranges.
+struct TransactionsReceiver {
+    transactions: broadcast::Receiver<TransactionsBatch>,
+    last_lt: u64,
+    pending: Option<TransactionsBatch>,
+}
+
+enum ReceivedBatch {
+    Batch(TransactionsBatch),
+    Gap(TransactionsBatchInfo),
+}
+
+impl TransactionsReceiver {
+    fn new(subscription: &SharedSubscription) -> Self {
+        // NOTE: lt is loaded before subscribing so that the gap range
+        // can only be wider than the actually skipped one
+        let last_lt = subscription.state.last_lt.load(Ordering::Acquire);
+        let transactions = subscription.state.transactions.subscribe();
+
+        Self {
+            transactions,
+            last_lt,
+            pending: None,
+        }
+    }
+
+    async fn recv(&mut self) -> Option<ReceivedBatch> {
+        let (batch, lagged) = match self.pending.take() {
+            Some(batch) => (batch, false),
+            None => recv_transactions_batch(&mut self.transactions).await?,
+        };
+
+        if lagged {
+            let info = TransactionsBatchInfo {
+                min_lt: self.last_lt + 1,
+                max_lt: batch.min_lt.saturating_sub(1),
+                gap: true,
+            };
+            self.last_lt = info.max_lt;
+            self.pending = Some(batch);
+            return Some(ReceivedBatch::Gap(info));
+        }
+
+        self.last_lt = batch.max_lt;
+        Some(ReceivedBatch::Batch(batch))
+    }
+}
+
+/// Receives the next batch and reports whether some batches were skipped before it.
+async fn recv_transactions_batch(
+    rx: &mut broadcast::Receiver<TransactionsBatch>,
+) -> Option<(TransactionsBatch, bool)> {
+    let mut lagged = false;
+    loop {
+        match rx.recv().await {
+            Ok(batch) => return Some((batch, lagged)),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                log::warn!("Transactions iterator skipped {skipped} batches");
+                lagged = true;
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
 #[pymethods]
 impl AccountTransactionsAsyncIter {
     fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
@@ -990,13 +2571,8 @@ impl AccountTransactionsAsyncIter {
                     "Entering closed transactions subscription",
                 )),
                 AccountTransactionsAsyncIterState::Uninit { transport, address } => {
-                    let subscription = transport.get_subscription(address.clone()).await?;
-                    let transactions = subscription.state.transactions.subscribe();
-                    log::debug!("Created transactions iterator for {address}");
-                    *state = AccountTransactionsAsyncIterState::Active {
-                        transactions,
-                        subscription,
-                    };
+                    *state =
+                        AccountTransactionsAsyncIterState::activate(transport, address).await?;
                     Ok(slf)
                 }
             }
@@ -1026,24 +2602,28 @@ impl AccountTransactionsAsyncIter {
                     return Err(PyStopAsyncIteration::new_err(()))
                 }
                 AccountTransactionsAsyncIterState::Uninit { transport, address } => {
-                    let subscription = transport.get_subscription(address.clone()).await?;
-                    let transactions = subscription.state.transactions.subscribe();
-                    log::debug!("Created transactions iterator for {address}");
-                    *state = AccountTransactionsAsyncIterState::Active {
-                        transactions,
-                        subscription,
-                    };
+                    *state =
+                        AccountTransactionsAsyncIterState::activate(transport, address).await?;
                 }
                 _ => {}
             };
 
             match &mut *state {
                 AccountTransactionsAsyncIterState::Active {
-                    transactions,
+                    receiver,
                     subscription,
-                } => match transactions.recv().await {
-                    Ok(batch) => Ok(batch),
-                    Err(_) => {
+                    lag_mode,
+                } => match receiver.recv().await {
+                    Some(ReceivedBatch::Batch(batch)) => Ok(batch.value),
+                    Some(ReceivedBatch::Gap(info)) => match lag_mode {
+                        LagMode::Raise => {
+                            Err(SubscriptionLagged::new_err((info.min_lt, info.max_lt)))
+                        }
+                        LagMode::Gap => Ok(Python::with_gil(|py| {
+                            (Vec::<Transaction>::new(), info).into_py(py)
+                        })),
+                    },
+                    None => {
                         log::debug!("Closed transactions iterator for {}", subscription.address);
                         *state = AccountTransactionsAsyncIterState::Closed;
                         Err(PyStopAsyncIteration::new_err(()))
@@ -1057,209 +2637,1522 @@ impl AccountTransactionsAsyncIter {
 }
 
 #[pyclass]
-pub struct TraceTransaction(Arc<tokio::sync::Mutex<TraceTransactionState>>);
+pub struct AccountsWatcher(Arc<AccountsWatcherState>);
 
-struct TraceTransactionState {
+struct AccountsWatcherState {
     transport: Arc<TransportState>,
-    yield_root: bool,
-    root_hash: Option<ton_types::UInt256>,
-    root: Option<PyObject>,
-    queue: VecDeque<ton_types::UInt256>,
+    /// Addresses which are subscribed on first use.
+    initial: parking_lot::Mutex<Option<Vec<ton_block::MsgAddressInt>>>,
+    accounts: parking_lot::Mutex<WatchedAccountsMap>,
+    items_tx: mpsc::Sender<PyResult<PyObject>>,
+    items_rx: tokio::sync::Mutex<mpsc::Receiver<PyResult<PyObject>>>,
+    cancellation_token: CancellationToken,
 }
 
-impl TraceTransactionState {
-    fn extract_messages(
-        tx: &ton_block::Transaction,
-        queue: &mut VecDeque<ton_types::UInt256>,
-    ) -> PyResult<()> {
-        let mut hashes = Vec::new();
-        tx.out_msgs
-            .iterate_slices(|slice| {
-                let Some(msg_cell) = slice.reference_opt(0) else {
-                    return Ok(true);
-                };
+/// Watched accounts, `None` while the subscription is being created.
+type WatchedAccountsMap = FastHashMap<ton_block::MsgAddressInt, Option<WatchedAccount>>;
 
-                let message = ton_block::Message::construct_from_cell(msg_cell.clone())?;
-                if message.is_internal() {
-                    hashes.push(msg_cell.repr_hash());
-                }
+const MAX_CONCURRENT_ACCOUNT_REQUESTS: usize = 16;
 
-                Ok(true)
+/// Shared account subscription and the task which forwards
+/// its transactions into the watcher channel.
+struct WatchedAccount {
+    _subscription: Arc<SharedSubscription>,
+    forwarder: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for WatchedAccount {
+    fn drop(&mut self) {
+        self.forwarder.abort();
+    }
+}
+
+impl AccountsWatcherState {
+    async fn ensure_started(&self) -> PyResult<()> {
+        let initial = self.initial.lock().take();
+        if let Some(addresses) = initial {
+            self.add(addresses).await?;
+        }
+        Ok(())
+    }
+
+    async fn add(&self, addresses: Vec<ton_block::MsgAddressInt>) -> PyResult<()> {
+        use std::collections::hash_map;
+
+        if self.cancellation_token.is_cancelled() {
+            return Err(PyRuntimeError::new_err("Accounts watcher is closed"));
+        }
+
+        // NOTE: addresses are reserved at once, so concurrent calls don't subscribe
+        // to the same account twice. Unfinished subscriptions are retried by the next call.
+        let new_addresses = {
+            let mut accounts = self.accounts.lock();
+            addresses
+                .into_iter()
+                .filter(|address| match accounts.entry(address.clone()) {
+                    hash_map::Entry::Vacant(entry) => {
+                        entry.insert(None);
+                        true
+                    }
+                    hash_map::Entry::Occupied(entry) => entry.get().is_none(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let transport = &self.transport;
+        let subscribed = futures_util::stream::iter(new_addresses)
+            .map(|address| async move {
+                let subscription = transport.get_subscription(address.clone()).await;
+                (address, subscription)
             })
-            .handle_runtime_error()?;
+            .buffer_unordered(MAX_CONCURRENT_ACCOUNT_REQUESTS)
+            .collect::<Vec<_>>()
+            .await;
+
+        let lag_mode = self.transport.subscription_options.read().lag_mode;
+
+        let mut result = Ok(());
+        let mut accounts = self.accounts.lock();
+        for (address, subscription) in subscribed {
+            let hash_map::Entry::Occupied(mut entry) = accounts.entry(address) else {
+                // Removed while subscribing
+                continue;
+            };
+            if entry.get().is_some() {
+                continue;
+            }
+
+            match subscription {
+                Ok(subscription) => {
+                    let forwarder = tokio::spawn(forward_account_transactions(
+                        entry.key().clone(),
+                        TransactionsReceiver::new(&subscription),
+                        lag_mode,
+                        self.items_tx.clone(),
+                    ));
+                    *entry.get_mut() = Some(WatchedAccount {
+                        _subscription: subscription,
+                        forwarder,
+                    });
+                }
+                Err(e) => {
+                    entry.remove();
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn remove(&self, addresses: &[ton_block::MsgAddressInt]) {
+        if let Some(initial) = &mut *self.initial.lock() {
+            initial.retain(|address| !addresses.contains(address));
+        }
+
+        let mut accounts = self.accounts.lock();
+        for address in addresses {
+            accounts.remove(address);
+        }
+    }
+
+    fn addresses(&self) -> Vec<ton_block::MsgAddressInt> {
+        let mut addresses = self.initial.lock().clone().unwrap_or_default();
+        addresses.extend(self.accounts.lock().keys().cloned());
+        addresses
+    }
+
+    fn close(&self) {
+        self.cancellation_token.cancel();
+        self.accounts.lock().clear();
+    }
+}
+
+impl Drop for AccountsWatcherState {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Forwards batches of the shared account subscription into the watcher channel.
+async fn forward_account_transactions(
+    address: ton_block::MsgAddressInt,
+    mut receiver: TransactionsReceiver,
+    lag_mode: LagMode,
+    items_tx: mpsc::Sender<PyResult<PyObject>>,
+) {
+    while let Some(batch) = receiver.recv().await {
+        let item = Python::with_gil(|py| match batch {
+            ReceivedBatch::Batch(batch) => {
+                let (transactions, info) = batch.value.extract::<(PyObject, PyObject)>(py)?;
+                Ok((Address(address.clone()), transactions, info).into_py(py))
+            }
+            ReceivedBatch::Gap(info) => match lag_mode {
+                LagMode::Raise => Err(SubscriptionLagged::new_err((info.min_lt, info.max_lt))),
+                LagMode::Gap => {
+                    let transactions = Vec::<Transaction>::new();
+                    Ok((Address(address.clone()), transactions, info).into_py(py))
+                }
+            },
+        });
+
+        if items_tx.send(item).await.is_err() {
+            break;
+        }
+    }
+    log::debug!("Stopped watching {address}");
+}
+
+#[pymethods]
+impl AccountsWatcher {
+    #[getter]
+    fn addresses(&self) -> Vec<Address> {
+        self.0.addresses().into_iter().map(Address).collect()
+    }
+
+    fn add<'a>(&self, py: Python<'a>, addresses: Vec<Address>) -> PyResult<&'a PyAny> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            state.ensure_started().await?;
+            state
+                .add(addresses.into_iter().map(|Address(addr)| addr).collect())
+                .await
+        })
+    }
+
+    fn remove<'a>(&self, py: Python<'a>, addresses: Vec<Address>) -> PyResult<&'a PyAny> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let addresses = addresses
+                .into_iter()
+                .map(|Address(addr)| addr)
+                .collect::<Vec<_>>();
+            state.remove(&addresses);
+            Ok(())
+        })
+    }
+
+    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            state.close();
+            log::debug!("Closed accounts watcher");
+            Ok(())
+        })
+    }
+
+    fn __aenter__<'a>(slf: PyRef<'a, Self>, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = slf.0.clone();
+        let slf = slf.into_py(py);
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if state.cancellation_token.is_cancelled() {
+                return Err(PyRuntimeError::new_err("Entering closed accounts watcher"));
+            }
+            state.ensure_started().await?;
+            Ok(slf)
+        })
+    }
+
+    fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        _exc_type: &'a PyAny,
+        _exc_value: &'a PyAny,
+        _traceback: &'a PyAny,
+    ) -> PyResult<&'a PyAny> {
+        self.close(py)
+    }
+
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'a>(&'a mut self, py: Python<'a>) -> PyResult<Option<&'a PyAny>> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            if state.cancellation_token.is_cancelled() {
+                return Err(PyStopAsyncIteration::new_err(()));
+            }
+            state.ensure_started().await?;
+
+            let mut items_rx = state.items_rx.lock().await;
+            tokio::select! {
+                item = items_rx.recv() => match item {
+                    Some(item) => item,
+                    None => Err(PyStopAsyncIteration::new_err(())),
+                },
+                _ = state.cancellation_token.cancelled() => {
+                    Err(PyStopAsyncIteration::new_err(()))
+                }
+            }
+        })
+        .map(Some)
+    }
+}
+
+#[pyclass]
+pub struct TransactionsIter(Arc<tokio::sync::Mutex<PagedIterState<Transaction>>>);
+
+/// Buffered pages of an async iterator filled by a background producer.
+struct PagedIterState<T> {
+    pages: mpsc::Receiver<PyResult<Vec<T>>>,
+    buffer: VecDeque<T>,
+    producer: ProducerState,
+}
+
+impl<T> PagedIterState<T> {
+    fn new<F, Fut>(prefetch: usize, f: F) -> Self
+    where
+        F: FnOnce(mpsc::Sender<PyResult<Vec<T>>>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (pages_tx, pages_rx) = mpsc::channel(prefetch);
+        Self {
+            pages: pages_rx,
+            buffer: Default::default(),
+            producer: ProducerState::Idle(Box::pin(f(pages_tx))),
+        }
+    }
+
+    async fn next(&mut self) -> PyResult<Option<T>> {
+        self.producer.ensure_started();
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Ok(Some(item));
+            }
+
+            match self.pages.recv().await {
+                Some(Ok(page)) => self.buffer.extend(page),
+                Some(Err(e)) => return Err(e),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        self.producer.abort();
+        self.pages.close();
+        self.buffer.clear();
+    }
+}
+
+impl<T> Drop for PagedIterState<T> {
+    fn drop(&mut self) {
+        self.producer.abort();
+    }
+}
+
+/// Pages producer which is spawned on the first iteration.
+enum ProducerState {
+    Idle(futures_util::future::BoxFuture<'static, ()>),
+    Running(tokio::task::JoinHandle<()>),
+    Stopped,
+}
+
+impl ProducerState {
+    fn ensure_started(&mut self) {
+        if let Self::Idle(_) = self {
+            let Self::Idle(producer) = std::mem::replace(self, Self::Stopped) else {
+                unreachable!();
+            };
+            *self = Self::Running(tokio::spawn(producer));
+        }
+    }
+
+    fn abort(&mut self) {
+        if let Self::Running(handle) = std::mem::replace(self, Self::Stopped) {
+            handle.abort();
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct TransactionsFilter {
+    from_lt: u64,
+    to_lt: u64,
+    since: u32,
+    until: u32,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum TransactionsOrder {
+    Asc,
+    Desc,
+}
+
+impl FromStr for TransactionsOrder {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(PyValueError::new_err("Unknown transactions order")),
+        }
+    }
+}
+
+/// Arguments of the transactions history iterators.
+struct TransactionsIterParams<'a> {
+    from_lt: Option<u64>,
+    to_lt: Option<u64>,
+    since: Option<u32>,
+    until: Option<u32>,
+    order: Option<&'a str>,
+    limit: Option<usize>,
+    prefetch: Option<usize>,
+}
+
+impl TransactionsIterParams<'_> {
+    fn build(
+        self,
+        transport: &Arc<TransportState>,
+        address: Address,
+    ) -> PyResult<PagedIterState<Transaction>> {
+        const DEFAULT_PREFETCH: usize = 2;
+
+        let filter = TransactionsFilter {
+            from_lt: self.from_lt.unwrap_or_default(),
+            to_lt: self.to_lt.unwrap_or(u64::MAX),
+            since: self.since.unwrap_or_default(),
+            until: self.until.unwrap_or(u32::MAX),
+        };
+        if filter.from_lt > filter.to_lt || filter.since > filter.until {
+            return Err(PyValueError::new_err("Invalid transactions range"));
+        }
+
+        let order = match self.order {
+            Some(order) => order.parse()?,
+            None => TransactionsOrder::Desc,
+        };
+
+        let prefetch = self.prefetch.unwrap_or(DEFAULT_PREFETCH);
+        if prefetch == 0 {
+            return Err(PyValueError::new_err("Prefetch must be greater than 0"));
+        }
+
+        let transport = transport.clone();
+        let limit = self.limit.unwrap_or(usize::MAX);
+        Ok(PagedIterState::new(prefetch, |pages_tx| {
+            produce_transactions(transport, address.0, filter, order, limit, pages_tx)
+        }))
+    }
+}
+
+/// Walks account transactions from the newest to the oldest and sends them in pages.
+///
+/// NOTE: transactions can only be fetched in descending order, so for the
+/// ascending order the whole range is walked first. Only the oldest `limit`
+/// transactions are kept in memory while walking.
+async fn produce_transactions(
+    transport: Arc<TransportState>,
+    address: ton_block::MsgAddressInt,
+    filter: TransactionsFilter,
+    order: TransactionsOrder,
+    mut limit: usize,
+    pages_tx: mpsc::Sender<PyResult<Vec<Transaction>>>,
+) {
+    let page_size = transport.handle.as_ref().info().max_transactions_per_fetch;
+
+    let mut collected = Vec::new();
+    let mut next_lt = filter.to_lt;
+    while limit > 0 {
+        let res = transport
+            .get_raw_transactions(&address, next_lt, page_size, None, None)
+            .await;
+        let raw_transactions = match res {
+            Ok(raw_transactions) => raw_transactions,
+            Err(e) => {
+                pages_tx.send(Err(e)).await.ok();
+                return;
+            }
+        };
+
+        let mut page = Vec::with_capacity(raw_transactions.len());
+        let mut reached_end = raw_transactions.is_empty();
+        for raw in raw_transactions {
+            let (lt, now, prev_lt) = (raw.data.lt, raw.data.now, raw.data.prev_trans_lt);
+            if lt < filter.from_lt || now < filter.since {
+                reached_end = true;
+                break;
+            }
+
+            next_lt = prev_lt;
+            reached_end = prev_lt == 0;
+
+            if lt > filter.to_lt || now > filter.until {
+                continue;
+            }
+
+            match Transaction::try_from(raw) {
+                Ok(tx) => page.push(tx),
+                Err(e) => {
+                    pages_tx.send(Err(e)).await.ok();
+                    return;
+                }
+            }
+
+            if order == TransactionsOrder::Desc {
+                limit -= 1;
+                if limit == 0 {
+                    reached_end = true;
+                    break;
+                }
+            }
+        }
+
+        match order {
+            TransactionsOrder::Desc if !page.is_empty() => {
+                if pages_tx.send(Ok(page)).await.is_err() {
+                    return;
+                }
+            }
+            TransactionsOrder::Desc => {}
+            TransactionsOrder::Asc => {
+                collected.extend(page);
+                if collected.len() > limit {
+                    collected.drain(..collected.len() - limit);
+                }
+            }
+        }
+
+        if reached_end {
+            break;
+        }
+    }
+
+    if order == TransactionsOrder::Asc {
+        collected.reverse();
+
+        let page_size = std::cmp::max(page_size as usize, 1);
+        let mut collected = collected.into_iter().peekable();
+        while collected.peek().is_some() {
+            let page = collected.by_ref().take(page_size).collect();
+            if pages_tx.send(Ok(page)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl TransactionsIter {
+    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            state.lock().await.close();
+            Ok(())
+        })
+    }
+
+    fn __aenter__<'a>(slf: PyRef<'a, Self>, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let slf = slf.into_py(py);
+        pyo3_asyncio::tokio::future_into_py(py, async move { Ok(slf) })
+    }
+
+    fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        _exc_type: &'a PyAny,
+        _exc_value: &'a PyAny,
+        _traceback: &'a PyAny,
+    ) -> PyResult<&'a PyAny> {
+        self.close(py)
+    }
+
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'a>(&'a mut self, py: Python<'a>) -> PyResult<Option<&'a PyAny>> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            match state.lock().await.next().await? {
+                Some(item) => Ok(item),
+                None => Err(PyStopAsyncIteration::new_err(())),
+            }
+        })
+        .map(Some)
+    }
+}
+
+#[pyclass]
+pub struct AccountsByCodeHashIter(Arc<tokio::sync::Mutex<PagedIterState<CodeHashAccount>>>);
+
+enum CodeHashAccount {
+    Address(Address),
+    WithState(Address, Option<AccountState>),
+}
+
+impl IntoPy<PyObject> for CodeHashAccount {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Self::Address(address) => address.into_py(py),
+            Self::WithState(address, state) => (address, state).into_py(py),
+        }
+    }
+}
+
+/// Arguments of the accounts by code hash iterators.
+struct AccountsByCodeHashIterParams {
+    with_states: Option<bool>,
+    concurrency: Option<usize>,
+    page_size: Option<u8>,
+    prefetch: Option<usize>,
+}
+
+impl AccountsByCodeHashIterParams {
+    fn build(
+        self,
+        transport: &Arc<TransportState>,
+        code_hash: &[u8],
+    ) -> PyResult<PagedIterState<CodeHashAccount>> {
+        const DEFAULT_CONCURRENCY: usize = 10;
+        const DEFAULT_PAGE_SIZE: u8 = 50;
+        const DEFAULT_PREFETCH: usize = 2;
+
+        let code_hash = ton_types::UInt256::from_bytes(code_hash, "code hash")?;
+
+        let concurrency = self.concurrency.unwrap_or(DEFAULT_CONCURRENCY);
+        let page_size = self.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+        let prefetch = self.prefetch.unwrap_or(DEFAULT_PREFETCH);
+        if concurrency == 0 || page_size == 0 || prefetch == 0 {
+            return Err(PyValueError::new_err(
+                "Concurrency, page size and prefetch must be greater than 0",
+            ));
+        }
+
+        let transport = transport.clone();
+        let with_states = self.with_states.unwrap_or_default();
+        Ok(PagedIterState::new(prefetch, |pages_tx| {
+            produce_accounts_by_code_hash(
+                transport,
+                code_hash,
+                with_states,
+                concurrency,
+                page_size,
+                pages_tx,
+            )
+        }))
+    }
+}
+
+async fn produce_accounts_by_code_hash(
+    transport: Arc<TransportState>,
+    code_hash: ton_types::UInt256,
+    with_states: bool,
+    concurrency: usize,
+    page_size: u8,
+    pages_tx: mpsc::Sender<PyResult<Vec<CodeHashAccount>>>,
+) {
+    let mut continuation = None;
+    loop {
+        let res = transport
+            .get_accounts_by_code_hash(
+                &code_hash,
+                continuation.clone(),
+                Some(page_size),
+                None,
+                None,
+            )
+            .await;
+        let addresses = match res {
+            Ok(addresses) => addresses
+                .into_iter()
+                .map(|Address(address)| address)
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                pages_tx.send(Err(e)).await.ok();
+                return;
+            }
+        };
+
+        let is_last_page = addresses.len() < page_size as usize;
+        continuation = addresses.last().cloned();
+
+        let page = if with_states {
+            // NOTE: states are fetched concurrently within the producer,
+            // so aborting it also cancels all requests in flight
+            let transport = transport.as_ref();
+            let res = futures_util::stream::iter(addresses)
+                .map(|address| async move {
+                    let state = transport.get_contract_state(&address, None, None).await?;
+                    let state = match state {
+                        nt::transport::models::RawContractState::NotExists { .. } => None,
+                        nt::transport::models::RawContractState::Exists(state) => {
+                            Some(AccountState(state.account))
+                        }
+                    };
+                    PyResult::Ok(CodeHashAccount::WithState(Address(address), state))
+                })
+                .buffered(concurrency)
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .collect::<PyResult<Vec<_>>>();
+
+            match res {
+                Ok(page) => page,
+                Err(e) => {
+                    pages_tx.send(Err(e)).await.ok();
+                    return;
+                }
+            }
+        } else {
+            addresses
+                .into_iter()
+                .map(|address| CodeHashAccount::Address(Address(address)))
+                .collect()
+        };
+
+        if !page.is_empty() && pages_tx.send(Ok(page)).await.is_err() {
+            return;
+        }
+
+        if is_last_page {
+            return;
+        }
+    }
+}
+
+#[pymethods]
+impl AccountsByCodeHashIter {
+    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            state.lock().await.close();
+            Ok(())
+        })
+    }
+
+    fn __aenter__<'a>(slf: PyRef<'a, Self>, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let slf = slf.into_py(py);
+        pyo3_asyncio::tokio::future_into_py(py, async move { Ok(slf) })
+    }
+
+    fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        _exc_type: &'a PyAny,
+        _exc_value: &'a PyAny,
+        _traceback: &'a PyAny,
+    ) -> PyResult<&'a PyAny> {
+        self.close(py)
+    }
+
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'a>(&'a mut self, py: Python<'a>) -> PyResult<Option<&'a PyAny>> {
+        let state = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            match state.lock().await.next().await? {
+                Some(item) => Ok(item),
+                None => Err(PyStopAsyncIteration::new_err(())),
+            }
+        })
+        .map(Some)
+    }
+}
+
+#[pyclass]
+pub struct TraceTransaction {
+    state: Arc<tokio::sync::Mutex<TraceTransactionState>>,
+    truncated: Arc<AtomicBool>,
+}
+
+struct TraceTransactionState {
+    transport: Arc<TransportState>,
+    yield_root: bool,
+    root_hash: Option<ton_types::UInt256>,
+    root: Option<PyObject>,
+    queue: VecDeque<TracedMessage>,
+    deadline: tokio::time::Instant,
+    max_depth: Option<usize>,
+    max_count: Option<usize>,
+    /// Whether some messages were not traced due to `max_depth` or `max_count`.
+    truncated: Arc<AtomicBool>,
+    /// Observed transactions with hashes of their parent transactions.
+    observed: Vec<(
+        Option<ton_types::UInt256>,
+        nt::transport::models::RawTransaction,
+    )>,
+}
+
+struct TracedMessage {
+    hash: ton_types::UInt256,
+    parent: ton_types::UInt256,
+    depth: usize,
+}
+
+impl TraceTransactionState {
+    fn observe(
+        &mut self,
+        parent: Option<ton_types::UInt256>,
+        depth: usize,
+        tx: &nt::transport::models::RawTransaction,
+    ) -> PyResult<()> {
+        self.observed.push((parent, tx.clone()));
+
+        let mut hashes = Vec::new();
+        tx.data
+            .out_msgs
+            .iterate_slices(|slice| {
+                let Some(msg_cell) = slice.reference_opt(0) else {
+                    return Ok(true);
+                };
+
+                let message = ton_block::Message::construct_from_cell(msg_cell.clone())?;
+                if message.is_internal() {
+                    hashes.push(msg_cell.repr_hash());
+                }
+
+                Ok(true)
+            })
+            .handle_cell_error()?;
+
+        let depth_reached = matches!(self.max_depth, Some(max_depth) if depth >= max_depth);
+        let count_reached =
+            matches!(self.max_count, Some(max_count) if self.observed.len() >= max_count);
+        if depth_reached || count_reached {
+            if !hashes.is_empty() || (count_reached && !self.queue.is_empty()) {
+                self.truncated.store(true, Ordering::Release);
+            }
+            if count_reached {
+                self.queue.clear();
+            }
+            return Ok(());
+        }
+
+        self.queue
+            .extend(hashes.into_iter().map(|hash| TracedMessage {
+                hash,
+                parent: tx.hash,
+                depth: depth + 1,
+            }));
+        Ok(())
+    }
+
+    async fn next(&mut self) -> PyResult<Option<nt::transport::models::RawTransaction>> {
+        tokio::time::timeout_at(self.deadline, self.next_impl())
+            .await
+            .map_err(|_| Timeout::new_err("Transaction trace timed out"))?
+    }
+
+    async fn next_impl(&mut self) -> PyResult<Option<nt::transport::models::RawTransaction>> {
+        const MIN_INTERVAL_MS: u64 = 500;
+        const MAX_INTERVAL_MS: u64 = 3000;
+        const FACTOR: u64 = 2;
+
+        let transport = self.transport.clone();
+
+        if let Some(root_hash) = &self.root_hash {
+            let Some(tx) = transport.get_transaction(root_hash, None, None).await? else {
+                return Err(TransportError::new_err("Root transaction not found"));
+            };
+
+            self.observe(None, 0, &tx)?;
+
+            self.root_hash = None;
+            if std::mem::take(&mut self.yield_root) {
+                return Ok(Some(tx));
+            }
+        }
+
+        let Some(message) = self.queue.front() else {
+            return Ok(None);
+        };
+
+        let mut interval_ms = MIN_INTERVAL_MS;
+        // NOTE: the message stays in the queue on errors,
+        // so the next call continues from the same message
+        let tx = loop {
+            if let Some(tx) = transport
+                .get_dst_transaction(&message.hash, None, None)
+                .await?
+            {
+                break tx;
+            }
+
+            tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+            interval_ms = std::cmp::min(interval_ms * FACTOR, MAX_INTERVAL_MS);
+        };
+
+        let Some(message) = self.queue.pop_front() else {
+            return Ok(None);
+        };
+        self.observe(Some(message.parent), message.depth, &tx)?;
+
+        Ok(Some(tx))
+    }
+
+    /// Builds a tree from all observed transactions.
+    fn build_tree(&self, py: Python<'_>) -> PyResult<Py<TransactionTree>> {
+        let mut children = FastHashMap::<ton_types::UInt256, Vec<Py<TransactionTree>>>::default();
+
+        // Children are always observed after their parents
+        for (parent, tx) in self.observed.iter().rev() {
+            let mut node_children = children.remove(&tx.hash).unwrap_or_default();
+            node_children.reverse();
+
+            let node = TransactionTree::new(py, Transaction::try_from(tx.clone())?, node_children)?;
+            match parent {
+                Some(parent) => children.entry(*parent).or_default().push(node),
+                None => return Ok(node),
+            }
+        }
+
+        Err(TransportError::new_err("Root transaction not found"))
+    }
+}
+
+#[pymethods]
+impl TraceTransaction {
+    #[getter]
+    fn truncated(&self) -> bool {
+        self.truncated.load(Ordering::Acquire)
+    }
+
+    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.state.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+            state.yield_root = false;
+            state.root_hash = None;
+            state.root = None;
+            state.queue.clear();
+            Ok(())
+        })
+    }
+
+    fn wait<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.state.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+            state.yield_root = false;
+            state.root = None;
+            while state.next().await?.is_some() {}
+            Ok(())
+        })
+    }
+
+    fn tree<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let state = self.state.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+            state.yield_root = false;
+            state.root = None;
+            while state.next().await?.is_some() {}
+            Python::with_gil(|py| state.build_tree(py))
+        })
+    }
+
+    fn __aenter__<'a>(slf: PyRef<'a, Self>, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let slf = slf.into_py(py);
+        pyo3_asyncio::tokio::future_into_py(py, async move { Ok(slf) })
+    }
+
+    fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        _exc_type: &'a PyAny,
+        _exc_value: &'a PyAny,
+        _traceback: &'a PyAny,
+    ) -> PyResult<&'a PyAny> {
+        self.close(py)
+    }
+
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'a>(&'a mut self, py: Python<'a>) -> PyResult<Option<&'a PyAny>> {
+        let state = self.state.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+
+            if let Some(root) = state.root.take() {
+                state.yield_root = false;
+                return Ok(root);
+            }
+
+            match state.next().await? {
+                Some(tx) => {
+                    let tx = Transaction::try_from(tx)?;
+                    Python::with_gil(|py| Ok(tx.into_py(py)))
+                }
+                None => Err(PyStopAsyncIteration::new_err(())),
+            }
+        })
+        .map(Some)
+    }
+}
+
+#[pyclass(get_all)]
+pub struct TransactionsBatchInfo {
+    min_lt: u64,
+    max_lt: u64,
+    gap: bool,
+}
+
+#[pymethods]
+impl TransactionsBatchInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "<TransactionsBatchInfo min_lt={}, max_lt={}, gap={}>",
+            self.min_lt,
+            self.max_lt,
+            DisplayBool(self.gap)
+        )
+    }
+}
+
+pyo3::create_exception!(
+    nekoton,
+    SubscriptionLagged,
+    NekotonError,
+    "Transactions iterator fell behind and skipped some batches."
+);
+
+/// Subscription settings.
+#[derive(Clone)]
+#[pyclass]
+pub struct SubscriptionOptions {
+    interval: Duration,
+    short_interval: Duration,
+    capacity: usize,
+    lag_mode: LagMode,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            short_interval: Duration::from_secs(1),
+            capacity: 10,
+            lag_mode: LagMode::Raise,
+        }
+    }
+}
+
+#[pymethods]
+impl SubscriptionOptions {
+    /// Creates new subscription options. All durations are in seconds.
+    #[new]
+    fn new(
+        interval: Option<f64>,
+        short_interval: Option<f64>,
+        capacity: Option<usize>,
+        lag_mode: Option<&str>,
+    ) -> PyResult<Self> {
+        let default = Self::default();
+
+        let interval = match interval {
+            Some(interval) => Duration::try_from_secs_f64(interval).handle_value_error()?,
+            None => default.interval,
+        };
+        let short_interval = match short_interval {
+            Some(interval) => Duration::try_from_secs_f64(interval).handle_value_error()?,
+            None => default.short_interval,
+        };
+        if interval.is_zero() || short_interval.is_zero() {
+            return Err(PyValueError::new_err("Intervals must be greater than 0"));
+        }
+
+        let capacity = capacity.unwrap_or(default.capacity);
+        if capacity == 0 {
+            return Err(PyValueError::new_err("Capacity must be greater than 0"));
+        }
+
+        let lag_mode = match lag_mode {
+            Some(lag_mode) => lag_mode.parse()?,
+            None => default.lag_mode,
+        };
+
+        Ok(Self {
+            interval,
+            short_interval,
+            capacity,
+            lag_mode,
+        })
+    }
+
+    #[getter]
+    fn interval(&self) -> f64 {
+        self.interval.as_secs_f64()
+    }
+
+    #[getter]
+    fn short_interval(&self) -> f64 {
+        self.short_interval.as_secs_f64()
+    }
+
+    #[getter]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[getter]
+    fn lag_mode(&self) -> &'static str {
+        match self.lag_mode {
+            LagMode::Raise => "raise",
+            LagMode::Gap => "gap",
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SubscriptionOptions(interval={}, short_interval={}, capacity={}, lag_mode={})",
+            self.interval(),
+            self.short_interval(),
+            self.capacity,
+            self.lag_mode(),
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum LagMode {
+    /// Raise `SubscriptionLagged` with the skipped range.
+    Raise,
+    /// Yield an empty batch with the skipped range.
+    Gap,
+}
+
+impl FromStr for LagMode {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raise" => Ok(Self::Raise),
+            "gap" => Ok(Self::Gap),
+            _ => Err(PyValueError::new_err("Unknown lag mode")),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+#[pyclass]
+pub struct Clock(pub Arc<ClockState>);
+
+#[pymethods]
+impl Clock {
+    /// Creates a new clock with the specified offset in milliseconds.
+    #[new]
+    pub fn new(offset: Option<i64>, frozen_at_ms: Option<u64>) -> Self {
+        let clock = Self(Default::default());
+        clock.0.inner.update_offset(offset.unwrap_or_default());
+        clock.set_frozen_at_ms(frozen_at_ms);
+        clock
+    }
+
+    #[getter]
+    pub fn now_sec(&self) -> u64 {
+        nt::utils::Clock::now_sec_u64(self.0.as_ref())
+    }
+
+    #[getter]
+    pub fn now_ms(&self) -> u64 {
+        nt::utils::Clock::now_ms_u64(self.0.as_ref())
+    }
+
+    #[getter]
+    pub fn get_offset(&self) -> i64 {
+        self.0.inner.offset_ms()
+    }
+
+    #[setter]
+    pub fn set_offset(&self, offset: i64) {
+        self.0.inner.update_offset(offset)
+    }
+
+    #[getter]
+    pub fn get_frozen_at_ms(&self) -> Option<u64> {
+        self.0.frozen_at_ms()
+    }
+
+    #[setter]
+    pub fn set_frozen_at_ms(&self, frozen_at_ms: Option<u64>) {
+        self.0
+            .frozen_at_ms
+            .store(frozen_at_ms.unwrap_or_default(), Ordering::Release);
+    }
+
+    fn __repr__(&self) -> String {
+        match self.get_frozen_at_ms() {
+            Some(frozen_at_ms) => format!("Clock(frozen_at_ms={frozen_at_ms})"),
+            None => format!("Clock(offset={})", self.get_offset()),
+        }
+    }
+}
+
+/// Clock with an adjustable offset which can be stopped at a fixed time.
+#[derive(Default)]
+pub struct ClockState {
+    inner: nt::utils::ClockWithOffset,
+    /// Fixed time in milliseconds, zero when the clock is running.
+    frozen_at_ms: AtomicU64,
+}
+
+impl ClockState {
+    pub fn frozen_at_ms(&self) -> Option<u64> {
+        match self.frozen_at_ms.load(Ordering::Acquire) {
+            0 => None,
+            frozen_at_ms => Some(frozen_at_ms),
+        }
+    }
+
+    /// Moves the stopped clock forward. Does nothing for the running clock.
+    pub fn advance_frozen(&self, now_ms: u64) {
+        _ = self
+            .frozen_at_ms
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |frozen_at_ms| {
+                (frozen_at_ms != 0 && frozen_at_ms < now_ms).then_some(now_ms)
+            });
+    }
+}
+
+impl nt::utils::Clock for ClockState {
+    fn now_sec_u64(&self) -> u64 {
+        self.now_ms_u64() / 1000
+    }
+
+    fn now_ms_f64(&self) -> f64 {
+        match self.frozen_at_ms() {
+            Some(frozen_at_ms) => frozen_at_ms as f64,
+            None => self.inner.now_ms_f64(),
+        }
+    }
+
+    fn now_ms_u64(&self) -> u64 {
+        match self.frozen_at_ms() {
+            Some(frozen_at_ms) => frozen_at_ms,
+            None => self.inner.now_ms_u64(),
+        }
+    }
+}
+
+impl<'a> AsRef<dyn nt::utils::Clock + 'a> for Clock {
+    fn as_ref(&self) -> &(dyn nt::utils::Clock + 'a) {
+        self.0.as_ref()
+    }
+}
+
+/// Retry policy for transport requests.
+#[derive(Clone)]
+#[pyclass]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: f64,
+    retry_on: Option<Vec<String>>,
+}
+
+impl RetryPolicy {
+    const NONE: Self = Self {
+        max_attempts: 1,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        multiplier: 1.0,
+        jitter: 0.0,
+        retry_on: None,
+    };
+
+    async fn run<T, F, Fut>(&self, f: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        self.run_if(|e| self.is_retryable(e), f).await
+    }
+
+    /// Same as `run`, but with a custom retry condition.
+    async fn run_if<T, R, F, Fut>(&self, is_retryable: R, mut f: F) -> anyhow::Result<T>
+    where
+        R: Fn(&anyhow::Error) -> bool,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let mut attempt = 1;
+        let mut backoff = self.initial_backoff;
+        loop {
+            match f().await {
+                Ok(result) => return Ok(result),
+                Err(e) if attempt < self.max_attempts && is_retryable(&e) => {
+                    log::warn!("Request failed (attempt {attempt}): {e:?}");
+
+                    tokio::time::sleep(self.apply_jitter(backoff)).await;
+                    backoff = std::cmp::min(backoff.mul_f64(self.multiplier), self.max_backoff);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn is_retryable(&self, error: &anyhow::Error) -> bool {
+        match &self.retry_on {
+            None => is_transport_level_error(error),
+            Some(patterns) => {
+                let error = format!("{error:#}");
+                patterns.iter().any(|pattern| error.contains(pattern))
+            }
+        }
+    }
+
+    fn apply_jitter(&self, backoff: Duration) -> Duration {
+        if self.jitter <= 0.0 {
+            return backoff;
+        }
+        let factor = rand::thread_rng().gen_range(-self.jitter..=self.jitter);
+        backoff.mul_f64((1.0 + factor).max(0.0))
+    }
+}
+
+#[pymethods]
+impl RetryPolicy {
+    /// Creates a new retry policy. All durations are in seconds.
+    #[new]
+    fn new(
+        max_attempts: Option<u32>,
+        initial_backoff: Option<f64>,
+        max_backoff: Option<f64>,
+        multiplier: Option<f64>,
+        jitter: Option<f64>,
+        retry_on: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let max_attempts = max_attempts.unwrap_or(3);
+        if max_attempts == 0 {
+            return Err(PyValueError::new_err("Max attempts must be greater than 0"));
+        }
+
+        let multiplier = multiplier.unwrap_or(2.0);
+        if !multiplier.is_finite() || multiplier < 1.0 {
+            return Err(PyValueError::new_err("Invalid backoff multiplier"));
+        }
+
+        let jitter = jitter.unwrap_or(0.1);
+        if !(0.0..=1.0).contains(&jitter) {
+            return Err(PyValueError::new_err("Jitter must be in range [0, 1]"));
+        }
 
-        queue.extend(hashes);
-        Ok(())
-    }
+        let initial_backoff =
+            Duration::try_from_secs_f64(initial_backoff.unwrap_or(0.5)).handle_value_error()?;
+        let max_backoff =
+            Duration::try_from_secs_f64(max_backoff.unwrap_or(10.0)).handle_value_error()?;
+        if max_backoff < initial_backoff {
+            return Err(PyValueError::new_err(
+                "Max backoff must not be less than initial backoff",
+            ));
+        }
 
-    async fn next(&mut self) -> PyResult<Option<nt::transport::models::RawTransaction>> {
-        const MIN_INTERVAL_MS: u64 = 500;
-        const MAX_INTERVAL_MS: u64 = 3000;
-        const FACTOR: u64 = 2;
+        Ok(Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff,
+            multiplier,
+            jitter,
+            retry_on,
+        })
+    }
 
-        let transport = self.transport.handle.as_ref();
+    #[getter]
+    fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
 
-        if let Some(root_hash) = &self.root_hash {
-            let Some(tx) = transport
-                .get_transaction(root_hash)
-                .await
-                .handle_runtime_error()?
-            else {
-                return Err(PyRuntimeError::new_err("Root transaction not found"));
-            };
+    #[getter]
+    fn initial_backoff(&self) -> f64 {
+        self.initial_backoff.as_secs_f64()
+    }
 
-            Self::extract_messages(&tx.data, &mut self.queue)?;
+    #[getter]
+    fn max_backoff(&self) -> f64 {
+        self.max_backoff.as_secs_f64()
+    }
 
-            self.root_hash = None;
-            if std::mem::take(&mut self.yield_root) {
-                return Ok(Some(tx));
-            }
-        }
+    #[getter]
+    fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
 
-        let Some(message_hash) = self.queue.front() else {
-            return Ok(None);
-        };
+    #[getter]
+    fn jitter(&self) -> f64 {
+        self.jitter
+    }
 
-        let mut interval_ms = MIN_INTERVAL_MS;
-        let tx = loop {
-            if let Ok(Some(tx)) = transport.get_dst_transaction(message_hash).await {
-                break tx;
-            }
+    #[getter]
+    fn retry_on(&self) -> Option<Vec<String>> {
+        self.retry_on.clone()
+    }
 
-            tokio::time::sleep(Duration::from_millis(interval_ms)).await;
-            interval_ms = std::cmp::min(interval_ms * FACTOR, MAX_INTERVAL_MS);
-        };
+    fn __repr__(&self) -> String {
+        format!(
+            "RetryPolicy(max_attempts={}, initial_backoff={}, max_backoff={}, multiplier={}, jitter={}, retry_on={:?})",
+            self.max_attempts,
+            self.initial_backoff(),
+            self.max_backoff(),
+            self.multiplier,
+            self.jitter,
+            self.retry_on,
+        )
+    }
+}
 
-        Self::extract_messages(&tx.data, &mut self.queue)?;
-        self.queue.pop_front();
+/// Transport cache settings.
+#[derive(Clone)]
+#[pyclass]
+pub struct CacheConfig {
+    account_state_ttl: Duration,
+    account_states_capacity: usize,
+    transactions_capacity: usize,
+    blockchain_config_ttl: Duration,
+}
 
-        Ok(Some(tx))
+impl CacheConfig {
+    fn build(&self) -> cache::TransportCache {
+        cache::TransportCache::new(
+            self.account_state_ttl,
+            self.account_states_capacity,
+            self.transactions_capacity,
+            self.blockchain_config_ttl,
+        )
     }
 }
 
 #[pymethods]
-impl TraceTransaction {
-    fn close<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
-        let state = self.0.clone();
-        pyo3_asyncio::tokio::future_into_py(py, async move {
-            let mut state = state.lock().await;
-            state.yield_root = false;
-            state.root_hash = None;
-            state.root = None;
-            state.queue.clear();
-            Ok(())
-        })
-    }
+impl CacheConfig {
+    /// Creates a new cache config. All durations are in seconds.
+    #[new]
+    fn new(
+        account_state_ttl: Option<f64>,
+        account_states_capacity: Option<usize>,
+        transactions_capacity: Option<usize>,
+        blockchain_config_ttl: Option<f64>,
+    ) -> PyResult<Self> {
+        let account_state_ttl =
+            Duration::try_from_secs_f64(account_state_ttl.unwrap_or(1.0)).handle_value_error()?;
+        let blockchain_config_ttl =
+            Duration::try_from_secs_f64(blockchain_config_ttl.unwrap_or(60.0))
+                .handle_value_error()?;
+
+        let account_states_capacity = account_states_capacity.unwrap_or(1000);
+        let transactions_capacity = transactions_capacity.unwrap_or(10000);
+        if account_states_capacity == 0 || transactions_capacity == 0 {
+            return Err(PyValueError::new_err(
+                "Cache capacity must be greater than 0",
+            ));
+        }
 
-    fn wait<'a>(&self, py: Python<'a>) -> PyResult<&'a PyAny> {
-        let state = self.0.clone();
-        pyo3_asyncio::tokio::future_into_py(py, async move {
-            let mut state = state.lock().await;
-            state.yield_root = false;
-            state.root = None;
-            while state.next().await?.is_some() {}
-            Ok(())
+        Ok(Self {
+            account_state_ttl,
+            account_states_capacity,
+            transactions_capacity,
+            blockchain_config_ttl,
         })
     }
 
-    fn __aenter__<'a>(slf: PyRef<'a, Self>, py: Python<'a>) -> PyResult<&'a PyAny> {
-        let slf = slf.into_py(py);
-        pyo3_asyncio::tokio::future_into_py(py, async move { Ok(slf) })
+    #[getter]
+    fn account_state_ttl(&self) -> f64 {
+        self.account_state_ttl.as_secs_f64()
     }
 
-    fn __aexit__<'a>(
-        &self,
-        py: Python<'a>,
-        _exc_type: &'a PyAny,
-        _exc_value: &'a PyAny,
-        _traceback: &'a PyAny,
-    ) -> PyResult<&'a PyAny> {
-        self.close(py)
+    #[getter]
+    fn account_states_capacity(&self) -> usize {
+        self.account_states_capacity
     }
 
-    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
-        slf
+    #[getter]
+    fn transactions_capacity(&self) -> usize {
+        self.transactions_capacity
     }
 
-    pub fn __anext__<'a>(&'a mut self, py: Python<'a>) -> PyResult<Option<&'a PyAny>> {
-        let state = self.0.clone();
-        pyo3_asyncio::tokio::future_into_py(py, async move {
-            let mut state = state.lock().await;
-            if let Some(root) = state.root.take() {
-                state.yield_root = false;
-                return Ok(root);
-            }
+    #[getter]
+    fn blockchain_config_ttl(&self) -> f64 {
+        self.blockchain_config_ttl.as_secs_f64()
+    }
 
-            match state.next().await? {
-                Some(tx) => {
-                    let tx = Transaction::try_from(tx)?;
-                    Python::with_gil(|py| Ok(tx.into_py(py)))
-                }
-                None => Err(PyStopAsyncIteration::new_err(())),
-            }
-        })
-        .map(Some)
+    fn __repr__(&self) -> String {
+        format!(
+            "CacheConfig(account_state_ttl={}, account_states_capacity={}, transactions_capacity={}, blockchain_config_ttl={})",
+            self.account_state_ttl(),
+            self.account_states_capacity,
+            self.transactions_capacity,
+            self.blockchain_config_ttl(),
+        )
     }
 }
 
+/// Transport cache hit/miss counters.
+#[derive(Copy, Clone)]
 #[pyclass(get_all)]
-pub struct TransactionsBatchInfo {
-    min_lt: u64,
-    max_lt: u64,
+pub struct CacheStats {
+    hits: u64,
+    misses: u64,
 }
 
 #[pymethods]
-impl TransactionsBatchInfo {
+impl CacheStats {
     fn __repr__(&self) -> String {
-        format!(
-            "<TransactionsBatchInfo min_lt={}, max_lt={}>",
-            self.min_lt, self.max_lt
-        )
+        format!("CacheStats(hits={}, misses={})", self.hits, self.misses)
     }
 }
 
-#[derive(Default, Clone)]
+/// Result of the local emulation of an external message.
 #[pyclass]
-pub struct Clock(pub Arc<nt::utils::ClockWithOffset>);
+pub struct EstimatedFees {
+    transaction: Transaction,
+    out_msgs: Vec<Message>,
+}
 
 #[pymethods]
-impl Clock {
-    /// Creates a new clock with the specified offset in milliseconds.
-    #[new]
-    pub fn new(offset: Option<i64>) -> Self {
-        Self(Arc::new(nt::utils::ClockWithOffset::new(
-            offset.unwrap_or_default(),
-        )))
+impl EstimatedFees {
+    #[getter]
+    fn total_fees(&self) -> Tokens {
+        self.transaction.total_fees()
     }
 
     #[getter]
-    pub fn now_sec(&self) -> u64 {
-        nt::utils::Clock::now_sec_u64(self.0.as_ref())
+    fn gas_used(&self) -> PyResult<Option<u64>> {
+        let compute_phase = self.transaction.compute_phase()?;
+        Ok(compute_phase.map(|phase| phase.gas_used()))
     }
 
     #[getter]
-    pub fn now_ms(&self) -> u64 {
-        nt::utils::Clock::now_ms_u64(self.0.as_ref())
+    fn compute_phase(&self) -> PyResult<Option<TransactionComputePhase>> {
+        self.transaction.compute_phase()
     }
 
     #[getter]
-    pub fn get_offset(&self) -> i64 {
-        self.0.offset_ms()
+    fn action_phase(&self) -> PyResult<Option<TransactionActionPhase>> {
+        self.transaction.action_phase()
     }
 
-    #[setter]
-    pub fn set_offset(&self, offset: i64) {
-        self.0.update_offset(offset)
+    #[getter]
+    fn out_msgs(&self) -> Vec<Message> {
+        self.out_msgs.clone()
     }
 
-    fn __repr__(&self) -> String {
-        format!("Clock(offset={})", self.get_offset())
+    #[getter]
+    fn transaction(&self) -> Transaction {
+        self.transaction.clone()
     }
-}
 
-impl<'a> AsRef<dyn nt::utils::Clock + 'a> for Clock {
-    fn as_ref(&self) -> &(dyn nt::utils::Clock + 'a) {
-        self.0.as_ref()
+    fn __repr__(&self) -> PyResult<String> {
+        let gas_used = match self.gas_used()? {
+            Some(gas_used) => gas_used.to_string(),
+            None => "None".to_owned(),
+        };
+        Ok(format!(
+            "<EstimatedFees total_fees={}, gas_used={}, out_msgs={}>",
+            self.total_fees(),
+            gas_used,
+            self.out_msgs.len()
+        ))
     }
 }
 
@@ -1268,6 +4161,12 @@ pub enum TransportHandle {
     GraphQl(Arc<nt::transport::gql::GqlTransport>),
     Jrpc(Arc<nt::transport::jrpc::JrpcTransport>),
     Proto(Arc<nt::transport::proto::ProtoTransport>),
+    Failover(Arc<failover::FailoverTransport>),
+    Local(Arc<local::LocalBlockchain>),
+    Custom(Arc<custom::PythonTransport>),
+    Recording(Arc<recording::RecordingTransport>),
+    Replay(Arc<recording::ReplayTransport>),
+    Cached(Arc<cache::CachedTransport>),
 }
 
 impl<'a> AsRef<dyn nt::transport::Transport + 'a> for TransportHandle {
@@ -1276,6 +4175,12 @@ impl<'a> AsRef<dyn nt::transport::Transport + 'a> for TransportHandle {
             Self::GraphQl(transport) => transport.as_ref(),
             Self::Jrpc(transport) => transport.as_ref(),
             Self::Proto(transport) => transport.as_ref(),
+            Self::Failover(transport) => transport.as_ref(),
+            Self::Local(transport) => transport.as_ref(),
+            Self::Custom(transport) => transport.as_ref(),
+            Self::Recording(transport) => transport.as_ref(),
+            Self::Replay(transport) => transport.as_ref(),
+            Self::Cached(transport) => transport.as_ref(),
         }
     }
 }
@@ -1286,11 +4191,26 @@ impl From<TransportHandle> for Arc<dyn nt::transport::Transport> {
             TransportHandle::GraphQl(transport) => transport,
             TransportHandle::Jrpc(transport) => transport,
             TransportHandle::Proto(transport) => transport,
+            TransportHandle::Failover(transport) => transport,
+            TransportHandle::Local(transport) => transport,
+            TransportHandle::Custom(transport) => transport,
+            TransportHandle::Recording(transport) => transport,
+            TransportHandle::Replay(transport) => transport,
+            TransportHandle::Cached(transport) => transport,
         }
     }
 }
 
 impl TransportHandle {
+    /// Whether sent messages are processed immediately by the transport.
+    fn is_local(&self) -> bool {
+        match self {
+            Self::Local(_) => true,
+            Self::Cached(transport) => transport.inner().is_local(),
+            _ => false,
+        }
+    }
+
     pub async fn check_connection(&self) -> PyResult<()> {
         let transport = self.as_ref();
         if transport.info().has_key_blocks {
@@ -1307,7 +4227,7 @@ impl TransportHandle {
                     .unwrap(),
             )
             .await
-            .handle_runtime_error()?;
+            .map_err(handle_transport_error)?;
         Ok(())
     }
 
@@ -1321,15 +4241,37 @@ impl TransportHandle {
         self.as_ref()
             .get_accounts_by_code_hash(&GIVER_CODE_HASH, 1, &None)
             .await
-            .handle_runtime_error()?;
+            .map_err(handle_transport_error)?;
         Ok(())
     }
 }
 
+fn poll_contract_state_from(
+    state: nt::transport::models::RawContractState,
+    last_trans_lt: u64,
+) -> nt::transport::models::PollContractState {
+    use nt::transport::models::{PollContractState, RawContractState};
+
+    match state {
+        RawContractState::NotExists { timings } => PollContractState::NotExists { timings },
+        RawContractState::Exists(contract)
+            if contract.account.storage.last_trans_lt == last_trans_lt =>
+        {
+            PollContractState::Unchanged {
+                timings: contract.timings,
+            }
+        }
+        RawContractState::Exists(contract) => PollContractState::Changed(contract),
+    }
+}
+
 struct SharedSubscription {
     address: ton_block::MsgAddressInt,
+    clock: Clock,
     state: SubscriptionState,
     skip_iteration_signal: Arc<Notify>,
+    /// Whether sent messages are processed immediately by the transport.
+    poll_after_send: bool,
     subscription: tokio::sync::Mutex<nt::core::ContractSubscription>,
 }
 
@@ -1337,39 +4279,73 @@ impl SharedSubscription {
     async fn subscribe(
         clock: Clock,
         transport: TransportHandle,
+        block_walker: Option<Arc<block_walking::BlockWalker>>,
+        options: &SubscriptionOptions,
         address: ton_block::MsgAddressInt,
     ) -> PyResult<Arc<Self>> {
-        const TX_CAPACITY: usize = 10;
-
         let (account_state, _) = watch::channel(py_none());
-        let (transactions_tx, _) = broadcast::channel(TX_CAPACITY);
+        let (transactions_tx, _) = broadcast::channel(options.capacity);
 
         let state = SubscriptionState {
             account_state,
             transactions: transactions_tx,
+            last_lt: Default::default(),
             pending_messages: Default::default(),
         };
 
-        let subscription = tokio::sync::Mutex::new(
-            nt::core::ContractSubscription::subscribe(
-                clock.0,
-                transport.into(),
-                address.clone(),
-                &mut |account_state| state.on_state_changed(account_state.clone()),
-                None,
-            )
-            .await
-            .handle_runtime_error()?,
-        );
+        // NOTE: the starting block is captured before the initial state is fetched,
+        // so transactions between them are not missed
+        let block_walking = match block_walker {
+            Some(block_walker) => {
+                let start_block_id = block_walker
+                    .get_latest_block_id(&address)
+                    .await
+                    .map_err(handle_transport_error)?;
+                Some((block_walker, start_block_id))
+            }
+            None => None,
+        };
+
+        let subscription = nt::core::ContractSubscription::subscribe(
+            clock.0.clone(),
+            transport.clone().into(),
+            address.clone(),
+            &mut |account_state| state.on_state_changed(account_state.clone()),
+            None,
+        )
+        .await
+        .map_err(handle_transport_error)?;
+        state
+            .last_lt
+            .store(subscription.contract_state().last_lt, Ordering::Release);
+        let subscription = tokio::sync::Mutex::new(subscription);
 
         let shared = Arc::new(SharedSubscription {
             address,
+            clock,
             state,
             skip_iteration_signal: Arc::new(Default::default()),
+            poll_after_send: transport.is_local(),
             subscription,
         });
 
-        tokio::spawn(subscription_loop(shared.clone()));
+        match block_walking {
+            Some((block_walker, start_block_id)) => {
+                tokio::spawn(block_walking_loop(
+                    shared.clone(),
+                    transport,
+                    block_walker,
+                    start_block_id,
+                ));
+            }
+            None => {
+                tokio::spawn(subscription_loop(
+                    shared.clone(),
+                    options.interval,
+                    options.short_interval,
+                ));
+            }
+        }
 
         Ok(shared)
     }
@@ -1378,51 +4354,131 @@ impl SharedSubscription {
         &self,
         message: &Message,
         expire_at: u32,
+        retry_policy: Option<&RetryPolicy>,
     ) -> PyResult<Option<Transaction>> {
+        let mut rx = self.broadcast(message, expire_at, retry_policy).await?;
+
+        // NOTE: the future can be dropped while waiting (on timeout or
+        // when the Python task is cancelled), so the entry is removed by the guard.
+        let _guard = PendingMessageGuard {
+            pending_messages: &self.state.pending_messages,
+            message_hash: message.hash,
+        };
+
+        wait_pending_message(&mut rx).await
+    }
+
+    /// Sends the message and registers it as pending.
+    ///
+    /// The entry is removed when the transaction is found or the message expires.
+    async fn broadcast(
+        &self,
+        message: &Message,
+        expire_at: u32,
+        retry_policy: Option<&RetryPolicy>,
+    ) -> PyResult<oneshot::Receiver<ReceivedTransaction>> {
         use dashmap::mapref::entry;
 
         let (tx, rx) = oneshot::channel();
         match self.state.pending_messages.entry(message.hash) {
-            entry::Entry::Occupied(_) => return Err(PyRuntimeError::new_err("Duplicate message")),
+            entry::Entry::Occupied(_) => return Err(TransportError::new_err("Duplicate message")),
             entry::Entry::Vacant(entry) => {
-                entry.insert(tx);
+                entry.insert(PendingEntry {
+                    tx,
+                    message: message.clone(),
+                    expire_at,
+                    tracked: false,
+                });
             }
         }
 
-        let pending_message = {
-            let mut subscription = self.subscription.lock().await;
-            subscription.send(&message.data, expire_at).await
+        // NOTE: the future can be dropped at any await point (on timeout or
+        // when the Python task is cancelled), so the entry is removed by the guard.
+        let guard = PendingMessageGuard {
+            pending_messages: &self.state.pending_messages,
+            message_hash: message.hash,
         };
 
+        // NOTE: the message is only sent again when the previous attempt
+        // has not reached the endpoint, otherwise it could be broadcasted twice
+        let retry_policy = retry_policy.unwrap_or(&RetryPolicy::NONE);
+        let pending_message = retry_policy
+            .run_if(is_connection_error, || async {
+                let mut subscription = self.subscription.lock().await;
+                subscription.send(&message.data, expire_at).await
+            })
+            .await;
+
         match pending_message {
             Ok(tx) => {
                 if tx.message_hash != message.hash {
                     // TODO: panic instead?
-                    self.state.pending_messages.remove(&message.hash);
-                    return Err(PyRuntimeError::new_err("Pending message mismatch"));
+                    return Err(TransportError::new_err("Pending message mismatch"));
+                }
+
+                // NOTE: the local transport executes the message while sending it,
+                // so the account is polled right away instead of waiting for the
+                // next interval. Other transports would only waste a request here.
+                if self.poll_after_send {
+                    self.skip_iteration_signal.notify_one();
                 }
             }
-            Err(e) => {
-                self.state.pending_messages.remove(&message.hash);
-                return Err(e).handle_runtime_error();
-            }
+            Err(e) => return Err(handle_transport_error(e)),
         }
 
-        let result = rx.await.handle_runtime_error();
-        self.state.pending_messages.remove(&message.hash);
+        guard.disarm();
+        Ok(rx)
+    }
+
+    /// Registers the message as pending without sending it.
+    ///
+    /// The entry is resolved by the incoming transactions of the account
+    /// and expires after the first refresh past its expiration timestamp.
+    fn track(
+        &self,
+        message: &Message,
+        expire_at: u32,
+    ) -> PyResult<oneshot::Receiver<ReceivedTransaction>> {
+        use dashmap::mapref::entry;
 
-        match result.handle_runtime_error()? {
-            ReceivedTransaction::Expired => Ok(None),
-            ReceivedTransaction::Invalid => {
-                // TODO: panic instead?
-                Err(PyRuntimeError::new_err("Failed to parse transaction"))
+        let (tx, rx) = oneshot::channel();
+        match self.state.pending_messages.entry(message.hash) {
+            entry::Entry::Occupied(_) => Err(TransportError::new_err("Duplicate message")),
+            entry::Entry::Vacant(entry) => {
+                entry.insert(PendingEntry {
+                    tx,
+                    message: message.clone(),
+                    expire_at,
+                    tracked: true,
+                });
+                Ok(rx)
             }
-            ReceivedTransaction::Valid(tx) => Ok(Some(tx)),
         }
     }
 }
 
-async fn subscription_loop(shared: Arc<SharedSubscription>) {
+async fn wait_pending_message(
+    rx: &mut oneshot::Receiver<ReceivedTransaction>,
+) -> PyResult<Option<Transaction>> {
+    let Ok(received) = rx.await else {
+        return Err(TransportError::new_err("Pending message cancelled"));
+    };
+
+    match received {
+        ReceivedTransaction::Expired => Ok(None),
+        ReceivedTransaction::Invalid => {
+            // TODO: panic instead?
+            Err(CellError::new_err("Failed to parse transaction"))
+        }
+        ReceivedTransaction::Valid(tx) => Ok(Some(tx)),
+    }
+}
+
+async fn subscription_loop(
+    shared: Arc<SharedSubscription>,
+    interval: Duration,
+    short_interval: Duration,
+) {
     fn split_shared(
         shared: Arc<SharedSubscription>,
     ) -> (
@@ -1437,16 +4493,13 @@ async fn subscription_loop(shared: Arc<SharedSubscription>) {
         )
     }
 
-    const INTERVAL: Duration = Duration::from_secs(5);
-    const SHORT_INTERVAL: Duration = Duration::from_secs(1);
-
     let (address, skip_iteration_signal, shared) = split_shared(shared);
 
     let mut polling_method = models::PollingMethod::Manual;
     loop {
         let interval = match polling_method {
-            models::PollingMethod::Manual => INTERVAL,
-            models::PollingMethod::Reliable => SHORT_INTERVAL,
+            models::PollingMethod::Manual => interval,
+            models::PollingMethod::Reliable => short_interval,
         };
 
         let signal = skip_iteration_signal.notified();
@@ -1460,8 +4513,7 @@ async fn subscription_loop(shared: Arc<SharedSubscription>) {
             return;
         };
 
-        // TODO: add support for block traversal
-
+        let refreshed_at = shared.clock.now_sec();
         let mut subscription = shared.subscription.lock().await;
         let res = subscription
             .refresh(
@@ -1476,18 +4528,99 @@ async fn subscription_loop(shared: Arc<SharedSubscription>) {
             )
             .await;
 
-        if let Err(e) = res {
-            log::error!("Subscription loop error for {address}: {e:?}");
+        match res {
+            Ok(()) => shared.state.expire_tracked_messages(refreshed_at),
+            Err(e) => log::error!("Subscription loop error for {address}: {e:?}"),
         }
 
         polling_method = subscription.polling_method();
     }
 }
 
+async fn block_walking_loop(
+    shared: Arc<SharedSubscription>,
+    transport: TransportHandle,
+    block_walker: Arc<block_walking::BlockWalker>,
+    start_block_id: String,
+) {
+    const NEXT_BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
+    const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+    let address = shared.address.clone();
+    let shared = Arc::downgrade(&shared);
+
+    // NOTE: transactions of the starting block are already included into the
+    // initial state, later blocks can also contain known transactions which
+    // are skipped by the subscription
+    let mut current_block_id = start_block_id;
+    loop {
+        if shared.strong_count() == 0 {
+            log::debug!("Stopped subscription for {address}");
+            return;
+        }
+
+        let res = block_walker
+            .wait_for_next_block(&current_block_id, &address, NEXT_BLOCK_TIMEOUT)
+            .await;
+        let next_block_id = match res {
+            Ok(id) => id,
+            Err(e) => {
+                log::error!("Failed to get next block for {address}: {e:?}");
+                tokio::time::sleep(RETRY_INTERVAL).await;
+                continue;
+            }
+        };
+
+        let block = match block_walker.get_block(&next_block_id).await {
+            Ok(block) => block,
+            Err(e) => {
+                log::error!("Failed to get block {next_block_id} for {address}: {e:?}");
+                tokio::time::sleep(RETRY_INTERVAL).await;
+                continue;
+            }
+        };
+
+        let Some(shared) = shared.upgrade() else {
+            log::debug!("Stopped subscription for {address}");
+            return;
+        };
+
+        let refreshed_at = shared.clock.now_sec();
+        let mut subscription = shared.subscription.lock().await;
+        let res = subscription.handle_block(
+            &block,
+            &mut |transactions, _| shared.state.on_transactions_found(transactions),
+            &mut |pending_transaction, transaction| {
+                shared
+                    .state
+                    .on_message_sent(pending_transaction, transaction)
+            },
+            &mut |pending_transaction| shared.state.on_message_expired(pending_transaction),
+        );
+        drop(subscription);
+
+        match res {
+            // Block contains new transactions for this account
+            Ok(Some(_)) => match transport.as_ref().get_contract_state(&address).await {
+                Ok(state) => shared.state.on_state_changed(state),
+                Err(e) => log::error!("Failed to refresh state for {address}: {e:?}"),
+            },
+            Ok(None) => {}
+            Err(e) => log::error!("Subscription loop error for {address}: {e:?}"),
+        }
+        if res.is_ok() {
+            shared.state.expire_tracked_messages(refreshed_at);
+        }
+
+        current_block_id = next_block_id;
+    }
+}
+
 struct SubscriptionState {
     account_state: watch::Sender<PyObject>,
-    transactions: broadcast::Sender<PyObject>,
-    pending_messages: FastDashMap<ton_types::UInt256, ResultTx>,
+    transactions: broadcast::Sender<TransactionsBatch>,
+    last_lt: AtomicU64,
+    pending_messages: FastDashMap<ton_types::UInt256, PendingEntry>,
 }
 
 impl SubscriptionState {
@@ -1500,6 +4633,27 @@ impl SubscriptionState {
     }
 
     fn on_transactions_found(&self, mut transactions: Vec<nt::transport::models::RawTransaction>) {
+        // Skip already emitted transactions
+        let last_lt = self.last_lt.load(Ordering::Acquire);
+        transactions.retain(|tx| tx.data.lt > last_lt);
+
+        // Resolve tracked messages which are not known to the contract subscription
+        for tx in &transactions {
+            let Some(msg_cell) = tx.data.in_msg_cell() else {
+                continue;
+            };
+            let message_hash = msg_cell.repr_hash();
+            if let Some((_, entry)) = self
+                .pending_messages
+                .remove_if(&message_hash, |_, entry| entry.tracked)
+            {
+                _ = entry.tx.send(match Transaction::try_from(tx.clone()) {
+                    Ok(transaction) => ReceivedTransaction::Valid(transaction),
+                    Err(_) => ReceivedTransaction::Invalid,
+                });
+            }
+        }
+
         // Arrange transactions in ascending order
         transactions.reverse();
 
@@ -1512,12 +4666,22 @@ impl SubscriptionState {
             (Some(first), Some(last)) => TransactionsBatchInfo {
                 min_lt: first.lt(),
                 max_lt: last.lt(),
+                gap: false,
             },
             _ => return,
         };
 
+        let (min_lt, max_lt) = (batch_info.min_lt, batch_info.max_lt);
+        self.last_lt.fetch_max(max_lt, Ordering::AcqRel);
+
         let value = Python::with_gil(|py| (transactions, batch_info).into_py(py));
-        self.transactions.send(value).ok();
+        self.transactions
+            .send(TransactionsBatch {
+                value,
+                min_lt,
+                max_lt,
+            })
+            .ok();
     }
 
     fn on_message_sent(
@@ -1525,31 +4689,91 @@ impl SubscriptionState {
         pending_transaction: models::PendingTransaction,
         transaction: nt::transport::models::RawTransaction,
     ) {
-        if let Some((_, tx)) = self
+        if let Some((_, entry)) = self
             .pending_messages
             .remove(&pending_transaction.message_hash)
         {
-            _ = tx.send(match Transaction::try_from(transaction) {
+            _ = entry.tx.send(match Transaction::try_from(transaction) {
                 Ok(transaction) => ReceivedTransaction::Valid(transaction),
                 Err(_) => ReceivedTransaction::Invalid,
             });
         }
     }
 
+    /// Expires tracked messages which could not be included after the refresh.
+    fn expire_tracked_messages(&self, refreshed_at: u64) {
+        // NOTE: the fetched state can lag behind the clock a bit
+        const EXPIRATION_MARGIN: u64 = 10;
+
+        let expired = self
+            .pending_messages
+            .iter()
+            .filter(|entry| {
+                entry.tracked && (entry.expire_at as u64 + EXPIRATION_MARGIN) < refreshed_at
+            })
+            .map(|entry| *entry.key())
+            .collect::<Vec<_>>();
+
+        for message_hash in expired {
+            if let Some((_, entry)) = self.pending_messages.remove(&message_hash) {
+                _ = entry.tx.send(ReceivedTransaction::Expired);
+            }
+        }
+    }
+
     fn on_message_expired(&self, pending_transaction: models::PendingTransaction) {
-        if let Some((_, tx)) = self
+        if let Some((_, entry)) = self
             .pending_messages
             .remove(&pending_transaction.message_hash)
         {
-            _ = tx.send(ReceivedTransaction::Expired);
+            _ = entry.tx.send(ReceivedTransaction::Expired);
         }
     }
 }
 
+#[derive(Clone)]
+struct TransactionsBatch {
+    value: PyObject,
+    min_lt: u64,
+    max_lt: u64,
+}
+
+struct PendingMessageGuard<'a> {
+    pending_messages: &'a FastDashMap<ton_types::UInt256, PendingEntry>,
+    message_hash: ton_types::UInt256,
+}
+
+impl PendingMessageGuard<'_> {
+    /// Keeps the entry in the map.
+    fn disarm(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for PendingMessageGuard<'_> {
+    fn drop(&mut self) {
+        self.pending_messages.remove(&self.message_hash);
+    }
+}
+
 enum ReceivedTransaction {
     Expired,
     Invalid,
     Valid(Transaction),
 }
 
-type ResultTx = oneshot::Sender<ReceivedTransaction>;
+struct PendingEntry {
+    tx: oneshot::Sender<ReceivedTransaction>,
+    message: Message,
+    expire_at: u32,
+    /// Whether the message was not sent by this subscription.
+    tracked: bool,
+}
+
+/// Serialized pending message.
+#[derive(Serialize, Deserialize)]
+struct StoredPendingMessage {
+    hash: String,
+    expire_at: u32,
+    boc: String,
+}
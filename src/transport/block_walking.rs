@@ -0,0 +1,83 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use quick_cache::sync::Cache;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum PollingMode {
+    /// Poll account states with a fixed interval.
+    #[default]
+    Polling,
+    /// Walk through shard blocks which contain the account.
+    BlockWalking,
+}
+
+impl PollingMode {
+    pub fn from_optional_param(mode: Option<&str>) -> PyResult<Self> {
+        match mode {
+            None => Ok(Self::default()),
+            Some(s) => s.parse(),
+        }
+    }
+}
+
+impl FromStr for PollingMode {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "polling" => Ok(Self::Polling),
+            "block_walking" => Ok(Self::BlockWalking),
+            _ => Err(PyValueError::new_err("Unknown polling mode")),
+        }
+    }
+}
+
+/// Block source shared by all subscriptions of the transport.
+///
+/// Subscriptions for accounts in the same shard wait for the same blocks,
+/// so each block is downloaded only once.
+pub struct BlockWalker {
+    transport: Arc<nt::transport::gql::GqlTransport>,
+    blocks: Cache<String, Arc<ton_block::Block>>,
+}
+
+impl BlockWalker {
+    pub fn new(transport: Arc<nt::transport::gql::GqlTransport>) -> Self {
+        const BLOCKS_CAPACITY: usize = 100;
+
+        Self {
+            transport,
+            blocks: Cache::new(BLOCKS_CAPACITY),
+        }
+    }
+
+    pub async fn get_latest_block_id(&self, address: &ton_block::MsgAddressInt) -> Result<String> {
+        let block = self.transport.get_latest_block(address).await?;
+        Ok(block.id)
+    }
+
+    pub async fn wait_for_next_block(
+        &self,
+        current: &str,
+        address: &ton_block::MsgAddressInt,
+        timeout: Duration,
+    ) -> Result<String> {
+        self.transport
+            .wait_for_next_block(current, address, timeout)
+            .await
+    }
+
+    pub async fn get_block(&self, id: &str) -> Result<Arc<ton_block::Block>> {
+        self.blocks
+            .get_or_insert_async(id, async {
+                let block = self.transport.get_block(id).await?;
+                Ok(Arc::new(block))
+            })
+            .await
+    }
+}
@@ -0,0 +1,270 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use nt::transport::models::{
+    NetworkCapabilities, PollContractState, RawContractState, RawTransaction, TransportInfo,
+};
+use nt::transport::Transport;
+use nt::utils::Clock;
+use parking_lot::Mutex;
+use quick_cache::sync::Cache;
+
+use super::TransportHandle;
+
+/// Request cache shared by all methods of the transport.
+pub struct TransportCache {
+    account_state_ttl: Duration,
+    blockchain_config_ttl: Duration,
+    account_states: Cache<ton_block::MsgAddressInt, (RawContractState, Instant)>,
+    transactions: Cache<ton_types::UInt256, RawTransaction>,
+    dst_transactions: Cache<ton_types::UInt256, RawTransaction>,
+    blockchain_config: Mutex<Option<(ton_executor::BlockchainConfig, Instant)>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TransportCache {
+    pub fn new(
+        account_state_ttl: Duration,
+        account_states_capacity: usize,
+        transactions_capacity: usize,
+        blockchain_config_ttl: Duration,
+    ) -> Self {
+        Self {
+            account_state_ttl,
+            blockchain_config_ttl,
+            account_states: Cache::new(account_states_capacity),
+            transactions: Cache::new(transactions_capacity),
+            dst_transactions: Cache::new(transactions_capacity),
+            blockchain_config: Default::default(),
+            hits: Default::default(),
+            misses: Default::default(),
+        }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    pub fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Option<RawContractState> {
+        let state = self
+            .account_states
+            .get(address)
+            .filter(|(_, updated_at)| updated_at.elapsed() < self.account_state_ttl)
+            .map(|(state, _)| state);
+        self.count(state)
+    }
+
+    pub fn store_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        state: &RawContractState,
+    ) {
+        if !self.account_state_ttl.is_zero() {
+            self.account_states
+                .insert(address.clone(), (state.clone(), Instant::now()));
+        }
+    }
+
+    pub fn get_transaction(&self, hash: &ton_types::UInt256) -> Option<RawTransaction> {
+        self.count(self.transactions.get(hash))
+    }
+
+    pub fn get_dst_transaction(&self, message_hash: &ton_types::UInt256) -> Option<RawTransaction> {
+        self.count(self.dst_transactions.get(message_hash))
+    }
+
+    pub fn store_transaction(&self, tx: &RawTransaction) {
+        self.transactions.insert(tx.hash, tx.clone());
+    }
+
+    pub fn store_dst_transaction(&self, message_hash: &ton_types::UInt256, tx: &RawTransaction) {
+        self.dst_transactions.insert(*message_hash, tx.clone());
+        self.store_transaction(tx);
+    }
+
+    pub fn get_blockchain_config(&self) -> Option<ton_executor::BlockchainConfig> {
+        let config = self
+            .blockchain_config
+            .lock()
+            .as_ref()
+            .filter(|(_, updated_at)| updated_at.elapsed() < self.blockchain_config_ttl)
+            .map(|(config, _)| config.clone());
+        self.count(config)
+    }
+
+    pub fn store_blockchain_config(&self, config: &ton_executor::BlockchainConfig) {
+        *self.blockchain_config.lock() = Some((config.clone(), Instant::now()));
+    }
+
+    fn count<T>(&self, value: Option<T>) -> Option<T> {
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+}
+
+/// Transport wrapper which shares the cache with the requests of subscriptions.
+///
+/// NOTE: polled states are always fetched from the inner transport
+/// so that subscriptions are not delayed by the cache.
+pub struct CachedTransport {
+    inner: TransportHandle,
+    cache: Arc<TransportCache>,
+}
+
+impl CachedTransport {
+    pub fn new(inner: TransportHandle, cache: Arc<TransportCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    pub fn inner(&self) -> &TransportHandle {
+        &self.inner
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for CachedTransport {
+    fn info(&self) -> TransportInfo {
+        self.inner.as_ref().info()
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        self.inner.as_ref().send_message(message).await
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<RawContractState> {
+        if let Some(state) = self.cache.get_contract_state(address) {
+            return Ok(state);
+        }
+
+        let state = self.inner.as_ref().get_contract_state(address).await?;
+        self.cache.store_contract_state(address, &state);
+        Ok(state)
+    }
+
+    async fn get_library_cell(&self, hash: &ton_types::UInt256) -> Result<Option<ton_types::Cell>> {
+        self.inner.as_ref().get_library_cell(hash).await
+    }
+
+    async fn poll_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        last_trans_lt: u64,
+    ) -> Result<PollContractState> {
+        let state = self
+            .inner
+            .as_ref()
+            .poll_contract_state(address, last_trans_lt)
+            .await?;
+        if let PollContractState::Changed(contract) = &state {
+            let contract = RawContractState::Exists(contract.clone());
+            self.cache.store_contract_state(address, &contract);
+        }
+        Ok(state)
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        self.inner
+            .as_ref()
+            .get_accounts_by_code_hash(code_hash, limit, continuation)
+            .await
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<RawTransaction>> {
+        let transactions = self
+            .inner
+            .as_ref()
+            .get_transactions(address, from_lt, count)
+            .await?;
+        transactions
+            .iter()
+            .for_each(|tx| self.cache.store_transaction(tx));
+        Ok(transactions)
+    }
+
+    async fn get_transaction(&self, id: &ton_types::UInt256) -> Result<Option<RawTransaction>> {
+        if let Some(tx) = self.cache.get_transaction(id) {
+            return Ok(Some(tx));
+        }
+
+        let tx = self.inner.as_ref().get_transaction(id).await?;
+        if let Some(tx) = &tx {
+            self.cache.store_transaction(tx);
+        }
+        Ok(tx)
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<RawTransaction>> {
+        if let Some(tx) = self.cache.get_dst_transaction(message_hash) {
+            return Ok(Some(tx));
+        }
+
+        let tx = self
+            .inner
+            .as_ref()
+            .get_dst_transaction(message_hash)
+            .await?;
+        if let Some(tx) = &tx {
+            self.cache.store_dst_transaction(message_hash, tx);
+        }
+        Ok(tx)
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        self.inner.as_ref().get_latest_key_block().await
+    }
+
+    async fn get_capabilities(&self, clock: &dyn Clock) -> Result<NetworkCapabilities> {
+        self.inner.as_ref().get_capabilities(clock).await
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        clock: &dyn Clock,
+        force: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        if !force {
+            if let Some(config) = self.cache.get_blockchain_config() {
+                return Ok(config);
+            }
+        }
+
+        let config = self
+            .inner
+            .as_ref()
+            .get_blockchain_config(clock, force)
+            .await?;
+        self.cache.store_blockchain_config(&config);
+        Ok(config)
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use nt::abi::{GenTimings, LastTransactionId};
+use nt::transport::models::{
+    ExistingContract, NetworkCapabilities, PollContractState, RawContractState, RawTransaction,
+    TransportInfo,
+};
+use nt::utils::Clock;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use ton_block::GetRepresentationHash;
+
+use crate::models::{AccountState, Address, BlockchainConfig, Message, Transaction};
+
+/// Transport which forwards all requests to the Python handler coroutines.
+pub struct PythonTransport {
+    handler: PyObject,
+    /// Event loop of the transport creator, used when the request is not
+    /// made from a Python task (e.g. by background subscription loops).
+    fallback_locals: pyo3_asyncio::TaskLocals,
+    max_transactions_per_fetch: u8,
+}
+
+impl PythonTransport {
+    pub fn new(
+        handler: PyObject,
+        fallback_locals: pyo3_asyncio::TaskLocals,
+        max_transactions_per_fetch: u8,
+    ) -> Self {
+        Self {
+            handler,
+            fallback_locals,
+            max_transactions_per_fetch,
+        }
+    }
+
+    async fn call<A, F, T>(&self, method: &str, args: A, extract: F) -> Result<T>
+    where
+        A: IntoPy<Py<PyTuple>> + Send,
+        F: FnOnce(&PyAny) -> PyResult<T> + Send,
+        T: Send,
+    {
+        let fut = Python::with_gil(|py| {
+            // NOTE: locals are resolved for each call so that the coroutine
+            // runs on the event loop of the caller
+            let locals = pyo3_asyncio::tokio::get_current_locals(py)
+                .unwrap_or_else(|_| self.fallback_locals.clone());
+
+            let coroutine = self.handler.as_ref(py).call_method1(method, args)?;
+            pyo3_asyncio::into_future_with_locals(&locals, coroutine)
+        })?;
+
+        let result = fut.await?;
+        Python::with_gil(|py| extract(result.as_ref(py))).map_err(From::from)
+    }
+
+    async fn get_account_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<Option<ton_block::AccountStuff>> {
+        self.call("get_account_state", (Address(address.clone()),), |result| {
+            let state = result.extract::<Option<PyRef<'_, AccountState>>>()?;
+            Ok(state.map(|state| state.0.clone()))
+        })
+        .await
+    }
+}
+
+fn extract_transaction(result: &PyAny) -> PyResult<Option<RawTransaction>> {
+    let tx = result.extract::<Option<Transaction>>()?;
+    Ok(tx.map(|tx| RawTransaction {
+        hash: tx.0.hash,
+        data: tx.0.data.clone(),
+    }))
+}
+
+#[async_trait::async_trait]
+impl nt::transport::Transport for PythonTransport {
+    fn info(&self) -> TransportInfo {
+        TransportInfo {
+            max_transactions_per_fetch: self.max_transactions_per_fetch,
+            reliable_behavior: nt::core::models::ReliableBehavior::IntensivePolling,
+            has_key_blocks: false,
+        }
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        let message = Message {
+            hash: message.hash()?,
+            data: message.clone(),
+        };
+        self.call("send_message", (message,), |_| Ok(())).await
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<RawContractState> {
+        Ok(match self.get_account_state(address).await? {
+            Some(account) => RawContractState::Exists(ExistingContract {
+                last_transaction_id: LastTransactionId::Inexact {
+                    latest_lt: account.storage.last_trans_lt,
+                },
+                account,
+                timings: GenTimings::Unknown,
+            }),
+            None => RawContractState::NotExists {
+                timings: GenTimings::Unknown,
+            },
+        })
+    }
+
+    async fn get_library_cell(&self, _: &ton_types::UInt256) -> Result<Option<ton_types::Cell>> {
+        Ok(None)
+    }
+
+    async fn poll_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        last_trans_lt: u64,
+    ) -> Result<PollContractState> {
+        let state = self.get_contract_state(address).await?;
+        Ok(super::poll_contract_state_from(state, last_trans_lt))
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        let code_hash = code_hash.as_slice().to_vec();
+        let continuation = continuation.clone().map(Address);
+        self.call(
+            "get_accounts_by_code_hash",
+            (code_hash, limit, continuation),
+            |result| {
+                let addresses = result.extract::<Vec<Address>>()?;
+                Ok(addresses.into_iter().map(|Address(addr)| addr).collect())
+            },
+        )
+        .await
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<RawTransaction>> {
+        self.call(
+            "get_transactions",
+            (Address(address.clone()), from_lt, count),
+            |result| {
+                let transactions = result.extract::<Vec<Transaction>>()?;
+                Ok(transactions
+                    .into_iter()
+                    .map(|tx| RawTransaction {
+                        hash: tx.0.hash,
+                        data: tx.0.data.clone(),
+                    })
+                    .collect())
+            },
+        )
+        .await
+    }
+
+    async fn get_transaction(&self, id: &ton_types::UInt256) -> Result<Option<RawTransaction>> {
+        let id = id.as_slice().to_vec();
+        self.call("get_transaction", (id,), extract_transaction)
+            .await
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<RawTransaction>> {
+        let message_hash = message_hash.as_slice().to_vec();
+        self.call("get_dst_transaction", (message_hash,), extract_transaction)
+            .await
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        anyhow::bail!("Key blocks are not supported by the custom transport")
+    }
+
+    async fn get_capabilities(&self, clock: &dyn Clock) -> Result<NetworkCapabilities> {
+        let config = self.get_blockchain_config(clock, false).await?;
+        Ok(NetworkCapabilities {
+            global_id: config.global_id(),
+            raw: config.capabilites(),
+        })
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        _: &dyn Clock,
+        force: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        self.call("get_blockchain_config", (force,), |result| {
+            let config = result.extract::<BlockchainConfig>()?;
+            Ok(config.as_ref().clone())
+        })
+        .await
+    }
+}
@@ -0,0 +1,298 @@
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use anyhow::Result;
+use nt::transport::models::{
+    NetworkCapabilities, PollContractState, RawContractState, RawTransaction, TransportInfo,
+};
+use nt::transport::Transport;
+use nt::utils::Clock;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use super::TransportHandle;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum SelectionStrategy {
+    /// Use the same endpoint until it fails.
+    #[default]
+    Failover,
+    /// Use the next healthy endpoint for each request.
+    RoundRobin,
+}
+
+impl SelectionStrategy {
+    pub fn from_optional_param(strategy: Option<&str>) -> PyResult<Self> {
+        match strategy {
+            None => Ok(Self::default()),
+            Some(s) => s.parse(),
+        }
+    }
+}
+
+impl FromStr for SelectionStrategy {
+    type Err = PyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "failover" => Ok(Self::Failover),
+            "round_robin" => Ok(Self::RoundRobin),
+            _ => Err(PyValueError::new_err("Unknown endpoint selection strategy")),
+        }
+    }
+}
+
+/// Transport which distributes requests between several endpoints.
+pub struct FailoverTransport {
+    endpoints: Vec<Endpoint>,
+    strategy: SelectionStrategy,
+    active: AtomicUsize,
+    next: AtomicUsize,
+}
+
+struct Endpoint {
+    url: String,
+    handle: TransportHandle,
+    healthy: AtomicBool,
+}
+
+impl FailoverTransport {
+    pub fn new(endpoints: Vec<(String, TransportHandle)>, strategy: SelectionStrategy) -> Self {
+        assert!(!endpoints.is_empty());
+
+        Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(url, handle)| Endpoint {
+                    url,
+                    handle,
+                    healthy: AtomicBool::new(true),
+                })
+                .collect(),
+            strategy,
+            active: AtomicUsize::new(0),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn endpoints(&self) -> Vec<String> {
+        self.endpoints.iter().map(|e| e.url.clone()).collect()
+    }
+
+    /// Endpoint which has served the last request.
+    pub fn active_endpoint(&self) -> String {
+        self.endpoints[self.active.load(Ordering::Acquire)]
+            .url
+            .clone()
+    }
+
+    /// Periodically checks all endpoints until the transport is dropped.
+    pub fn spawn_health_checks(self: &Arc<Self>, interval: Duration) {
+        if self.endpoints.len() < 2 {
+            return;
+        }
+
+        let weak = Arc::downgrade(self);
+        pyo3_asyncio::tokio::get_runtime().spawn(health_check_loop(weak, interval));
+    }
+
+    /// Checks all endpoints concurrently, each check must complete within the timeout.
+    async fn check_endpoints(&self, timeout: Duration) {
+        let results = futures_util::future::join_all(self.endpoints.iter().map(|endpoint| async {
+            matches!(
+                tokio::time::timeout(timeout, endpoint.handle.check_connection()).await,
+                Ok(Ok(()))
+            )
+        }))
+        .await;
+
+        for (i, (endpoint, healthy)) in self.endpoints.iter().zip(results).enumerate() {
+            if !healthy {
+                log::warn!("Endpoint {} is unhealthy", endpoint.url);
+            }
+            endpoint.healthy.store(healthy, Ordering::Release);
+
+            if healthy && !self.is_healthy(self.active.load(Ordering::Acquire)) {
+                self.set_active(i);
+            }
+        }
+    }
+
+    fn is_healthy(&self, index: usize) -> bool {
+        self.endpoints[index].healthy.load(Ordering::Acquire)
+    }
+
+    fn set_active(&self, index: usize) {
+        let prev = self.active.swap(index, Ordering::AcqRel);
+        if prev != index && self.strategy == SelectionStrategy::Failover {
+            log::info!("Switched to endpoint {}", self.endpoints[index].url);
+        }
+    }
+
+    fn select(&self) -> usize {
+        match self.strategy {
+            SelectionStrategy::Failover => self.active.load(Ordering::Acquire),
+            SelectionStrategy::RoundRobin => {
+                self.next.fetch_add(1, Ordering::AcqRel) % self.endpoints.len()
+            }
+        }
+    }
+
+    async fn call<T, F, Fut>(&self, f: F) -> Result<T>
+    where
+        F: Fn(TransportHandle) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let len = self.endpoints.len();
+        let start = self.select();
+
+        // Healthy endpoints go first, starting from the selected one
+        let mut order = (0..len).map(|i| (start + i) % len).collect::<Vec<_>>();
+        order.sort_by_key(|&index| !self.is_healthy(index));
+
+        let mut last_error = None;
+        for index in order {
+            let endpoint = &self.endpoints[index];
+            match f(endpoint.handle.clone()).await {
+                Ok(result) => {
+                    endpoint.healthy.store(true, Ordering::Release);
+                    self.set_active(index);
+                    return Ok(result);
+                }
+                // NOTE: the request was handled by the endpoint, so other endpoints
+                // would most likely reject it too
+                Err(e) if !super::is_transport_level_error(&e) => {
+                    self.set_active(index);
+                    return Err(e.context(FailedEndpoint {
+                        url: endpoint.url.clone(),
+                    }));
+                }
+                Err(e) => {
+                    log::warn!("Request to {} failed: {e:?}", endpoint.url);
+                    endpoint.healthy.store(false, Ordering::Release);
+                    last_error = Some(e.context(FailedEndpoint {
+                        url: endpoint.url.clone(),
+                    }));
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No endpoints available")))
+    }
+}
+
+/// Error context with the endpoint which failed the request.
+#[derive(Debug, Clone)]
+pub struct FailedEndpoint {
+    pub url: String,
+}
+
+impl std::fmt::Display for FailedEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "request to {} failed", self.url)
+    }
+}
+
+async fn health_check_loop(transport: Weak<FailoverTransport>, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let Some(transport) = transport.upgrade() else {
+            return;
+        };
+        transport.check_endpoints(interval).await;
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for FailoverTransport {
+    fn info(&self) -> TransportInfo {
+        self.endpoints[0].handle.as_ref().info()
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        self.call(|t| async move { t.as_ref().send_message(message).await })
+            .await
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<RawContractState> {
+        self.call(|t| async move { t.as_ref().get_contract_state(address).await })
+            .await
+    }
+
+    async fn get_library_cell(&self, hash: &ton_types::UInt256) -> Result<Option<ton_types::Cell>> {
+        self.call(|t| async move { t.as_ref().get_library_cell(hash).await })
+            .await
+    }
+
+    async fn poll_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        last_trans_lt: u64,
+    ) -> Result<PollContractState> {
+        self.call(|t| async move { t.as_ref().poll_contract_state(address, last_trans_lt).await })
+            .await
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        self.call(|t| async move {
+            t.as_ref()
+                .get_accounts_by_code_hash(code_hash, limit, continuation)
+                .await
+        })
+        .await
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<RawTransaction>> {
+        self.call(|t| async move { t.as_ref().get_transactions(address, from_lt, count).await })
+            .await
+    }
+
+    async fn get_transaction(&self, id: &ton_types::UInt256) -> Result<Option<RawTransaction>> {
+        self.call(|t| async move { t.as_ref().get_transaction(id).await })
+            .await
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<RawTransaction>> {
+        self.call(|t| async move { t.as_ref().get_dst_transaction(message_hash).await })
+            .await
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        self.call(|t| async move { t.as_ref().get_latest_key_block().await })
+            .await
+    }
+
+    async fn get_capabilities(&self, clock: &dyn Clock) -> Result<NetworkCapabilities> {
+        self.call(|t| async move { t.as_ref().get_capabilities(clock).await })
+            .await
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        clock: &dyn Clock,
+        force: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        self.call(|t| async move { t.as_ref().get_blockchain_config(clock, force).await })
+            .await
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use nt::abi::{GenTimings, LastTransactionId, TransactionId};
+use nt::transport::models::{
+    ExistingContract, NetworkCapabilities, PollContractState, RawContractState, RawTransaction,
+    TransportInfo,
+};
+use nt::utils::Clock;
+use ton_block::GetRepresentationHash;
+
+use crate::abi::{
+    execute_ordinary_transaction, ExecutedTransaction, ExecutionParams, MessageCascade,
+    RootExecutionFailed,
+};
+use crate::models::AccountState;
+use crate::util::{FastHashMap, FastHashSet};
+
+/// In-memory blockchain which executes every message locally.
+pub struct LocalBlockchain {
+    clock: super::Clock,
+    /// Explicit config or the one fetched from the forked transport on first use.
+    config: tokio::sync::OnceCell<Arc<ton_executor::BlockchainConfig>>,
+    check_signature: bool,
+    fork: Option<Fork>,
+    /// Prevents transactions of concurrent cascades from interleaving.
+    process_lock: tokio::sync::Mutex<()>,
+    state: parking_lot::Mutex<LocalBlockchainState>,
+}
+
+/// Remote transport used to fetch states of accounts on first access.
+pub struct Fork {
+    pub transport: Arc<super::TransportState>,
+    pub retry_policy: Option<super::RetryPolicy>,
+    pub timeout: Option<Duration>,
+}
+
+impl LocalBlockchain {
+    /// Max number of messages produced by a single external message.
+    const MAX_MESSAGES_PER_SEND: usize = 10000;
+
+    pub fn new(
+        clock: super::Clock,
+        config: Option<ton_executor::BlockchainConfig>,
+        check_signature: bool,
+        fork: Option<Fork>,
+    ) -> Result<Self> {
+        anyhow::ensure!(
+            config.is_some() || fork.is_some(),
+            "Blockchain config is required without fork"
+        );
+
+        Ok(Self {
+            clock,
+            config: tokio::sync::OnceCell::new_with(config.map(Arc::new)),
+            check_signature,
+            fork,
+            process_lock: Default::default(),
+            state: Default::default(),
+        })
+    }
+
+    pub fn get_account(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Option<ton_block::AccountStuff> {
+        self.state.lock().accounts.get(address).cloned()
+    }
+
+    pub fn set_account(
+        &self,
+        address: ton_block::MsgAddressInt,
+        account: Option<ton_block::AccountStuff>,
+    ) {
+        let mut state = self.state.lock();
+        state.resolved.insert(address.clone());
+        match account {
+            Some(account) => {
+                state.lt = std::cmp::max(state.lt, account.storage.last_trans_lt);
+                state.accounts.insert(address, account);
+            }
+            None => {
+                state.accounts.remove(&address);
+            }
+        }
+    }
+
+    pub fn accounts(&self) -> Vec<ton_block::MsgAddressInt> {
+        self.state.lock().accounts.keys().cloned().collect()
+    }
+
+    /// Returns the blockchain config, fetching it from the forked transport if needed.
+    async fn config(&self) -> Result<Arc<ton_executor::BlockchainConfig>> {
+        let config = self
+            .config
+            .get_or_try_init(|| async {
+                let Some(fork) = &self.fork else {
+                    anyhow::bail!("Blockchain config is not specified");
+                };
+                let config = fork
+                    .transport
+                    .get_blockchain_config(false, fork.retry_policy.as_ref(), fork.timeout)
+                    .await?;
+                Ok(Arc::new(config))
+            })
+            .await?;
+        Ok(config.clone())
+    }
+
+    /// Executes the message and delivers all produced internal messages.
+    async fn process(&self, message: &ton_block::Message) -> Result<()> {
+        let mut cascade = MessageCascade::new(message.clone(), Self::MAX_MESSAGES_PER_SEND);
+
+        // NOTE: cascades are executed one by one so that each of them
+        // sees a consistent state of the involved accounts. The lock is
+        // released while unknown accounts are fetched from the fork.
+        let mut guard = None;
+        while let Some(item) = cascade.next() {
+            let is_root = item.parent.is_none();
+            let result = match item.message.dst() {
+                Some(dst) => {
+                    let resolved = self.state.lock().resolved.contains(&dst);
+                    if !resolved {
+                        guard = None;
+                        if let Err(e) = self.resolve_account(&dst).await {
+                            if is_root {
+                                return Err(e);
+                            }
+                            cascade.complete(item, Err(e))?;
+                            continue;
+                        }
+                    }
+
+                    if guard.is_none() {
+                        guard = Some(self.process_lock.lock().await);
+                    }
+                    self.execute(dst, item.message.clone()).await.map(Some)
+                }
+                None => Err(anyhow::anyhow!("Message without destination")),
+            };
+
+            cascade.complete(item, result).map_err(|e| {
+                if is_root {
+                    e.context(RootExecutionFailed::default())
+                } else {
+                    e
+                }
+            })?;
+        }
+        drop(guard);
+
+        let output = cascade.finish();
+        for (_, e) in output.failed {
+            log::warn!("Failed to execute internal message: {e:?}");
+        }
+
+        if let Some(root) = output.transactions.into_iter().next() {
+            if root.raw.data.read_description()?.is_aborted() {
+                return Err(anyhow::Error::new(RootExecutionFailed {
+                    transaction: Some(root.raw),
+                }));
+            }
+        }
+        anyhow::ensure!(!output.truncated, "Too many messages produced");
+        Ok(())
+    }
+
+    async fn execute(
+        &self,
+        dst: ton_block::MsgAddressInt,
+        message: ton_block::Message,
+    ) -> Result<RawTransaction> {
+        let config = self.config().await?;
+        let (mut account, block_lt) = self.state.lock().prepare(&dst);
+
+        let params = ExecutionParams {
+            block_unixtime: self.clock.0.now_sec_u64() as u32,
+            block_lt,
+            check_signature: self.check_signature,
+            ..Default::default()
+        };
+
+        // NOTE: execution is CPU-bound, so it is moved out of the runtime worker
+        let (transaction, account, message) = tokio::task::spawn_blocking(move || {
+            let transaction =
+                execute_ordinary_transaction(&config, &message, &mut account, params)?;
+            Ok::<_, anyhow::Error>((transaction, account, message))
+        })
+        .await??;
+
+        self.state
+            .lock()
+            .commit(dst, &message, account, transaction)
+    }
+
+    /// Fetches the account state from the forked transport on first access.
+    async fn resolve_account(&self, address: &ton_block::MsgAddressInt) -> Result<()> {
+        let Some(fork) = &self.fork else {
+            return Ok(());
+        };
+
+        let resolved = self.state.lock().resolved.contains(address);
+        if resolved {
+            return Ok(());
+        }
+
+        let account = fork
+            .transport
+            .get_account_state(address, fork.retry_policy.as_ref(), fork.timeout)
+            .await?;
+
+        let mut state = self.state.lock();
+        // NOTE: the account could have been changed while the state was being fetched
+        if state.resolved.insert(address.clone()) {
+            if let Some(AccountState(account)) = account {
+                state.lt = std::cmp::max(state.lt, account.storage.last_trans_lt);
+                state.accounts.insert(address.clone(), account);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct LocalBlockchainState {
+    accounts: FastHashMap<ton_block::MsgAddressInt, ton_block::AccountStuff>,
+    account_transactions: FastHashMap<ton_block::MsgAddressInt, BTreeMap<u64, ton_types::UInt256>>,
+    transactions: FastHashMap<ton_types::UInt256, RawTransaction>,
+    dst_transactions: FastHashMap<ton_types::UInt256, ton_types::UInt256>,
+    /// Accounts which are no longer fetched from the forked transport.
+    resolved: FastHashSet<ton_block::MsgAddressInt>,
+    lt: u64,
+}
+
+impl LocalBlockchainState {
+    const LT_OFFSET: u64 = 10;
+
+    /// Returns the current account state and the block lt for the next transaction.
+    fn prepare(&mut self, dst: &ton_block::MsgAddressInt) -> (ton_block::Account, u64) {
+        self.resolved.insert(dst.clone());
+
+        let account = match self.accounts.get(dst) {
+            Some(account) => ton_block::Account::Account(account.clone()),
+            None => ton_block::Account::AccountNone,
+        };
+
+        let last_trans_lt = account.last_tr_time().unwrap_or_default();
+        let block_lt = std::cmp::max(self.lt, last_trans_lt) + Self::LT_OFFSET;
+
+        (account, block_lt)
+    }
+
+    /// Stores the executed transaction and the new account state.
+    fn commit(
+        &mut self,
+        dst: ton_block::MsgAddressInt,
+        message: &ton_block::Message,
+        account: ton_block::Account,
+        transaction: ExecutedTransaction,
+    ) -> Result<RawTransaction> {
+        self.lt = std::cmp::max(self.lt, transaction.end_lt);
+
+        match account {
+            ton_block::Account::Account(account) => {
+                self.accounts.insert(dst.clone(), account);
+            }
+            ton_block::Account::AccountNone => {
+                self.accounts.remove(&dst);
+            }
+        }
+
+        let hash = transaction.raw.hash;
+        self.dst_transactions.insert(message.hash()?, hash);
+        self.account_transactions
+            .entry(dst)
+            .or_default()
+            .insert(transaction.raw.data.lt, hash);
+        self.transactions.insert(hash, transaction.raw.clone());
+
+        Ok(transaction.raw)
+    }
+
+    fn contract_state(&self, address: &ton_block::MsgAddressInt) -> RawContractState {
+        let Some(account) = self.accounts.get(address) else {
+            return RawContractState::NotExists {
+                timings: GenTimings::Unknown,
+            };
+        };
+
+        let latest_lt = account.storage.last_trans_lt;
+        let last_transaction_id = match self
+            .account_transactions
+            .get(address)
+            .and_then(|transactions| transactions.get(&latest_lt))
+        {
+            Some(hash) => LastTransactionId::Exact(TransactionId {
+                lt: latest_lt,
+                hash: *hash,
+            }),
+            None => LastTransactionId::Inexact { latest_lt },
+        };
+
+        RawContractState::Exists(ExistingContract {
+            account: account.clone(),
+            timings: GenTimings::Unknown,
+            last_transaction_id,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl nt::transport::Transport for LocalBlockchain {
+    fn info(&self) -> TransportInfo {
+        TransportInfo {
+            max_transactions_per_fetch: u8::MAX,
+            reliable_behavior: nt::core::models::ReliableBehavior::IntensivePolling,
+            has_key_blocks: false,
+        }
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        self.process(message).await
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<RawContractState> {
+        self.resolve_account(address).await?;
+        Ok(self.state.lock().contract_state(address))
+    }
+
+    async fn get_library_cell(&self, _: &ton_types::UInt256) -> Result<Option<ton_types::Cell>> {
+        Ok(None)
+    }
+
+    async fn poll_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        last_trans_lt: u64,
+    ) -> Result<PollContractState> {
+        self.resolve_account(address).await?;
+        let state = self.state.lock().contract_state(address);
+        Ok(super::poll_contract_state_from(state, last_trans_lt))
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        let state = self.state.lock();
+
+        let mut addresses = state
+            .accounts
+            .iter()
+            .filter(|(_, account)| match &account.storage.state {
+                ton_block::AccountState::AccountActive { state_init } => {
+                    matches!(&state_init.code, Some(code) if code.repr_hash() == *code_hash)
+                }
+                _ => false,
+            })
+            .map(|(address, _)| address.clone())
+            .collect::<Vec<_>>();
+        addresses.sort_by_key(|address| address.to_string());
+
+        let skip = match continuation {
+            Some(continuation) => {
+                let continuation = continuation.to_string();
+                addresses
+                    .iter()
+                    .take_while(|address| address.to_string() <= continuation)
+                    .count()
+            }
+            None => 0,
+        };
+
+        Ok(addresses
+            .into_iter()
+            .skip(skip)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<RawTransaction>> {
+        let state = self.state.lock();
+
+        let Some(transactions) = state.account_transactions.get(address) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(transactions
+            .range(..=from_lt)
+            .rev()
+            .take(count as usize)
+            .filter_map(|(_, hash)| state.transactions.get(hash).cloned())
+            .collect())
+    }
+
+    async fn get_transaction(&self, id: &ton_types::UInt256) -> Result<Option<RawTransaction>> {
+        Ok(self.state.lock().transactions.get(id).cloned())
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<RawTransaction>> {
+        let state = self.state.lock();
+        Ok(state
+            .dst_transactions
+            .get(message_hash)
+            .and_then(|hash| state.transactions.get(hash))
+            .cloned())
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        anyhow::bail!("Key blocks are not supported by the local transport")
+    }
+
+    async fn get_capabilities(&self, _: &dyn Clock) -> Result<NetworkCapabilities> {
+        let config = self.config().await?;
+        Ok(NetworkCapabilities {
+            global_id: config.global_id(),
+            raw: config.capabilites(),
+        })
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        _: &dyn Clock,
+        _: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        Ok(self.config().await?.as_ref().clone())
+    }
+}
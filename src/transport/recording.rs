@@ -0,0 +1,765 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::engine::Engine;
+use nt::abi::{GenTimings, LastTransactionId, TransactionId};
+use nt::core::models::ReliableBehavior;
+use nt::transport::models::{
+    ExistingContract, NetworkCapabilities, PollContractState, RawContractState, RawTransaction,
+    TransportInfo,
+};
+use nt::transport::Transport;
+use nt::utils::Clock;
+use serde::{Deserialize, Serialize};
+use ton_block::{Deserializable, GetRepresentationHash, Serializable};
+
+use crate::util::FastHashMap;
+
+/// Transport wrapper which writes all requests and responses into the file.
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    clock: super::Clock,
+    sent_messages: AtomicUsize,
+    writer: parking_lot::Mutex<std::io::BufWriter<std::fs::File>>,
+}
+
+impl RecordingTransport {
+    pub fn new<P: AsRef<Path>>(
+        inner: Arc<dyn Transport>,
+        clock: super::Clock,
+        path: P,
+    ) -> Result<Self> {
+        let file = std::fs::File::create(path).context("Failed to create records file")?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        let header = Header::new(inner.info(), clock.0.now_ms_u64());
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+
+        Ok(Self {
+            inner,
+            clock,
+            sent_messages: Default::default(),
+            writer: parking_lot::Mutex::new(writer),
+        })
+    }
+
+    /// Writes the entry into the file.
+    ///
+    /// NOTE: the response is returned to the caller as is, so failures are only logged.
+    fn record<T, F>(&self, request: Request, response: &Result<T>, f: F)
+    where
+        F: FnOnce(&T) -> Result<Response>,
+    {
+        let response = match response {
+            Ok(value) => f(value),
+            Err(e) => Ok(Response::Error {
+                message: e.to_string(),
+            }),
+        };
+
+        let res = response.and_then(|response| {
+            let entry = Entry {
+                request,
+                response,
+                now_ms: Some(self.clock.0.now_ms_u64()),
+            };
+
+            let mut writer = self.writer.lock();
+            serde_json::to_writer(&mut *writer, &entry)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+            Ok(())
+        });
+
+        if let Err(e) = res {
+            log::error!("Failed to record transport response: {e:?}");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for RecordingTransport {
+    fn info(&self) -> TransportInfo {
+        self.inner.info()
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        let message_hash = message.hash()?.to_hex_string();
+        let request = Request::SendMessage {
+            seqno: self.sent_messages.fetch_add(1, Ordering::Relaxed),
+        };
+        let result = self.inner.send_message(message).await;
+        self.record(request, &result, |_| Ok(Response::Sent { message_hash }));
+        result
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<RawContractState> {
+        let request = Request::GetContractState {
+            address: address.to_string(),
+        };
+        let result = self.inner.get_contract_state(address).await;
+        self.record(request, &result, |state| {
+            StoredContractState::try_from(state).map(Response::ContractState)
+        });
+        result
+    }
+
+    async fn get_library_cell(&self, hash: &ton_types::UInt256) -> Result<Option<ton_types::Cell>> {
+        let request = Request::GetLibraryCell {
+            hash: hash.to_hex_string(),
+        };
+        let result = self.inner.get_library_cell(hash).await;
+        self.record(request, &result, |cell| {
+            Ok(Response::Boc {
+                boc: cell.as_ref().map(encode_cell).transpose()?,
+            })
+        });
+        result
+    }
+
+    async fn poll_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        last_trans_lt: u64,
+    ) -> Result<PollContractState> {
+        let request = Request::PollContractState {
+            address: address.to_string(),
+            last_trans_lt,
+        };
+        let result = self.inner.poll_contract_state(address, last_trans_lt).await;
+        self.record(request, &result, |state| {
+            StoredPollContractState::try_from(state).map(Response::PolledContractState)
+        });
+        result
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        let request = Request::GetAccountsByCodeHash {
+            code_hash: code_hash.to_hex_string(),
+            limit,
+            continuation: continuation.as_ref().map(ToString::to_string),
+        };
+        let result = self
+            .inner
+            .get_accounts_by_code_hash(code_hash, limit, continuation)
+            .await;
+        self.record(request, &result, |addresses| {
+            Ok(Response::Addresses {
+                addresses: addresses.iter().map(ToString::to_string).collect(),
+            })
+        });
+        result
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<RawTransaction>> {
+        let request = Request::GetTransactions {
+            address: address.to_string(),
+            from_lt,
+            count,
+        };
+        let result = self.inner.get_transactions(address, from_lt, count).await;
+        self.record(request, &result, |transactions| {
+            Ok(Response::Transactions {
+                transactions: transactions
+                    .iter()
+                    .map(|tx| encode_boc(&tx.data))
+                    .collect::<Result<_>>()?,
+            })
+        });
+        result
+    }
+
+    async fn get_transaction(&self, id: &ton_types::UInt256) -> Result<Option<RawTransaction>> {
+        let request = Request::GetTransaction {
+            id: id.to_hex_string(),
+        };
+        let result = self.inner.get_transaction(id).await;
+        self.record(request, &result, |tx| {
+            Ok(Response::Boc {
+                boc: tx.as_ref().map(|tx| encode_boc(&tx.data)).transpose()?,
+            })
+        });
+        result
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<RawTransaction>> {
+        let request = Request::GetDstTransaction {
+            message_hash: message_hash.to_hex_string(),
+        };
+        let result = self.inner.get_dst_transaction(message_hash).await;
+        self.record(request, &result, |tx| {
+            Ok(Response::Boc {
+                boc: tx.as_ref().map(|tx| encode_boc(&tx.data)).transpose()?,
+            })
+        });
+        result
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        let result = self.inner.get_latest_key_block().await;
+        self.record(Request::GetLatestKeyBlock, &result, |block| {
+            Ok(Response::Boc {
+                boc: Some(encode_boc(block)?),
+            })
+        });
+        result
+    }
+
+    async fn get_capabilities(&self, clock: &dyn Clock) -> Result<NetworkCapabilities> {
+        let result = self.inner.get_capabilities(clock).await;
+        self.record(Request::GetCapabilities, &result, |capabilities| {
+            Ok(Response::Capabilities {
+                global_id: capabilities.global_id,
+                raw: capabilities.raw,
+            })
+        });
+        result
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        clock: &dyn Clock,
+        force: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        let result = self.inner.get_blockchain_config(clock, force).await;
+        self.record(Request::GetBlockchainConfig, &result, |config| {
+            Ok(Response::Config {
+                global_id: config.global_id(),
+                params: encode_boc(config.raw_config())?,
+            })
+        });
+        result
+    }
+}
+
+/// Transport which serves responses from the records file.
+pub struct ReplayTransport {
+    header: Header,
+    clock: super::Clock,
+    sent_messages: AtomicUsize,
+    responses: parking_lot::Mutex<FastHashMap<Request, ResponsesQueue>>,
+}
+
+impl ReplayTransport {
+    /// Creates a replay transport.
+    ///
+    /// Uses the clock stopped at the recording start time by default.
+    /// The stopped clock is moved to the recording time of each served response.
+    pub fn new<P: AsRef<Path>>(path: P, clock: Option<super::Clock>) -> Result<Self> {
+        let file = std::fs::File::open(path).context("Failed to open records file")?;
+        let mut lines = std::io::BufReader::new(file).lines();
+
+        let header: Header = match lines.next() {
+            Some(line) => serde_json::from_str(&line?).context("Invalid records header")?,
+            None => anyhow::bail!("Empty records file"),
+        };
+
+        let mut responses = FastHashMap::<Request, ResponsesQueue>::default();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry: Entry = serde_json::from_str(&line).context("Invalid records entry")?;
+            responses
+                .entry(entry.request)
+                .or_default()
+                .push(RecordedResponse {
+                    response: entry.response,
+                    now_ms: entry.now_ms,
+                });
+        }
+
+        let clock = clock.unwrap_or_else(|| super::Clock::new(None, Some(header.started_at_ms)));
+
+        Ok(Self {
+            header,
+            clock,
+            sent_messages: Default::default(),
+            responses: parking_lot::Mutex::new(responses),
+        })
+    }
+
+    pub fn clock(&self) -> &super::Clock {
+        &self.clock
+    }
+
+    fn next_response(&self, request: Request) -> Result<Response> {
+        let mut responses = self.responses.lock();
+        let Some(recorded) = responses.get_mut(&request).and_then(ResponsesQueue::next) else {
+            anyhow::bail!("No recorded response for {request:?}");
+        };
+
+        if let Some(now_ms) = recorded.now_ms {
+            self.clock.0.advance_frozen(now_ms);
+        }
+
+        match recorded.response {
+            Response::Error { message } => Err(anyhow::anyhow!(message)),
+            response => Ok(response),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for ReplayTransport {
+    fn info(&self) -> TransportInfo {
+        self.header.into()
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        let message_hash = message.hash()?.to_hex_string();
+        let request = Request::SendMessage {
+            seqno: self.sent_messages.fetch_add(1, Ordering::Relaxed),
+        };
+        match self.next_response(request)? {
+            Response::Sent {
+                message_hash: recorded,
+            } if recorded == message_hash => Ok(()),
+            Response::Sent {
+                message_hash: recorded,
+            } => anyhow::bail!(
+                "Sent message {message_hash} differs from the recorded one {recorded}. \
+                Make sure it was recorded with a stopped clock"
+            ),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<RawContractState> {
+        let request = Request::GetContractState {
+            address: address.to_string(),
+        };
+        match self.next_response(request)? {
+            Response::ContractState(state) => state.try_into(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_library_cell(&self, hash: &ton_types::UInt256) -> Result<Option<ton_types::Cell>> {
+        let request = Request::GetLibraryCell {
+            hash: hash.to_hex_string(),
+        };
+        match self.next_response(request)? {
+            Response::Boc { boc } => boc.as_deref().map(decode_cell).transpose(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn poll_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        last_trans_lt: u64,
+    ) -> Result<PollContractState> {
+        let request = Request::PollContractState {
+            address: address.to_string(),
+            last_trans_lt,
+        };
+        match self.next_response(request)? {
+            Response::PolledContractState(state) => state.try_into(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        let request = Request::GetAccountsByCodeHash {
+            code_hash: code_hash.to_hex_string(),
+            limit,
+            continuation: continuation.as_ref().map(ToString::to_string),
+        };
+        match self.next_response(request)? {
+            Response::Addresses { addresses } => addresses
+                .iter()
+                .map(|addr| addr.parse().map_err(anyhow::Error::from))
+                .collect(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<RawTransaction>> {
+        let request = Request::GetTransactions {
+            address: address.to_string(),
+            from_lt,
+            count,
+        };
+        match self.next_response(request)? {
+            Response::Transactions { transactions } => transactions
+                .iter()
+                .map(String::as_str)
+                .map(decode_transaction)
+                .collect(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_transaction(&self, id: &ton_types::UInt256) -> Result<Option<RawTransaction>> {
+        let request = Request::GetTransaction {
+            id: id.to_hex_string(),
+        };
+        match self.next_response(request)? {
+            Response::Boc { boc } => boc.as_deref().map(decode_transaction).transpose(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<RawTransaction>> {
+        let request = Request::GetDstTransaction {
+            message_hash: message_hash.to_hex_string(),
+        };
+        match self.next_response(request)? {
+            Response::Boc { boc } => boc.as_deref().map(decode_transaction).transpose(),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        match self.next_response(Request::GetLatestKeyBlock)? {
+            Response::Boc { boc: Some(boc) } => {
+                ton_block::Block::construct_from_cell(decode_cell(&boc)?)
+            }
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_capabilities(&self, _: &dyn Clock) -> Result<NetworkCapabilities> {
+        match self.next_response(Request::GetCapabilities)? {
+            Response::Capabilities { global_id, raw } => Ok(NetworkCapabilities { global_id, raw }),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        _: &dyn Clock,
+        _: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        match self.next_response(Request::GetBlockchainConfig)? {
+            Response::Config { global_id, params } => {
+                let params = ton_block::ConfigParams::construct_from_cell(decode_cell(&params)?)?;
+                ton_executor::BlockchainConfig::with_config(params, global_id)
+            }
+            _ => Err(unexpected_response()),
+        }
+    }
+}
+
+/// Recorded responses for the same request.
+///
+/// Responses are served in the recorded order, the last one is repeated.
+#[derive(Default)]
+struct ResponsesQueue {
+    items: VecDeque<RecordedResponse>,
+}
+
+impl ResponsesQueue {
+    fn push(&mut self, response: RecordedResponse) {
+        self.items.push_back(response);
+    }
+
+    fn next(&mut self) -> Option<RecordedResponse> {
+        if self.items.len() > 1 {
+            self.items.pop_front()
+        } else {
+            self.items.front().cloned()
+        }
+    }
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+struct Header {
+    started_at_ms: u64,
+    max_transactions_per_fetch: u8,
+    block_walking: bool,
+    has_key_blocks: bool,
+}
+
+impl Header {
+    fn new(info: TransportInfo, started_at_ms: u64) -> Self {
+        Self {
+            started_at_ms,
+            max_transactions_per_fetch: info.max_transactions_per_fetch,
+            block_walking: matches!(info.reliable_behavior, ReliableBehavior::BlockWalking),
+            has_key_blocks: info.has_key_blocks,
+        }
+    }
+}
+
+impl From<Header> for TransportInfo {
+    fn from(header: Header) -> Self {
+        Self {
+            max_transactions_per_fetch: header.max_transactions_per_fetch,
+            reliable_behavior: if header.block_walking {
+                ReliableBehavior::BlockWalking
+            } else {
+                ReliableBehavior::IntensivePolling
+            },
+            has_key_blocks: header.has_key_blocks,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    request: Request,
+    response: Response,
+    /// Clock time when the response was received.
+    #[serde(default)]
+    now_ms: Option<u64>,
+}
+
+#[derive(Clone)]
+struct RecordedResponse {
+    response: Response,
+    now_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum Request {
+    /// Sent messages are identified by the call order.
+    SendMessage {
+        seqno: usize,
+    },
+    GetContractState {
+        address: String,
+    },
+    PollContractState {
+        address: String,
+        last_trans_lt: u64,
+    },
+    GetLibraryCell {
+        hash: String,
+    },
+    GetAccountsByCodeHash {
+        code_hash: String,
+        limit: u8,
+        continuation: Option<String>,
+    },
+    GetTransactions {
+        address: String,
+        from_lt: u64,
+        count: u8,
+    },
+    GetTransaction {
+        id: String,
+    },
+    GetDstTransaction {
+        message_hash: String,
+    },
+    GetLatestKeyBlock,
+    GetCapabilities,
+    GetBlockchainConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    Error { message: String },
+    Sent { message_hash: String },
+    ContractState(StoredContractState),
+    PolledContractState(StoredPollContractState),
+    Boc { boc: Option<String> },
+    Addresses { addresses: Vec<String> },
+    Transactions { transactions: Vec<String> },
+    Capabilities { global_id: i32, raw: u64 },
+    Config { global_id: i32, params: String },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredContractState {
+    account: Option<String>,
+    gen_lt: Option<u64>,
+    gen_utime: Option<u32>,
+    last_transaction_lt: Option<u64>,
+    last_transaction_hash: Option<String>,
+}
+
+impl TryFrom<&RawContractState> for StoredContractState {
+    type Error = anyhow::Error;
+
+    fn try_from(state: &RawContractState) -> Result<Self> {
+        let timings = |timings: &GenTimings| match *timings {
+            GenTimings::Known { gen_lt, gen_utime } => (Some(gen_lt), Some(gen_utime)),
+            GenTimings::Unknown => (None, None),
+        };
+
+        Ok(match state {
+            RawContractState::NotExists { timings: t } => {
+                let (gen_lt, gen_utime) = timings(t);
+                Self {
+                    account: None,
+                    gen_lt,
+                    gen_utime,
+                    last_transaction_lt: None,
+                    last_transaction_hash: None,
+                }
+            }
+            RawContractState::Exists(contract) => {
+                let (gen_lt, gen_utime) = timings(&contract.timings);
+                let (last_transaction_lt, last_transaction_hash) =
+                    match &contract.last_transaction_id {
+                        LastTransactionId::Exact(id) => (id.lt, Some(id.hash.to_hex_string())),
+                        LastTransactionId::Inexact { latest_lt } => (*latest_lt, None),
+                    };
+
+                Self {
+                    account: Some(encode_boc(&ton_block::Account::Account(
+                        contract.account.clone(),
+                    ))?),
+                    gen_lt,
+                    gen_utime,
+                    last_transaction_lt: Some(last_transaction_lt),
+                    last_transaction_hash,
+                }
+            }
+        })
+    }
+}
+
+impl TryFrom<StoredContractState> for RawContractState {
+    type Error = anyhow::Error;
+
+    fn try_from(state: StoredContractState) -> Result<Self> {
+        let timings = match (state.gen_lt, state.gen_utime) {
+            (Some(gen_lt), Some(gen_utime)) => GenTimings::Known { gen_lt, gen_utime },
+            _ => GenTimings::Unknown,
+        };
+
+        let account = match state.account {
+            Some(boc) => ton_block::Account::construct_from_cell(decode_cell(&boc)?)?,
+            None => ton_block::Account::AccountNone,
+        };
+
+        Ok(match account {
+            ton_block::Account::AccountNone => RawContractState::NotExists { timings },
+            ton_block::Account::Account(account) => {
+                let latest_lt = state
+                    .last_transaction_lt
+                    .unwrap_or(account.storage.last_trans_lt);
+                let last_transaction_id = match state.last_transaction_hash {
+                    Some(hash) => LastTransactionId::Exact(TransactionId {
+                        lt: latest_lt,
+                        hash: hash.parse()?,
+                    }),
+                    None => LastTransactionId::Inexact { latest_lt },
+                };
+
+                RawContractState::Exists(ExistingContract {
+                    account,
+                    timings,
+                    last_transaction_id,
+                })
+            }
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredPollContractState {
+    unchanged: bool,
+    #[serde(flatten)]
+    state: StoredContractState,
+}
+
+impl TryFrom<&PollContractState> for StoredPollContractState {
+    type Error = anyhow::Error;
+
+    fn try_from(state: &PollContractState) -> Result<Self> {
+        let (unchanged, state) = match state {
+            PollContractState::Unchanged { timings } => {
+                (true, RawContractState::NotExists { timings: *timings })
+            }
+            PollContractState::NotExists { timings } => {
+                (false, RawContractState::NotExists { timings: *timings })
+            }
+            PollContractState::Changed(contract) => {
+                (false, RawContractState::Exists(contract.clone()))
+            }
+        };
+
+        Ok(Self {
+            unchanged,
+            state: StoredContractState::try_from(&state)?,
+        })
+    }
+}
+
+impl TryFrom<StoredPollContractState> for PollContractState {
+    type Error = anyhow::Error;
+
+    fn try_from(state: StoredPollContractState) -> Result<Self> {
+        Ok(match RawContractState::try_from(state.state)? {
+            RawContractState::NotExists { timings } if state.unchanged => {
+                PollContractState::Unchanged { timings }
+            }
+            RawContractState::NotExists { timings } => PollContractState::NotExists { timings },
+            RawContractState::Exists(contract) => PollContractState::Changed(contract),
+        })
+    }
+}
+
+fn unexpected_response() -> anyhow::Error {
+    anyhow::anyhow!("Unexpected recorded response")
+}
+
+fn encode_cell(cell: &ton_types::Cell) -> Result<String> {
+    let bytes = ton_types::serialize_toc(cell)?;
+    Ok(STANDARD.encode(bytes))
+}
+
+fn encode_boc<T: Serializable>(value: &T) -> Result<String> {
+    encode_cell(&value.serialize()?)
+}
+
+fn decode_cell(boc: &str) -> Result<ton_types::Cell> {
+    let bytes = STANDARD.decode(boc)?;
+    ton_types::deserialize_tree_of_cells(&mut bytes.as_slice())
+}
+
+fn decode_transaction(boc: &str) -> Result<RawTransaction> {
+    let cell = decode_cell(boc)?;
+    Ok(RawTransaction {
+        hash: cell.repr_hash(),
+        data: ton_block::Transaction::construct_from_cell(cell)?,
+    })
+}
@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 use once_cell::sync::OnceCell;
 use pyo3::exceptions::*;
+use pyo3::once_cell::GILOnceCell;
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple, PyType};
+use pyo3::{PyErrArguments, PyTypeInfo};
 
 pub type FastHashMap<K, V> = HashMap<K, V, ahash::RandomState>;
+pub type FastHashSet<K> = HashSet<K, ahash::RandomState>;
 pub type FastDashMap<K, V> = dashmap::DashMap<K, V, ahash::RandomState>;
 
 impl<T, E> HandleError for Result<T, E>
@@ -21,10 +25,24 @@ where
         }
     }
 
-    fn handle_runtime_error(self) -> PyResult<Self::Output> {
+    fn handle_cell_error(self) -> PyResult<Self::Output> {
         match self {
             Ok(r) => Ok(r),
-            Err(e) => Err(PyRuntimeError::new_err(e.to_string())),
+            Err(e) => Err(CellError::new_err(e.to_string())),
+        }
+    }
+
+    fn handle_abi_encode_error(self) -> PyResult<Self::Output> {
+        match self {
+            Ok(r) => Ok(r),
+            Err(e) => Err(AbiEncodeError::new_err(e.to_string())),
+        }
+    }
+
+    fn handle_abi_decode_error(self) -> PyResult<Self::Output> {
+        match self {
+            Ok(r) => Ok(r),
+            Err(e) => Err(AbiDecodeError::new_err(e.to_string())),
         }
     }
 }
@@ -33,7 +51,172 @@ pub trait HandleError {
     type Output;
 
     fn handle_value_error(self) -> PyResult<Self::Output>;
-    fn handle_runtime_error(self) -> PyResult<Self::Output>;
+    fn handle_cell_error(self) -> PyResult<Self::Output>;
+    fn handle_abi_encode_error(self) -> PyResult<Self::Output>;
+    fn handle_abi_decode_error(self) -> PyResult<Self::Output>;
+}
+
+pyo3::create_exception!(
+    nekoton,
+    NekotonError,
+    PyRuntimeError,
+    "Base class for all nekoton errors."
+);
+pyo3::create_exception!(
+    nekoton,
+    TransportError,
+    NekotonError,
+    "Transport request failed."
+);
+pyo3::create_exception!(
+    nekoton,
+    ExecutionError,
+    NekotonError,
+    "Failed to execute a contract."
+);
+
+/// Declares an exception with multiple bases.
+///
+/// Its type is created when the module is initialized, so later lookups can't fail.
+macro_rules! create_exception_with_bases {
+    ($name:ident, [$($base:expr),+], $doc:literal) => {
+        pub struct $name;
+
+        impl $name {
+            pub fn type_object(py: Python<'_>) -> PyResult<&PyType> {
+                static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+                if let Some(ty) = TYPE_OBJECT.get(py) {
+                    return Ok(ty.as_ref(py));
+                }
+
+                let bases = [$($base(py)?),+];
+                let ty = new_exception_type(py, stringify!($name), $doc, &bases)?;
+                Ok(TYPE_OBJECT.get_or_init(py, || ty).as_ref(py))
+            }
+
+            pub fn new_err<A>(args: A) -> PyErr
+            where
+                A: PyErrArguments + Send + Sync + 'static,
+            {
+                Python::with_gil(|py| match Self::type_object(py) {
+                    Ok(ty) => PyErr::from_type(ty, args),
+                    Err(e) => e,
+                })
+            }
+        }
+    };
+}
+
+create_exception_with_bases!(
+    Timeout,
+    [builtin_type::<NekotonError>, builtin_type::<PyTimeoutError>],
+    "Operation timed out."
+);
+create_exception_with_bases!(
+    MessageExpired,
+    [Timeout::type_object],
+    "External message expired while waiting for its delivery."
+);
+create_exception_with_bases!(
+    AbiEncodeError,
+    [builtin_type::<NekotonError>, builtin_type::<PyValueError>],
+    "Failed to encode ABI values."
+);
+create_exception_with_bases!(
+    AbiDecodeError,
+    [builtin_type::<NekotonError>, builtin_type::<PyValueError>],
+    "Failed to decode ABI values."
+);
+create_exception_with_bases!(
+    CellError,
+    [builtin_type::<NekotonError>, builtin_type::<PyValueError>],
+    "Invalid cell or BOC."
+);
+
+fn builtin_type<T: PyTypeInfo>(py: Python<'_>) -> PyResult<&PyType> {
+    Ok(PyType::new::<T>(py))
+}
+
+fn new_exception_type(
+    py: Python<'_>,
+    name: &str,
+    doc: &str,
+    bases: &[&PyType],
+) -> PyResult<Py<PyType>> {
+    let dict = PyDict::new(py);
+    dict.set_item("__doc__", doc)?;
+    dict.set_item("__module__", "nekoton")?;
+
+    let ty = py
+        .get_type::<PyType>()
+        .call1((name, PyTuple::new(py, bases), dict))?;
+    Ok(ty.downcast::<PyType>()?.into())
+}
+
+pub fn add_error_types(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("NekotonError", py.get_type::<NekotonError>())?;
+    m.add("TransportError", py.get_type::<TransportError>())?;
+    m.add("Timeout", Timeout::type_object(py)?)?;
+    m.add("MessageExpired", MessageExpired::type_object(py)?)?;
+    m.add("AbiEncodeError", AbiEncodeError::type_object(py)?)?;
+    m.add("AbiDecodeError", AbiDecodeError::type_object(py)?)?;
+    m.add("ExecutionError", py.get_type::<ExecutionError>())?;
+    m.add("CellError", CellError::type_object(py)?)?;
+
+    // Defaults for errors raised without extra info
+    let ty = py.get_type::<TransportError>();
+    ty.setattr("endpoint", py.None())?;
+    ty.setattr("retriable", false)?;
+
+    for ty in [
+        AbiEncodeError::type_object(py)?,
+        AbiDecodeError::type_object(py)?,
+    ] {
+        ty.setattr("path", py.None())?;
+    }
+
+    let ty = py.get_type::<ExecutionError>();
+    ty.setattr("exit_code", py.None())?;
+    ty.setattr("gas_used", py.None())?;
+    ty.setattr("transaction", py.None())?;
+
+    Ok(())
+}
+
+pub fn transport_error(error: &anyhow::Error, endpoint: Option<String>, retriable: bool) -> PyErr {
+    let error = TransportError::new_err(format!("{error:#}"));
+    with_error_attrs(error, |py, value| {
+        value.setattr("endpoint", endpoint.into_py(py))?;
+        value.setattr("retriable", retriable)
+    })
+}
+
+pub fn abi_encode_error(path: String, message: String) -> PyErr {
+    let error = AbiEncodeError::new_err(message);
+    with_error_attrs(error, |_, value| value.setattr("path", path))
+}
+
+pub fn abi_decode_error(path: String, message: String) -> PyErr {
+    let error = AbiDecodeError::new_err(message);
+    with_error_attrs(error, |_, value| value.setattr("path", path))
+}
+
+pub fn execution_error(message: String, exit_code: Option<i32>, gas_used: Option<u64>) -> PyErr {
+    let error = ExecutionError::new_err(message);
+    with_error_attrs(error, |py, value| {
+        value.setattr("exit_code", exit_code.into_py(py))?;
+        value.setattr("gas_used", gas_used.into_py(py))
+    })
+}
+
+pub fn with_error_attrs<F>(error: PyErr, f: F) -> PyErr
+where
+    F: FnOnce(Python<'_>, &PyAny) -> PyResult<()>,
+{
+    Python::with_gil(|py| match f(py, error.value(py)) {
+        Ok(()) => error,
+        Err(e) => e,
+    })
 }
 
 pub trait HashExt: Sized {
@@ -81,11 +264,11 @@ impl Encoding {
         }
 
         let bytes = self.decode_bytes(boc)?;
-        ton_types::deserialize_tree_of_cells(&mut bytes.as_slice()).handle_value_error()
+        ton_types::deserialize_tree_of_cells(&mut bytes.as_slice()).handle_cell_error()
     }
 
     pub fn encode_cell(&self, cell: &ton_types::Cell) -> PyResult<String> {
-        let cell = ton_types::serialize_toc(cell).handle_runtime_error()?;
+        let cell = ton_types::serialize_toc(cell).handle_cell_error()?;
         Ok(self.encode_bytes(&cell))
     }
 
@@ -138,6 +321,36 @@ pub fn py_none() -> PyObject {
         .clone()
 }
 
+/// `__reduce__` result: a callable and its positional arguments.
+pub type PyReduce = (PyObject, Py<PyTuple>);
+
+/// Reduces an object to a constructor call of its class.
+pub fn reduce_new<T: PyTypeInfo>(py: Python<'_>, args: impl IntoPy<Py<PyTuple>>) -> PyReduce {
+    (py.get_type::<T>().into_py(py), args.into_py(py))
+}
+
+/// `__reduce__` result with a state which is passed to `__setstate__`.
+pub type PyReduceWithState = (PyObject, Py<PyTuple>, PyObject);
+
+/// Reduces an enum variant to a lookup of the class attribute.
+pub fn reduce_enum<T: PyTypeInfo>(py: Python<'_>, variant: &str) -> PyResult<PyReduce> {
+    let getattr = py.import("builtins")?.getattr("getattr")?;
+    Ok((
+        getattr.into_py(py),
+        (py.get_type::<T>(), variant).into_py(py),
+    ))
+}
+
+/// Reduces an object to a static method call of its class (e.g. `from_bytes`).
+pub fn reduce_with<T: PyTypeInfo>(
+    py: Python<'_>,
+    method: &str,
+    args: impl IntoPy<Py<PyTuple>>,
+) -> PyResult<PyReduce> {
+    let method = py.get_type::<T>().getattr(method)?;
+    Ok((method.into_py(py), args.into_py(py)))
+}
+
 pub fn serialize_state_init_data_key(key: u64) -> ton_types::SliceData {
     use ton_block::Serializable;
 
@@ -1,8 +1,11 @@
 import asyncio
+import copy
 import logging
 import os
+import pickle
 
 import nekoton as nt
+from nekoton.contracts.ever_wallet import _send_transaction
 
 FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
 logging.basicConfig(format=FORMAT)
@@ -230,6 +233,172 @@ assert code == nt.Cell.decode(
     "te6ccgEBBAEAHgABFP8A9KQT9LzyyAsBAgLOAwIABaNUQAAJ0IPAWpI="
 )
 
+# Retry policy
+retry_policy = nt.RetryPolicy(max_attempts=5, retry_on=["overloaded"])
+assert retry_policy.retry_on == ["overloaded"]
+assert "retry_on=" in repr(retry_policy)
+try:
+    nt.RetryPolicy(initial_backoff=2, max_backoff=1)
+    assert False, "max backoff must be validated"
+except ValueError:
+    pass
+
+# Subscription options
+subscription_options = nt.SubscriptionOptions(interval=5, short_interval=0.5)
+assert subscription_options.interval == 5
+for invalid in [{"interval": 0}, {"short_interval": 0}, {"capacity": 0}]:
+    try:
+        nt.SubscriptionOptions(**invalid)
+        assert False, f"{invalid} must be rejected"
+    except ValueError:
+        pass
+
+# Errors hierarchy
+for error_type in [nt.CellError, nt.AbiEncodeError, nt.AbiDecodeError]:
+    assert issubclass(error_type, nt.NekotonError)
+    assert issubclass(error_type, ValueError)
+assert issubclass(nt.MessageExpired, nt.Timeout)
+assert issubclass(nt.Timeout, nt.NekotonError)
+assert issubclass(nt.Timeout, TimeoutError)
+assert issubclass(nt.TransportError, nt.NekotonError)
+
+try:
+    nt.AccountState.from_bytes(b"invalid")
+    assert False, "invalid account state must be rejected"
+except nt.CellError as e:
+    assert isinstance(e, ValueError)
+
+try:
+    nt.ContractAbi.from_file(os.path.join(dirname, "missing.abi.json"))
+    assert False, "missing abi file must be rejected"
+except FileNotFoundError:
+    pass
+
+try:
+    nt.Asm.compile("UNKNOWN_OPCODE")
+    assert False, "invalid asm must be rejected"
+except ValueError:
+    pass
+
+# Pickling
+def roundtrip(value):
+    return [pickle.loads(pickle.dumps(value)), copy.copy(value), copy.deepcopy(value)]
+
+
+for item in roundtrip(depool_abi.function("onStakeAccept")):
+    assert item == depool_abi.function("onStakeAccept")
+for item in roundtrip(depool_abi.event("RoundStakeIsAccepted")):
+    assert item == depool_abi.event("RoundStakeIsAccepted")
+
+for item in roundtrip(tree):
+    assert item.to_bytes() == tree.to_bytes()
+    assert list(item) == list(tree)
+
+assert tree.root.compute_phase is not None
+phases = [
+    tree.root.storage_phase,
+    tree.root.credit_phase,
+    tree.root.compute_phase,
+    tree.root.action_phase,
+    tree.root.bounce_phase,
+]
+for phase in filter(None, phases):
+    for item in roundtrip(phase):
+        assert type(item) is type(phase)
+        assert item.to_bytes() == phase.to_bytes()
+
+for header in [
+    nt.InternalMessageHeader(value=nt.Tokens(1), dst=my_addr, bounce=True),
+    nt.ExternalInMessageHeader(my_addr),
+    nt.ExternalOutMessageHeader(src=my_addr, created_lt=1, created_at=2),
+]:
+    message = nt.Message(header=header)
+    for item in roundtrip(header):
+        assert type(item) is type(header)
+        assert nt.Message(header=item) == message
+
+cs = builder.build().as_slice()
+cs.advance(bits=16, refs=1)
+cs.shrink(bits=16)
+for item in roundtrip(cs):
+    assert repr(item) == repr(cs)
+    assert item.load_u16() == 123
+
+context = nt.SignatureContext(global_id=42, capabilities=0x4000000)
+for item in roundtrip(context):
+    assert item.apply(b"data") == context.apply(b"data")
+
+for seed in [nt.LegacySeed.generate(), nt.Bip39Seed.generate()]:
+    for item in roundtrip(seed):
+        assert type(item) is type(seed)
+        assert item.derive() == seed.derive()
+
+keypair = nt.KeyPair.generate()
+unsigned = _send_transaction.encode_external_message(
+    my_addr,
+    input={
+        "dest": my_addr,
+        "value": nt.Tokens(1),
+        "bounce": False,
+        "flags": 3,
+        "payload": nt.Cell(),
+    },
+    public_key=keypair.public_key,
+)
+for item in roundtrip(unsigned):
+    assert item.hash == unsigned.hash
+    assert item.sign(keypair, None) == unsigned.sign(keypair, None)
+
+# Items are pickled with their own ABI only
+item_abi = pickle.dumps(depool_abi.function("onStakeAccept"))
+assert len(item_abi) < len(pickle.dumps(depool_abi)) / 4
+
+for value in [
+    nt.TransactionType.Tick,
+    nt.AccountStatus.Frozen,
+    nt.AccountStatusChange.Deleted,
+    nt.MessageType.ExternalOut,
+]:
+    for item in roundtrip(value):
+        assert item == value and type(item) is type(value)
+
+for param, value in [
+    (nt.AbiUint(32), 5),
+    (
+        nt.AbiMap(nt.AbiUint(32), nt.AbiArray(nt.AbiOptional(nt.AbiCell()))),
+        {1: [None, nt.Cell()]},
+    ),
+    (
+        nt.AbiTuple([("value", nt.AbiVarUint(16)), ("ref", nt.AbiRef(nt.AbiString()))]),
+        {"value": 1, "ref": "abc"},
+    ),
+]:
+    expected = nt.Cell.build(abi=[("x", param)], value={"x": value})
+    for item in roundtrip(param):
+        assert type(item) is type(param)
+        assert nt.Cell.build(abi=[("x", item)], value={"x": value}) == expected
+
+for item in roundtrip(builder):
+    assert item.build() == builder.build()
+    assert item.is_exotic == builder.is_exotic
+
+options = [
+    nt.BehaviorModifiers(chksig_always_succeed=True),
+    nt.TraceOptions(max_steps=10, path="trace.jsonl"),
+    nt.ExecutionOverrides(
+        block_lt=1,
+        block_unixtime=2,
+        seed_block=bytes(32),
+        behavior_modifiers=nt.BehaviorModifiers(chksig_always_succeed=True),
+    ),
+]
+for value in options:
+    for item in roundtrip(value):
+        assert repr(item) == repr(value)
+for item in roundtrip(options[2]):
+    assert item.seed_block == bytes(32)
+    assert item.behavior_modifiers.chksig_always_succeed
+
 
 # Subscriptions
 async def main():
@@ -0,0 +1,125 @@
+import asyncio
+import logging
+from collections import Counter
+from typing import List, Optional
+
+import nekoton as nt
+from fixtures import EmptyHandler, fund, make_config
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+receiver_addr = nt.Address(
+    "0:2222222222222222222222222222222222222222222222222222222222222222"
+)
+
+
+class CountingHandler(EmptyHandler):
+    """Forwards requests to the local blockchain and counts them."""
+
+    def __init__(self, inner: nt.LocalTransport):
+        super().__init__()
+        self.inner = inner
+        self.calls: Counter = Counter()
+
+    async def get_account_state(self, address: nt.Address) -> Optional[nt.AccountState]:
+        self.calls["get_account_state"] += 1
+        return await self.inner.get_account_state(address)
+
+    async def get_transactions(
+        self, address: nt.Address, lt: int, limit: int
+    ) -> List[nt.Transaction]:
+        self.calls["get_transactions"] += 1
+        return await self.inner.get_transactions(address, lt=lt, limit=limit)
+
+    async def get_transaction(self, hash: bytes) -> Optional[nt.Transaction]:
+        self.calls["get_transaction"] += 1
+        return await self.inner.get_transaction(hash)
+
+    async def get_dst_transaction(self, message_hash: bytes) -> Optional[nt.Transaction]:
+        self.calls["get_dst_transaction"] += 1
+        return await self.inner.get_dst_transaction(message_hash)
+
+    async def get_blockchain_config(self, force: bool) -> nt.BlockchainConfig:
+        self.calls["get_blockchain_config"] += 1
+        return await self.inner.get_blockchain_config(force=force)
+
+
+async def main():
+    config = make_config()
+    local = nt.LocalTransport(config)
+    wallet = nt.contracts.HighloadWalletV2(local, nt.KeyPair.generate())
+    executor = nt.TransactionExecutor(config)
+    local.set_account(wallet.address, fund(executor, wallet.address, nt.Tokens(10)))
+
+    sent = [
+        await wallet.send(dst=receiver_addr, value=nt.Tokens(value))
+        for value in [1, 2, 3]
+    ]
+
+    uncached = nt.CustomTransport(EmptyHandler())
+    assert uncached.cache_stats is None
+
+    handler = CountingHandler(local)
+    transport = nt.CustomTransport(
+        handler,
+        cache=nt.CacheConfig(account_state_ttl=60, blockchain_config_ttl=60),
+    )
+
+    # Account states are served from the cache within the ttl
+    first = await transport.get_account_state(wallet.address)
+    second = await transport.get_account_state(wallet.address)
+    assert first is not None and second is not None
+    assert first.to_bytes() == second.to_bytes()
+    assert handler.calls["get_account_state"] == 1
+    stats = transport.cache_stats
+    assert stats.misses == 1 and stats.hits == 1
+
+    # Config is cached unless the request is forced
+    config = await transport.get_blockchain_config()
+    cached_config = await transport.get_blockchain_config()
+    assert cached_config.global_id == config.global_id
+    assert handler.calls["get_blockchain_config"] == 1
+    stats = transport.cache_stats
+    assert stats.misses == 2 and stats.hits == 2
+
+    await transport.get_blockchain_config(force=True)
+    assert handler.calls["get_blockchain_config"] == 2
+    stats = transport.cache_stats
+    assert stats.misses == 2 and stats.hits == 2
+
+    # Transactions are immutable and stay cached, including fetched pages
+    transactions = await transport.get_transactions(wallet.address, limit=1)
+    assert transactions == [sent[-1]]
+    tx = transactions[0]
+    assert await transport.get_transaction(tx.hash) == tx
+    assert await transport.get_transaction(tx.hash) == tx
+    assert transport.cache_stats.hits == 4
+
+    history = [tx async for tx in transport.iter_transactions(wallet.address, limit=3)]
+    assert history == sent[::-1]
+    for tx in history:
+        assert await transport.get_transaction(tx.hash) == tx
+    assert transport.cache_stats.hits == 7
+    assert handler.calls["get_transaction"] == 0
+
+    # Only found destination transactions are cached
+    out_msg_hash = sent[0].get_out_msgs()[0].hash
+    dst_tx = await transport.get_dst_transaction(out_msg_hash)
+    assert dst_tx is not None
+    assert await transport.get_dst_transaction(out_msg_hash) == dst_tx
+    assert await transport.get_transaction(dst_tx.hash) == dst_tx
+    assert handler.calls["get_dst_transaction"] == 1
+
+    assert await transport.get_dst_transaction(bytes(32)) is None
+    assert await transport.get_dst_transaction(bytes(32)) is None
+    assert handler.calls["get_dst_transaction"] == 3
+
+    stats = transport.cache_stats
+    assert stats.hits == 9 and stats.misses == 5
+    assert handler.calls["get_transaction"] == 0
+
+
+if __name__ == "__main__":
+    asyncio.run(main())
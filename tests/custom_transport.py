@@ -0,0 +1,183 @@
+import asyncio
+import json
+import logging
+from typing import Dict, List, Optional
+
+import nekoton as nt
+from fixtures import make_config
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+
+class InMemoryHandler:
+    def __init__(self):
+        self.states: Dict[nt.Address, nt.AccountState] = {}
+        self.transactions: Dict[bytes, nt.Transaction] = {}
+        self.sent: List[nt.Message] = []
+
+    async def get_account_state(self, address: nt.Address) -> Optional[nt.AccountState]:
+        return self.states.get(address)
+
+    async def get_transactions(
+        self, address: nt.Address, lt: int, limit: int
+    ) -> List[nt.Transaction]:
+        return []
+
+    async def get_transaction(self, hash: bytes) -> Optional[nt.Transaction]:
+        return self.transactions.get(hash)
+
+    async def get_dst_transaction(self, message_hash: bytes) -> Optional[nt.Transaction]:
+        return None
+
+    async def get_accounts_by_code_hash(
+        self, code_hash: bytes, limit: int, continuation: Optional[nt.Address]
+    ) -> List[nt.Address]:
+        return []
+
+    async def get_blockchain_config(self, force: bool) -> nt.BlockchainConfig:
+        raise RuntimeError("Config is not available")
+
+    async def send_message(self, message: nt.Message) -> None:
+        self.sent.append(message)
+
+
+class LocalHandler:
+    """Forwards all requests to the local blockchain."""
+
+    def __init__(self, inner: nt.LocalTransport):
+        self.inner = inner
+
+    async def get_account_state(self, address: nt.Address) -> Optional[nt.AccountState]:
+        return await self.inner.get_account_state(address)
+
+    async def get_transactions(
+        self, address: nt.Address, lt: int, limit: int
+    ) -> List[nt.Transaction]:
+        return await self.inner.get_transactions(address, lt=lt, limit=limit)
+
+    async def get_transaction(self, hash: bytes) -> Optional[nt.Transaction]:
+        return await self.inner.get_transaction(hash)
+
+    async def get_dst_transaction(self, message_hash: bytes) -> Optional[nt.Transaction]:
+        return await self.inner.get_dst_transaction(message_hash)
+
+    async def get_accounts_by_code_hash(
+        self, code_hash: bytes, limit: int, continuation: Optional[nt.Address]
+    ) -> List[nt.Address]:
+        return await self.inner.get_accounts_by_code_hash(
+            code_hash, continuation=continuation, limit=limit
+        )
+
+    async def get_blockchain_config(self, force: bool) -> nt.BlockchainConfig:
+        return await self.inner.get_blockchain_config(force=force)
+
+    async def send_message(self, message: nt.Message) -> None:
+        header = message.header
+        assert isinstance(header, nt.ExternalInMessageHeader)
+        external_message = nt.SignedExternalMessage(
+            header.dst,
+            self.inner.clock.now_sec + 60,
+            body=message.body,
+            state_init=message.state_init,
+        )
+        await self.inner.send_external_message_nowait(external_message)
+
+
+async def main():
+    handler = InMemoryHandler()
+    transport = nt.CustomTransport(handler)
+    assert transport.handler is handler
+
+    await transport.check_connection()
+
+    address = nt.Address(
+        "0:0000000000000000000000000000000000000000000000000000000000000000"
+    )
+    assert await transport.get_account_state(address) is None
+    assert await transport.get_transactions(address) == []
+    assert await transport.get_transaction(bytes(32)) is None
+
+    # Paged iterators don't fetch anything until the first iteration
+    requests = []
+
+    class CountingHandler(InMemoryHandler):
+        async def get_transactions(self, address, lt, limit):
+            requests.append(lt)
+            return []
+
+    counting = nt.CustomTransport(CountingHandler())
+    history = counting.iter_transactions(address)
+    await asyncio.sleep(0.1)
+    assert requests == []
+    assert [tx async for tx in history] == []
+    assert len(requests) == 1
+
+    # Imported pending messages are not sent again by default
+    message = nt.SignedExternalMessage(address, transport.clock.now_sec + 600)
+    pending = await transport.send_external_message_nowait(message)
+    assert len(handler.sent) == 1
+
+    exported = json.loads(await transport.export_pending_messages())
+    assert [item["hash"] for item in exported] == [pending.hash.hex()]
+    assert pending.cancel()
+
+    exported.append(dict(exported[0], boc="invalid"))
+    data = json.dumps(exported)
+
+    other_handler = InMemoryHandler()
+    other_transport = nt.CustomTransport(other_handler)
+    resumed = await other_transport.import_pending_messages(data)
+    assert len(resumed) == 2
+    assert isinstance(resumed[0], nt.PendingMessage)
+    assert resumed[0].hash == pending.hash
+    assert isinstance(resumed[1], Exception)
+    assert other_handler.sent == []
+    assert resumed[0].cancel()
+
+    other_handler = InMemoryHandler()
+    other_transport = nt.CustomTransport(other_handler)
+    resumed = await other_transport.import_pending_messages(data, rebroadcast=True)
+    assert isinstance(resumed[0], nt.PendingMessage)
+    assert len(other_handler.sent) == 1
+    assert resumed[0].cancel()
+
+    # Requests are forwarded to the local blockchain
+    config = make_config()
+    local = nt.LocalTransport(config, check_signature=False)
+    transport = nt.CustomTransport(LocalHandler(local))
+
+    wallet = nt.contracts.HighloadWalletV2(transport, nt.KeyPair.generate())
+    funding = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(10), dst=wallet.address, src=address, bounce=False
+        )
+    )
+    _, wallet_state = nt.TransactionExecutor(config).execute(funding, None)
+    local.set_account(wallet.address, wallet_state)
+
+    async with transport.account_states(wallet.address) as states:
+        async for state in states:
+            assert state is not None
+            assert state.status == nt.AccountStatus.Uninit
+            break
+
+    receiver = nt.Address(
+        "0:2222222222222222222222222222222222222222222222222222222222222222"
+    )
+    async with transport.account_transactions(wallet.address) as batches:
+        tx = await wallet.send(dst=receiver, value=nt.Tokens(1))
+        assert not tx.aborted
+        async for batch, _ in batches:
+            assert batch == [tx]
+            break
+
+    traced = [item async for item in transport.trace_transaction(tx)]
+    assert len(traced) == 1
+    assert traced[0].account == receiver.account
+    assert local.get_account(receiver) is not None
+
+
+if __name__ == "__main__":
+    asyncio.run(main())
@@ -0,0 +1,118 @@
+import asyncio
+import logging
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+import nekoton as nt
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+bad_endpoint = "http://127.0.0.1:1"
+address = nt.Address(
+    "-1:0000000000000000000000000000000000000000000000000000000000000000"
+)
+
+
+class RejectingEndpoint:
+    """Local JRPC endpoint which rejects all requests with an error response."""
+
+    def __init__(self):
+        self.requests = 0
+        endpoint = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def do_POST(self):
+                self.rfile.read(int(self.headers.get("Content-Length", 0)))
+                endpoint.requests += 1
+
+                body = b'{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"rejected"}}'
+                self.send_response(200)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(body)))
+                self.end_headers()
+                self.wfile.write(body)
+
+            def log_message(self, format, *args):
+                pass
+
+        self.server = ThreadingHTTPServer(("127.0.0.1", 0), Handler)
+        self.url = f"http://127.0.0.1:{self.server.server_port}"
+        threading.Thread(target=self.server.serve_forever, daemon=True).start()
+
+    def close(self):
+        self.server.shutdown()
+        self.server.server_close()
+
+
+async def request_endpoint(transport: nt.JrpcTransport) -> nt.TransportError:
+    try:
+        await transport.get_account_state(address)
+    except nt.TransportError as e:
+        return e
+    assert False, "request must be rejected"
+
+
+async def main():
+    try:
+        nt.JrpcTransport(endpoint=bad_endpoint, endpoints=[bad_endpoint])
+        assert False, "endpoint and endpoints must be exclusive"
+    except ValueError:
+        pass
+
+    try:
+        nt.JrpcTransport(endpoints=[])
+        assert False, "endpoints must not be empty"
+    except ValueError:
+        pass
+
+    # A single endpoint is used without the failover layer
+    transport = nt.JrpcTransport(endpoint=bad_endpoint)
+    assert transport.endpoints == [bad_endpoint]
+    assert transport.active_endpoint == bad_endpoint
+    e = await request_endpoint(transport)
+    assert e.retriable
+    assert e.endpoint is None
+
+    first = RejectingEndpoint()
+    second = RejectingEndpoint()
+    try:
+        # Connection errors are retried on the next endpoint
+        transport = nt.JrpcTransport(
+            endpoints=[bad_endpoint, first.url], health_check_interval=3600
+        )
+        assert transport.endpoints == [bad_endpoint, first.url]
+        e = await request_endpoint(transport)
+        assert e.endpoint == first.url
+        assert not e.retriable
+        assert transport.active_endpoint == first.url
+        assert first.requests == 1
+
+        # Rejected requests are not sent to other endpoints
+        transport = nt.JrpcTransport(
+            endpoints=[first.url, second.url], health_check_interval=3600
+        )
+        e = await request_endpoint(transport)
+        assert e.endpoint == first.url
+        assert transport.active_endpoint == first.url
+        assert first.requests == 2 and second.requests == 0
+
+        # Round robin reports the endpoint of the last request
+        transport = nt.JrpcTransport(
+            endpoints=[first.url, second.url],
+            strategy="round_robin",
+            health_check_interval=3600,
+        )
+        for expected in [first.url, second.url, first.url]:
+            e = await request_endpoint(transport)
+            assert e.endpoint == expected
+            assert transport.active_endpoint == expected
+        assert first.requests == 4 and second.requests == 1
+    finally:
+        first.close()
+        second.close()
+
+
+if __name__ == "__main__":
+    asyncio.run(main())
@@ -0,0 +1,160 @@
+from typing import Dict, List, Optional
+
+import nekoton as nt
+
+GLOBAL_ID = 42
+
+giver_addr = nt.Address(
+    "-1:1111111111111111111111111111111111111111111111111111111111111111"
+)
+
+
+def _single_entry_dict(key_bits: int, value: nt.CellBuilder) -> nt.Cell:
+    # Dictionary root with a single zero key (`hml_same$11 v:0 n:key_bits`)
+    root = nt.CellBuilder()
+    root.store_ones(2)
+    root.store_bit_zero()
+    root.store_uint(key_bits, key_bits.bit_length())
+    root.store_builder(value)
+    return root.build()
+
+
+def _account_id(byte: int) -> nt.Cell:
+    param = nt.CellBuilder()
+    param.store_bytes(bytes([byte]) * 32)
+    return param.build()
+
+
+def _global_version(capabilities: int) -> nt.Cell:
+    param = nt.CellBuilder()
+    param.store_u8(0xC4)
+    param.store_u32(1)
+    param.store_u64(capabilities)
+    return param.build()
+
+
+def _workchains() -> nt.Cell:
+    descr = nt.CellBuilder()
+    descr.store_u8(0xA6)
+    descr.store_u32(0)  # enabled_since
+    descr.store_zeros(24)  # actual_min_split, min_split, max_split
+    descr.store_ones(3)  # basic, active, accept_msgs
+    descr.store_zeros(13)  # flags
+    descr.store_zeros(512)  # zerostate root and file hashes
+    descr.store_u32(0)  # version
+    descr.store_uint(1, 4)  # wfmt_basic
+    descr.store_i32(0)  # vm_version
+    descr.store_u64(0)  # vm_mode
+
+    param = nt.CellBuilder()
+    param.store_bit_one()
+    param.store_reference(_single_entry_dict(32, descr))
+    return param.build()
+
+
+def _storage_prices() -> nt.Cell:
+    prices = nt.CellBuilder()
+    prices.store_u8(0xCC)
+    prices.store_u32(0)  # utime_since
+    for price in [1, 500, 1000, 500000]:
+        prices.store_u64(price)
+    return _single_entry_dict(32, prices)
+
+
+def _gas_prices(gas_price: int, special_gas_limit: int) -> nt.Cell:
+    param = nt.CellBuilder()
+    param.store_u8(0xDE)
+    param.store_u64(gas_price << 16)
+    param.store_u64(1000000)  # gas_limit
+    param.store_u64(special_gas_limit)
+    param.store_u64(10000)  # gas_credit
+    param.store_u64(10000000)  # block_gas_limit
+    param.store_u64(100000000)  # freeze_due_limit
+    param.store_u64(1000000000)  # delete_due_limit
+    return param.build()
+
+
+def _msg_forward_prices(lump_price: int, bit_price: int, cell_price: int) -> nt.Cell:
+    param = nt.CellBuilder()
+    param.store_u8(0xEA)
+    param.store_u64(lump_price)
+    param.store_u64(bit_price << 16)
+    param.store_u64(cell_price << 16)
+    param.store_u32(98304)  # ihr_price_factor
+    param.store_u16(21845)  # first_frac
+    param.store_u16(21845)  # next_frac
+    return param.build()
+
+
+def _no_special_accounts() -> nt.Cell:
+    param = nt.CellBuilder()
+    param.store_bit_zero()
+    return param.build()
+
+
+def make_config(capabilities: int = 0) -> nt.BlockchainConfig:
+    """Builds a minimal blockchain config with mainnet-like prices."""
+    params: Dict[int, nt.Cell] = {
+        0: _account_id(0x55),
+        1: _account_id(0x33),
+        8: _global_version(capabilities),
+        12: _workchains(),
+        18: _storage_prices(),
+        20: _gas_prices(10000, 100000000),
+        21: _gas_prices(1000, 1000000),
+        24: _msg_forward_prices(10000000, 10000, 1000000),
+        25: _msg_forward_prices(1000000, 1000, 100000),
+        31: _no_special_accounts(),
+    }
+
+    dict_abi = [("params", nt.AbiMap(nt.AbiUint(32), nt.AbiCell()))]
+    slice = nt.Cell.build(abi=dict_abi, value={"params": params}).as_slice()
+    assert slice.load_bit()
+    return nt.BlockchainConfig.from_params_dict_cell(slice.load_reference(), GLOBAL_ID)
+
+
+def fund(
+    executor: nt.TransactionExecutor, address: nt.Address, value: nt.Tokens
+) -> nt.AccountState:
+    """Creates an uninit account with the specified balance."""
+    message = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=value, dst=address, src=giver_addr, bounce=False
+        )
+    )
+    tx, state = executor.execute(message, None)
+    assert not tx.aborted
+    assert state is not None
+    return state
+
+
+class EmptyHandler:
+    """Custom transport handler without any accounts."""
+
+    def __init__(self):
+        self.sent: List[nt.Message] = []
+
+    async def get_account_state(self, address: nt.Address) -> Optional[nt.AccountState]:
+        return None
+
+    async def get_transactions(
+        self, address: nt.Address, lt: int, limit: int
+    ) -> List[nt.Transaction]:
+        return []
+
+    async def get_transaction(self, hash: bytes) -> Optional[nt.Transaction]:
+        return None
+
+    async def get_dst_transaction(self, message_hash: bytes) -> Optional[nt.Transaction]:
+        return None
+
+    async def get_accounts_by_code_hash(
+        self, code_hash: bytes, limit: int, continuation: Optional[nt.Address]
+    ) -> List[nt.Address]:
+        return []
+
+    async def get_blockchain_config(self, force: bool) -> nt.BlockchainConfig:
+        return make_config()
+
+    async def send_message(self, message: nt.Message) -> None:
+        self.sent.append(message)
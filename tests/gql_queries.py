@@ -59,5 +59,25 @@ async def main():
     for addr, state in accounts:
         print(addr, state)
 
+    # Block walking subscriptions
+    block_walking = nt.GqlTransport(
+        endpoints=["mainnet.evercloud.dev/89a3b8f46a484f2ea3bdd364ddaee3a3"],
+        polling_mode="block_walking",
+    )
+    elector_addr = nt.Address(
+        "-1:3333333333333333333333333333333333333333333333333333333333333333"
+    )
+    async with block_walking.account_transactions(elector_addr) as batches:
+        async for batch, batch_info in batches:
+            assert len(batch) > 0
+            assert batch_info.min_lt <= batch_info.max_lt
+            break
+
+    try:
+        nt.RecordingTransport(block_walking, "records.jsonl")
+        assert False, "block walking must not be silently ignored"
+    except ValueError:
+        pass
+
 
 asyncio.run(main())
@@ -0,0 +1,412 @@
+import asyncio
+import json
+import logging
+import os
+import sys
+import tempfile
+import threading
+import time
+
+import nekoton as nt
+from fixtures import fund, giver_addr, make_config
+from nekoton.contracts.ever_wallet import _send_transaction
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+
+async def main():
+    config = make_config()
+    executor = nt.TransactionExecutor(config)
+
+    transport = nt.LocalTransport(config)
+    await transport.check_connection()
+    assert transport.accounts == []
+
+    keypair = nt.KeyPair.generate()
+    wallet = nt.contracts.HighloadWalletV2(transport, keypair)
+    assert await transport.get_account_state(wallet.address) is None
+
+    wallet_state = fund(executor, wallet.address, nt.Tokens(10))
+    assert wallet_state.status == nt.AccountStatus.Uninit
+    transport.set_account(wallet.address, wallet_state)
+    assert transport.accounts == [wallet.address]
+
+    # Deploy the wallet and send tokens to a non-existing account
+    receiver_addr = nt.Address(
+        "0:2222222222222222222222222222222222222222222222222222222222222222"
+    )
+    tx = await wallet.send(dst=receiver_addr, value=nt.Tokens(1))
+    assert not tx.aborted
+    assert tx.out_msgs_len == 1
+
+    wallet_state = await transport.get_account_state(wallet.address)
+    assert wallet_state is not None
+    assert wallet_state.status == nt.AccountStatus.Active
+    assert wallet_state.balance < nt.Tokens(9)
+
+    # Execution is reproducible with fixed block params
+    funding = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(1), dst=wallet.address, src=giver_addr, bounce=False
+        )
+    )
+    block_lt = wallet_state.last_trans_lt + 1000
+    fixed = nt.ExecutionOverrides(
+        block_lt=block_lt, block_unixtime=1700000000, seed_block=bytes(32)
+    )
+    first, first_state = executor.execute(funding, wallet_state, fixed)
+    second, second_state = executor.execute(funding, wallet_state, fixed)
+    assert first.hash == second.hash
+    assert first_state.to_bytes() == second_state.to_bytes()
+    assert first.lt == block_lt and first.now == 1700000000
+
+    shifted_lt = nt.ExecutionOverrides(
+        block_lt=block_lt + 1000, block_unixtime=1700000000, seed_block=bytes(32)
+    )
+    shifted, _ = executor.execute(funding, wallet_state, shifted_lt)
+    assert shifted.hash != first.hash
+
+    # Failed compute phase is reported with its exit code and used gas
+    try:
+        executor.execute(
+            nt.Message(header=nt.ExternalInMessageHeader(wallet.address)), wallet_state
+        )
+        assert False, "external message without a body must not be accepted"
+    except nt.ExecutionError as e:
+        assert e.exit_code is not None and e.exit_code != 0
+        assert e.gas_used is not None and e.gas_used > 0
+
+    # Execution releases the GIL, so other threads keep running
+    loop_code = nt.CellBuilder()
+    loop_code.store_u8(0xEB)  # AGAINEND
+    loop_init = nt.StateInit(loop_code.build(), nt.Cell())
+    loop_deploy = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(100),
+            dst=loop_init.compute_address(),
+            src=giver_addr,
+            bounce=False,
+        ),
+        state_init=loop_init,
+    )
+
+    ticks = 0
+    stopped = threading.Event()
+
+    def ticker():
+        nonlocal ticks
+        while not stopped.wait(0.001):
+            ticks += 1
+
+    switch_interval = sys.getswitchinterval()
+    # NOTE: the ticker can only run while the GIL is released
+    sys.setswitchinterval(10.0)
+    thread = threading.Thread(target=ticker)
+    thread.start()
+    try:
+        time.sleep(0.01)
+        ticks_before = ticks
+        loop_tx, _ = executor.execute(
+            loop_deploy, None, nt.ExecutionOverrides(gas_limit=1000000)
+        )
+        ticks_during = ticks - ticks_before
+    finally:
+        stopped.set()
+        thread.join()
+        sys.setswitchinterval(switch_interval)
+    assert loop_tx.compute_phase.exit_code == -14
+    assert ticks_during > 0
+
+    # Tick and tock transactions require the matching special flag
+    special_init = nt.StateInit(nt.Cell(), nt.Cell(), special=(True, False))
+    assert "special=(True, False)" in repr(special_init)
+    special_addr = special_init.compute_address(-1)
+    deploy = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(1), dst=special_addr, src=giver_addr, bounce=False
+        ),
+        state_init=special_init,
+    )
+    _, special_state = executor.execute(deploy, None, fixed)
+    assert special_state.status == nt.AccountStatus.Active
+    assert special_state.state_init.special == (True, False)
+
+    tick, _ = executor.execute_tick_tock(
+        special_state,
+        nt.TransactionType.Tick,
+        nt.ExecutionOverrides(block_unixtime=1700000000),
+    )
+    assert tick.type == nt.TransactionType.Tick
+    assert tick.now == 1700000000
+
+    for account, kind in [
+        (special_state, nt.TransactionType.Tock),
+        (wallet_state, nt.TransactionType.Tick),
+        (special_state, nt.TransactionType.Ordinary),
+    ]:
+        try:
+            executor.execute_tick_tock(account, kind)
+            assert False, f"{kind} must be rejected"
+        except ValueError:
+            pass
+
+    # Cascades report undelivered messages and honor the limit
+    unknown_addr = nt.Address(
+        "0:3333333333333333333333333333333333333333333333333333333333333333"
+    )
+    bounced = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(1), dst=unknown_addr, src=giver_addr, bounce=True
+        )
+    )
+    same_block = nt.ExecutionOverrides(block_unixtime=1700000000, seed_block=bytes(32))
+    result = executor.execute_tree(bounced, {}, overrides=same_block)
+    assert result.tree.root.now == 1700000000
+    assert result.tree.children == []
+    assert not result.truncated
+    assert [message.dst for message in result.pending] == [giver_addr]
+    assert result.failed == []
+
+    result = executor.execute_tree(bounced, {giver_addr: None}, overrides=same_block)
+    assert len(result.tree.children) == 1
+    assert result.pending == []
+    assert result.accounts[giver_addr] is not None
+
+    result = executor.execute_tree(
+        bounced, {giver_addr: None}, max_messages=0, overrides=same_block
+    )
+    assert result.truncated
+    assert result.tree.children == []
+    assert [message.dst for message in result.pending] == [giver_addr]
+    assert result.accounts[giver_addr] is None
+
+    # Traces are capped, written to a file and kept on failures
+    _, _, steps = executor.execute_traced(funding, wallet_state, fixed)
+    assert len(steps) > 3
+    _, _, capped = executor.execute_traced(
+        funding, wallet_state, fixed, trace=nt.TraceOptions(max_steps=3)
+    )
+    assert [step.instruction for step in capped] == [
+        step.instruction for step in steps[:3]
+    ]
+
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "trace.jsonl")
+        _, _, in_memory = executor.execute_traced(
+            funding, wallet_state, fixed, trace=nt.TraceOptions(path=path)
+        )
+        assert in_memory == []
+        with open(path) as f:
+            lines = [json.loads(line) for line in f]
+        assert [line["instruction"] for line in lines] == [
+            step.instruction for step in steps
+        ]
+
+    try:
+        nt.TraceOptions(max_steps=0)
+        assert False, "zero max_steps must be rejected"
+    except ValueError:
+        pass
+
+    body = nt.CellBuilder()
+    body.store_raw(bytes(64), 512)
+    body.store_u32(wallet.wallet_id)
+    body.store_u32(int(time.time()) + 60)
+    body.store_u32(0)
+    body.store_bit_zero()
+    try:
+        executor.execute_traced(
+            nt.Message(
+                header=nt.ExternalInMessageHeader(wallet.address), body=body.build()
+            ),
+            wallet_state,
+        )
+        assert False, "external message with an invalid signature must not be accepted"
+    except nt.ExecutionError as e:
+        assert e.exit_code is not None and e.exit_code != 0
+        assert len(e.trace) > 0
+        cell_loads = [hash for step in e.trace for hash in step.cell_loads]
+        assert wallet_state.state_init.data.repr_hash in cell_loads
+
+    # Functions are traced without a transaction
+    send_input = {
+        "dest": receiver_addr,
+        "value": nt.Tokens(1),
+        "bounce": False,
+        "flags": 3,
+        "payload": nt.Cell(),
+    }
+    output = _send_transaction.call(
+        wallet_state, send_input, trace=nt.TraceOptions(max_steps=100)
+    )
+    assert output.exit_code != 0
+    assert output.output is None
+    assert 0 < len(output.trace) <= 100
+
+    # Responsible calls are traced as internal messages
+    output = _send_transaction.call(
+        wallet_state, send_input, responsible=True, trace=nt.TraceOptions()
+    )
+    untraced = _send_transaction.call(wallet_state, send_input, responsible=True)
+    assert output.exit_code == untraced.exit_code
+    assert len(output.trace) > 0
+
+    # Produced internal message is delivered right away
+    receiver_state = transport.get_account(receiver_addr)
+    assert receiver_state is not None
+    assert receiver_state.status == nt.AccountStatus.Uninit
+    assert nt.Tokens("0.9") < receiver_state.balance <= nt.Tokens(1)
+
+    out_msg_hash = tx.get_out_msgs()[0].hash
+    receiver_tx = await transport.get_dst_transaction(out_msg_hash)
+    assert receiver_tx is not None
+    assert await transport.get_transaction(receiver_tx.hash) == receiver_tx
+    assert await transport.get_transactions(receiver_addr) == [receiver_tx]
+
+    traced = [item async for item in transport.trace_transaction(tx, yield_root=True)]
+    assert traced == [tx, receiver_tx]
+
+    trace = transport.trace_transaction(tx)
+    tree = await trace.tree()
+    assert tree.root == tx
+    assert [child.root for child in tree.children] == [receiver_tx]
+    assert not trace.truncated
+
+    # Streamed transactions are kept for the tree
+    trace = transport.trace_transaction(tx)
+    assert [item async for item in trace] == [receiver_tx]
+    tree = await trace.tree()
+    assert [child.root for child in tree.children] == [receiver_tx]
+
+    for kwargs in [dict(max_depth=0), dict(max_count=1)]:
+        trace = transport.trace_transaction(tx, **kwargs)
+        tree = await trace.tree()
+        assert tree.root == tx and tree.children == []
+        assert trace.truncated
+
+    for kwargs in [dict(max_count=0), dict(timeout=-1.0)]:
+        try:
+            transport.trace_transaction(tx, **kwargs)
+            assert False, f"invalid trace params must be rejected: {kwargs}"
+        except ValueError:
+            pass
+
+    # Subscriptions are notified about the local transactions
+    async with transport.account_transactions(wallet.address) as batches:
+        await wallet.send(dst=receiver_addr, value=nt.Tokens(1))
+        async for batch, _ in batches:
+            assert len(batch) == 1
+            break
+
+    # Removing accounts doesn't start the watcher
+    watcher = transport.watch_accounts([wallet.address, receiver_addr])
+    await watcher.remove([receiver_addr])
+    assert watcher.addresses == [wallet.address]
+    await watcher.close()
+
+    # Accounts are watched through shared subscriptions
+    transport.subscription_options = nt.SubscriptionOptions(interval=0.1)
+    async with transport.watch_accounts([wallet.address]) as watcher:
+        await asyncio.gather(watcher.add([receiver_addr]), watcher.add([receiver_addr]))
+        assert sorted(map(str, watcher.addresses)) == sorted(
+            map(str, [wallet.address, receiver_addr])
+        )
+
+        tx = await wallet.send(dst=receiver_addr, value=nt.Tokens(1))
+        received = {}
+        async for address, batch, info in watcher:
+            assert not info.gap
+            received[str(address)] = batch
+            if len(received) == 2:
+                break
+        assert received[str(wallet.address)] == [tx]
+        assert len(received[str(receiver_addr)]) == 1
+
+        await watcher.remove([receiver_addr])
+        assert watcher.addresses == [wallet.address]
+
+    # History is paged in both orders
+    history = await transport.get_transactions(wallet.address)
+    assert len(history) == 3
+    assert [tx async for tx in transport.iter_transactions(wallet.address)] == history
+
+    oldest = transport.iter_transactions(wallet.address, order="asc", limit=2)
+    assert [tx async for tx in oldest] == history[::-1][:2]
+
+    # Accounts are walked by code hash with their states
+    code_hash = wallet_state.state_init.code_hash
+    assert code_hash is not None
+    async with transport.iter_accounts_by_code_hash(
+        code_hash, with_states=True, page_size=1
+    ) as accounts:
+        items = [item async for item in accounts]
+    assert len(items) == 1
+    assert items[0][0] == wallet.address
+    assert items[0][1].status == nt.AccountStatus.Active
+
+    accounts = transport.iter_accounts_by_code_hash(code_hash)
+    await accounts.close()
+    assert [item async for item in accounts] == []
+
+    # Failed sends raise execution errors
+    impostor = nt.contracts.HighloadWalletV2.from_address(
+        transport, nt.KeyPair.generate(), wallet.address
+    )
+    try:
+        await impostor.send(dst=receiver_addr, value=nt.Tokens(1))
+        assert False, "message with an invalid signature must not be accepted"
+    except nt.ExecutionError as e:
+        assert e.exit_code is not None and e.exit_code != 0
+        assert e.transaction is None
+
+    overspend = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(1000), dst=receiver_addr, bounce=False
+        )
+    )
+    try:
+        await wallet.send_raw([(overspend, 0)])
+        assert False, "transaction with a failed action phase must be reported"
+    except nt.ExecutionError as e:
+        assert e.transaction is not None and e.transaction.aborted
+        assert await transport.get_transaction(e.transaction.hash) == e.transaction
+
+    transport.set_account(receiver_addr, None)
+    assert transport.get_account(receiver_addr) is None
+
+    # Forks take the config from the forked transport and keep changes local
+    forked = nt.LocalTransport(fork=transport, timeout=5.0)
+    forked_config = await forked.get_blockchain_config()
+    assert forked_config.global_id == config.global_id
+    assert forked_config.capabilities == config.capabilities
+
+    wallet_state = await transport.get_account_state(wallet.address)
+    forked_state = await forked.get_account_state(wallet.address)
+    assert forked_state.to_bytes() == wallet_state.to_bytes()
+
+    # Concurrent sends are processed one cascade at a time
+    assert await forked.get_account_state(receiver_addr) is None
+    forked_wallet = nt.contracts.HighloadWalletV2(forked, keypair)
+    txs = await asyncio.gather(
+        forked_wallet.send(dst=receiver_addr, value=nt.Tokens(1)),
+        forked_wallet.send(dst=receiver_addr, value=nt.Tokens(2)),
+    )
+    first, second = sorted(txs, key=lambda tx: tx.lt)
+    first_dst = await forked.get_dst_transaction(first.get_out_msgs()[0].hash)
+    assert first_dst is not None
+    assert first.lt < first_dst.lt < second.lt
+
+    original_state = await transport.get_account_state(wallet.address)
+    assert original_state.to_bytes() == wallet_state.to_bytes()
+
+    try:
+        nt.LocalTransport()
+        assert False, "local transport without config and fork must be rejected"
+    except ValueError:
+        pass
+
+
+if __name__ == "__main__":
+    asyncio.run(main())
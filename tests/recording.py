@@ -0,0 +1,61 @@
+import asyncio
+import logging
+import os
+import tempfile
+
+import nekoton as nt
+from fixtures import fund, make_config
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+
+async def main():
+    config = make_config()
+
+    # Stopped clock makes external messages reproducible
+    clock = nt.Clock(frozen_at_ms=1700000000000)
+    assert clock.frozen_at_ms == 1700000000000
+    assert clock.now_ms == clock.frozen_at_ms
+
+    local = nt.LocalTransport(config, clock=clock)
+    keypair = nt.KeyPair.generate()
+    wallet_addr = nt.contracts.HighloadWalletV2.compute_address(keypair.public_key)
+    receiver_addr = nt.Address(
+        "0:2222222222222222222222222222222222222222222222222222222222222222"
+    )
+
+    executor = nt.TransactionExecutor(config, clock=clock)
+    local.set_account(wallet_addr, fund(executor, wallet_addr, nt.Tokens(10)))
+
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "records.jsonl")
+
+        recording = nt.RecordingTransport(local, path)
+        wallet = nt.contracts.HighloadWalletV2(recording, keypair)
+        recorded_tx = await wallet.send(dst=receiver_addr, value=nt.Tokens(1))
+
+        replay = nt.ReplayTransport(path)
+        assert replay.clock.frozen_at_ms == clock.frozen_at_ms
+
+        wallet = nt.contracts.HighloadWalletV2(replay, keypair)
+        replayed_tx = await wallet.send(dst=receiver_addr, value=nt.Tokens(1))
+        assert replayed_tx == recorded_tx
+
+        # Sends are matched by the call order, so extra sends are not replayed
+        try:
+            await wallet.send(dst=receiver_addr, value=nt.Tokens(2))
+            assert False, "send without a recorded response must fail"
+        except nt.TransportError:
+            pass
+
+        try:
+            nt.ReplayTransport(os.path.join(tmp, "missing.jsonl"))
+            assert False, "missing records file must be rejected"
+        except OSError:
+            pass
+
+
+if __name__ == "__main__":
+    asyncio.run(main())
@@ -0,0 +1,90 @@
+import asyncio
+import logging
+
+import nekoton as nt
+from fixtures import EmptyHandler
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+address = nt.Address(
+    "0:0000000000000000000000000000000000000000000000000000000000000000"
+)
+
+
+class FlakyHandler(EmptyHandler):
+    """Fails the first `failures` account state requests."""
+
+    def __init__(self, failures: int, error: Exception):
+        super().__init__()
+        self.failures = failures
+        self.error = error
+        self.calls = 0
+
+    async def get_account_state(self, address: nt.Address):
+        self.calls += 1
+        if self.calls <= self.failures:
+            raise self.error
+        return None
+
+
+async def main():
+    retry_policy = nt.RetryPolicy(max_attempts=3, initial_backoff=0.01)
+
+    # Requests are not retried by default
+    handler = FlakyHandler(1, ConnectionError("connection refused"))
+    transport = nt.CustomTransport(handler)
+    try:
+        await transport.get_account_state(address)
+        assert False, "failed request must be raised"
+    except nt.TransportError as e:
+        assert e.retriable
+    assert handler.calls == 1
+
+    # Connection errors are retried with the policy
+    handler = FlakyHandler(2, ConnectionError("connection refused"))
+    transport = nt.CustomTransport(handler)
+    assert await transport.get_account_state(address, retry_policy=retry_policy) is None
+    assert handler.calls == 3
+
+    handler = FlakyHandler(5, TimeoutError("request timed out"))
+    transport = nt.CustomTransport(handler)
+    try:
+        await transport.get_account_state(address, retry_policy=retry_policy)
+        assert False, "request must fail after all attempts"
+    except nt.TransportError as e:
+        assert e.retriable
+    assert handler.calls == 3
+
+    # Rejected requests are not retried
+    handler = FlakyHandler(1, ValueError("invalid address"))
+    transport = nt.CustomTransport(handler)
+    try:
+        await transport.get_account_state(address, retry_policy=retry_policy)
+        assert False, "rejected request must be raised"
+    except nt.TransportError as e:
+        assert not e.retriable
+    assert handler.calls == 1
+
+    # Patterns replace the default retry condition
+    overloaded = nt.RetryPolicy(
+        max_attempts=3, initial_backoff=0.01, retry_on=["overloaded"]
+    )
+    handler = FlakyHandler(2, RuntimeError("endpoint is overloaded"))
+    transport = nt.CustomTransport(handler)
+    assert await transport.get_account_state(address, retry_policy=overloaded) is None
+    assert handler.calls == 3
+
+    handler = FlakyHandler(1, ConnectionError("connection refused"))
+    transport = nt.CustomTransport(handler)
+    try:
+        await transport.get_account_state(address, retry_policy=overloaded)
+        assert False, "unmatched error must be raised"
+    except nt.TransportError:
+        pass
+    assert handler.calls == 1
+
+
+if __name__ == "__main__":
+    asyncio.run(main())
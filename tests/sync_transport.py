@@ -0,0 +1,89 @@
+import asyncio
+import logging
+
+import nekoton as nt
+from fixtures import fund, make_config
+from nekoton.contracts.ever_wallet import EverWallet, _send_transaction
+
+FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
+logging.basicConfig(format=FORMAT)
+logging.getLogger().setLevel(logging.DEBUG)
+
+receiver_addr = nt.Address(
+    "0:2222222222222222222222222222222222222222222222222222222222222222"
+)
+
+
+def main():
+    config = make_config()
+
+    local = nt.LocalTransport(config)
+    transport = local.sync
+    assert transport.get_blockchain_config().global_id == config.global_id
+
+    keypair = nt.KeyPair.generate()
+    state_init = EverWallet.compute_state_init(keypair.public_key)
+    wallet_addr = state_init.compute_address(0)
+
+    executor = nt.TransactionExecutor(config)
+    local.set_account(wallet_addr, fund(executor, wallet_addr, nt.Tokens(10)))
+
+    # Messages can be sent without an event loop
+    context = transport.get_signature_context()
+    unsigned = _send_transaction.encode_external_message(
+        wallet_addr,
+        input={
+            "dest": receiver_addr,
+            "value": nt.Tokens(1),
+            "bounce": False,
+            "flags": 3,
+            "payload": nt.Cell(),
+        },
+        public_key=keypair.public_key,
+        state_init=state_init,
+    )
+
+    # Fees are estimated on top of the current state with a fake signature
+    fees = transport.estimate_fees(unsigned)
+    assert fees.compute_phase is not None
+    assert fees.gas_used == fees.compute_phase.gas_used > 0
+    assert nt.Tokens(0) < fees.total_fees < nt.Tokens(1)
+    assert len(fees.out_msgs) == 1
+
+    internal = nt.Message(
+        header=nt.InternalMessageHeader(
+            value=nt.Tokens(1), dst=wallet_addr, src=receiver_addr, bounce=False
+        )
+    )
+    try:
+        transport.estimate_fees(internal)
+        assert False, "internal message must be rejected"
+    except ValueError:
+        pass
+
+    message = unsigned.sign(keypair, context)
+
+    pending = transport.send_external_message_nowait(message)
+    tx = transport.wait_pending_message(pending)
+    assert tx is not None
+    assert not tx.aborted
+
+    with transport.iter_transactions(wallet_addr) as history:
+        assert list(history) == [tx]
+
+    code_hash = state_init.code_hash
+    assert list(transport.iter_accounts_by_code_hash(code_hash)) == [wallet_addr]
+
+    # Blocking calls are rejected inside a running event loop
+    async def inside_loop():
+        try:
+            transport.get_account_state(wallet_addr)
+            assert False, "blocking call inside an event loop must be rejected"
+        except RuntimeError:
+            pass
+
+    asyncio.run(inside_loop())
+
+
+if __name__ == "__main__":
+    main()
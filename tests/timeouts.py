@@ -1,32 +1,92 @@
 import asyncio
 import logging
+import time
 
 import nekoton as nt
+from fixtures import EmptyHandler
 
 FORMAT = "%(levelname)s %(name)s %(asctime)-15s %(filename)s:%(lineno)d %(message)s"
 logging.basicConfig(format=FORMAT)
 logging.getLogger().setLevel(logging.DEBUG)
 
+address = nt.Address(
+    "0:0000000000000000000000000000000000000000000000000000000000000000"
+)
+
+
+class SlowHandler(EmptyHandler):
+    """Delays account state requests."""
+
+    def __init__(self):
+        super().__init__()
+        self.delay = 0.0
+
+    async def get_account_state(self, address: nt.Address):
+        await asyncio.sleep(self.delay)
+        return None
+
 
 async def main():
-    clock = nt.Clock()
-    transport = nt.ProtoTransport(endpoint="https://jrpc.everwallet.net", clock=clock)
-    await transport.check_connection()
-
-    address = nt.Address(
-        "0:0000000000000000000000000000000000000000000000000000000000000000"
-    )
-
-    external_message = nt.SignedExternalMessage(
-        address,
-        clock.now_sec + 60,
-        body=None,
-        state_init=None,
-    )
-    tx = await transport.send_external_message(external_message)
-    print(tx)
-    assert tx is not None
-    await transport.trace_transaction(tx).wait()
+    handler = SlowHandler()
+    transport = nt.CustomTransport(handler, timeout=1.0)
+
+    # Requests are limited by the transport-wide timeout
+    handler.delay = 10.0
+    started_at = time.monotonic()
+    try:
+        await transport.get_account_state(address)
+        assert False, "request must time out"
+    except nt.Timeout:
+        pass
+    assert time.monotonic() - started_at < 5
+
+    # Per-call timeout overrides the default one
+    try:
+        await transport.get_account_state(address, timeout=0.01)
+        assert False, "request must time out"
+    except TimeoutError:
+        pass
+
+    handler.delay = 1.5
+    assert await transport.get_account_state(address, timeout=5.0) is None
+
+    for invalid in [-1.0, float("nan")]:
+        try:
+            transport.get_account_state(address, timeout=invalid)
+            assert False, f"invalid timeout must be rejected: {invalid}"
+        except ValueError:
+            pass
+
+    # Cancelled requests don't affect the transport
+    handler.delay = 10.0
+    task = asyncio.ensure_future(transport.get_account_state(address))
+    await asyncio.sleep(0.1)
+    task.cancel()
+    try:
+        await task
+        assert False, "request must be cancelled"
+    except asyncio.CancelledError:
+        pass
+
+    handler.delay = 0.0
+    assert await transport.get_account_state(address) is None
+
+    # Waiting for the transaction is limited by the per-call timeout
+    expired = nt.SignedExternalMessage(address, transport.clock.now_sec - 1)
+    try:
+        await transport.send_external_message(expired)
+        assert False, "expired message must be rejected"
+    except nt.MessageExpired:
+        pass
+    assert handler.sent == []
+
+    message = nt.SignedExternalMessage(address, transport.clock.now_sec + 60)
+    try:
+        await transport.send_external_message(message, timeout=0.5)
+        assert False, "waiting for the transaction must time out"
+    except nt.Timeout as e:
+        assert not isinstance(e, nt.MessageExpired)
+    assert len(handler.sent) == 1
 
 
 if __name__ == "__main__":